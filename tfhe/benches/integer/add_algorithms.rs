@@ -0,0 +1,82 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use itertools::iproduct;
+use rand::Rng;
+use tfhe::integer::keycache::KEY_CACHE;
+use tfhe::integer::{RadixCiphertextBig, ServerKey};
+use tfhe::shortint::keycache::NamedParam;
+use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+
+// Powers of two, as the work efficient algorithm requires
+const NUM_BLOCKS: [usize; 4] = [4, 8, 16, 32];
+// Thread counts above the available parallelism are skipped
+const NUM_THREADS: [usize; 5] = [1, 2, 4, 8, 16];
+
+type AddFn = fn(&ServerKey, &mut RadixCiphertextBig, &RadixCiphertextBig);
+
+// The default addition chooses its carry propagation from the numbers of blocks and threads
+const ADD_ALGORITHMS: [(&str, AddFn); 2] = [
+    ("default", ServerKey::add_assign_parallelized),
+    (
+        "work_efficient",
+        ServerKey::add_assign_parallelized_work_efficient,
+    ),
+];
+
+/// Benches the default parallel addition against the work efficient one, for each number of
+/// blocks and each number of threads, so that the carry propagation chosen by default can be
+/// checked against the timings of the machine at hand.
+///
+/// The inputs have empty carries, so only the addition algorithm itself is measured. Each
+/// addition runs within a rayon thread pool of the given number of threads.
+fn add_algorithms(c: &mut Criterion) {
+    let bench_name = "add_algorithms";
+    let mut bench_group = c.benchmark_group(bench_name);
+    bench_group
+        .sample_size(15)
+        .measurement_time(std::time::Duration::from_secs(30));
+    let mut rng = rand::thread_rng();
+
+    let param = PARAM_MESSAGE_2_CARRY_2;
+    let param_name = param.name();
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+
+    let available_threads = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let num_threads_iter = NUM_THREADS
+        .into_iter()
+        .filter(|num_threads| *num_threads <= available_threads);
+
+    for num_threads in num_threads_iter {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .unwrap();
+
+        for (num_block, (add_name, add_fn)) in iproduct!(NUM_BLOCKS, ADD_ALGORITHMS) {
+            let bench_id = format!(
+                "{bench_name}::{param_name}::{add_name}::{num_block}_blocks::\
+                {num_threads}_threads"
+            );
+            bench_group.bench_function(&bench_id, |b| {
+                let encrypt_two_values = || {
+                    let ct_0 = cks.encrypt_radix(rng.gen::<u64>(), num_block);
+                    let ct_1 = cks.encrypt_radix(rng.gen::<u64>(), num_block);
+                    (ct_0, ct_1)
+                };
+
+                b.iter_batched(
+                    encrypt_two_values,
+                    |(mut ct_0, ct_1)| {
+                        pool.install(|| add_fn(&sks, &mut ct_0, &ct_1));
+                    },
+                    criterion::BatchSize::SmallInput,
+                )
+            });
+        }
+    }
+
+    bench_group.finish()
+}
+
+criterion_group!(add_algorithm_comparison, add_algorithms);
+
+criterion_main!(add_algorithm_comparison);
@@ -439,6 +439,54 @@ fn bench_server_key_binary_scalar_function_clean_inputs<F>(
     bench_group.finish()
 }
 
+/// Benches the sum of many ciphertexts, reduced with the carry-save tree of
+/// `unchecked_sum_ciphertexts_parallelized` and with a tree of pairwise additions
+fn sum_ciphertexts_parallelized(c: &mut Criterion) {
+    const NB_OPERANDS: usize = 16;
+
+    let bench_name = "sum_ciphertexts_parallelized";
+    let mut bench_group = c.benchmark_group(bench_name);
+    bench_group
+        .sample_size(15)
+        .measurement_time(std::time::Duration::from_secs(60));
+    let mut rng = rand::thread_rng();
+
+    for (param, num_block, bit_size) in ParamsAndNumBlocksIter::default() {
+        let param_name = param.name();
+        let (cks, sks) = KEY_CACHE.get_from_params(param);
+
+        let mut encrypt_operands = || {
+            (0..NB_OPERANDS)
+                .map(|_| cks.encrypt_radix(rng.gen::<u64>(), num_block))
+                .collect::<Vec<RadixCiphertextBig>>()
+        };
+
+        let bench_id = format!("{bench_name}::carry_save::{param_name}::{bit_size}_bits");
+        bench_group.bench_function(&bench_id, |b| {
+            b.iter_batched(
+                &mut encrypt_operands,
+                |ctxts| {
+                    sks.unchecked_sum_ciphertexts_parallelized(&ctxts);
+                },
+                criterion::BatchSize::SmallInput,
+            )
+        });
+
+        let bench_id = format!("{bench_name}::pairwise::{param_name}::{bit_size}_bits");
+        bench_group.bench_function(&bench_id, |b| {
+            b.iter_batched(
+                &mut encrypt_operands,
+                |ctxts| {
+                    sks.default_binary_op_seq_parallelized(&ctxts, ServerKey::add_parallelized);
+                },
+                criterion::BatchSize::SmallInput,
+            )
+        });
+    }
+
+    bench_group.finish()
+}
+
 macro_rules! define_server_key_bench_unary_fn (
     (method_name: $server_key_method:ident, display_name:$name:ident) => {
         fn $server_key_method(c: &mut Criterion) {
@@ -607,6 +655,10 @@ define_server_key_bench_unary_fn!(
     method_name: full_propagate_parallelized,
     display_name: carry_propagation
 );
+define_server_key_bench_unary_fn!(
+    method_name: full_propagate_lookahead,
+    display_name: carry_propagation
+);
 
 define_server_key_bench_fn!(method_name: unchecked_max, display_name: max);
 define_server_key_bench_fn!(method_name: unchecked_min, display_name: min);
@@ -777,7 +829,13 @@ criterion_group!(
     unchecked_bitxor_parallelized,
 );
 
-criterion_group!(misc, full_propagate, full_propagate_parallelized);
+criterion_group!(
+    misc,
+    full_propagate,
+    full_propagate_parallelized,
+    full_propagate_lookahead,
+    sum_ciphertexts_parallelized,
+);
 
 // User-oriented benchmark group.
 // This gather all the operations that a high-level user could use.
@@ -33,6 +33,8 @@ fn gen_c_api() {
         "boolean-c-api",
         #[cfg(feature = "shortint-c-api")]
         "shortint-c-api",
+        #[cfg(feature = "integer-c-api")]
+        "integer-c-api",
         #[cfg(feature = "high-level-c-api")]
         "high-level-c-api",
         #[cfg(feature = "boolean")]
@@ -0,0 +1,157 @@
+#include "tfhe.h"
+#include <assert.h>
+#include <inttypes.h>
+#include <stdio.h>
+#include <stdlib.h>
+
+typedef int (*BinaryCallback)(const IntegerServerKey *, IntegerRadixCiphertext *,
+                              IntegerRadixCiphertext *, IntegerRadixCiphertext **);
+
+typedef int (*BinaryAssignCallback)(const IntegerServerKey *, IntegerRadixCiphertext *,
+                                    IntegerRadixCiphertext *);
+
+void test_integer_binary_op(const IntegerRadixClientKey *cks, const IntegerServerKey *sks,
+                            const uint64_t modulus, uint64_t (*c_fun)(uint64_t, uint64_t),
+                            BinaryCallback api_fun) {
+  uint64_t values[] = {0, 1, 17, 146, modulus - 1};
+  size_t num_values = sizeof(values) / sizeof(values[0]);
+
+  for (size_t i = 0; i < num_values; ++i) {
+    for (size_t j = 0; j < num_values; ++j) {
+      IntegerRadixCiphertext *ct_left = NULL;
+      IntegerRadixCiphertext *ct_right = NULL;
+      IntegerRadixCiphertext *ct_result = NULL;
+
+      uint64_t left = values[i] % modulus;
+      uint64_t right = values[j] % modulus;
+
+      uint64_t expected = c_fun(left, right) % modulus;
+
+      int encrypt_left_ok = integer_radix_client_key_encrypt(cks, left, &ct_left);
+      assert(encrypt_left_ok == 0);
+
+      int encrypt_right_ok = integer_radix_client_key_encrypt(cks, right, &ct_right);
+      assert(encrypt_right_ok == 0);
+
+      int api_call_ok = api_fun(sks, ct_left, ct_right, &ct_result);
+      assert(api_call_ok == 0);
+
+      uint64_t decrypted_result = -1;
+
+      int decrypt_ok = integer_radix_client_key_decrypt(cks, ct_result, &decrypted_result);
+      assert(decrypt_ok == 0);
+
+      assert(decrypted_result == expected);
+
+      destroy_integer_radix_ciphertext(ct_left);
+      destroy_integer_radix_ciphertext(ct_right);
+      destroy_integer_radix_ciphertext(ct_result);
+    }
+  }
+}
+
+void test_integer_binary_assign_op(const IntegerRadixClientKey *cks, const IntegerServerKey *sks,
+                                   const uint64_t modulus, uint64_t (*c_fun)(uint64_t, uint64_t),
+                                   BinaryAssignCallback api_fun) {
+  uint64_t values[] = {0, 1, 17, 146, modulus - 1};
+  size_t num_values = sizeof(values) / sizeof(values[0]);
+
+  for (size_t i = 0; i < num_values; ++i) {
+    for (size_t j = 0; j < num_values; ++j) {
+      IntegerRadixCiphertext *ct_left_and_result = NULL;
+      IntegerRadixCiphertext *ct_right = NULL;
+
+      uint64_t left = values[i] % modulus;
+      uint64_t right = values[j] % modulus;
+
+      uint64_t expected = c_fun(left, right) % modulus;
+
+      int encrypt_left_ok = integer_radix_client_key_encrypt(cks, left, &ct_left_and_result);
+      assert(encrypt_left_ok == 0);
+
+      int encrypt_right_ok = integer_radix_client_key_encrypt(cks, right, &ct_right);
+      assert(encrypt_right_ok == 0);
+
+      int api_call_ok = api_fun(sks, ct_left_and_result, ct_right);
+      assert(api_call_ok == 0);
+
+      uint64_t decrypted_result = -1;
+
+      int decrypt_ok =
+          integer_radix_client_key_decrypt(cks, ct_left_and_result, &decrypted_result);
+      assert(decrypt_ok == 0);
+
+      assert(decrypted_result == expected);
+
+      destroy_integer_radix_ciphertext(ct_left_and_result);
+      destroy_integer_radix_ciphertext(ct_right);
+    }
+  }
+}
+
+uint64_t add(uint64_t left, uint64_t right) { return left + right; }
+
+void test_mixed_ciphertext_kinds_fail(const IntegerRadixClientKey *cks,
+                                      const IntegerServerKey *sks) {
+  IntegerRadixCiphertext *ct_big = NULL;
+  IntegerRadixCiphertext *ct_small = NULL;
+  IntegerRadixCiphertext *ct_result = NULL;
+
+  int encrypt_big_ok = integer_radix_client_key_encrypt(cks, 3, &ct_big);
+  assert(encrypt_big_ok == 0);
+
+  int encrypt_small_ok = integer_radix_client_key_encrypt_small(cks, 5, &ct_small);
+  assert(encrypt_small_ok == 0);
+
+  int api_call_ok = integer_server_key_add_parallelized(sks, ct_big, ct_small, &ct_result);
+  assert(api_call_ok != 0);
+
+  destroy_integer_radix_ciphertext(ct_big);
+  destroy_integer_radix_ciphertext(ct_small);
+}
+
+void test_server_key(void) {
+  IntegerRadixClientKey *cks = NULL;
+  IntegerServerKey *sks = NULL;
+  ShortintParameters *params = NULL;
+
+  const uint32_t message_bits = 2;
+  const uint32_t carry_bits = 2;
+  const size_t num_blocks = 4;
+  const uint64_t modulus = (uint64_t)1 << (message_bits * num_blocks);
+
+  int get_params_ok = shortint_get_parameters(message_bits, carry_bits, &params);
+  assert(get_params_ok == 0);
+
+  int gen_keys_ok = integer_gen_keys_radix(params, num_blocks, &cks, &sks);
+  assert(gen_keys_ok == 0);
+
+  printf("add_parallelized\n");
+  test_integer_binary_op(cks, sks, modulus, add,
+                         (BinaryCallback)integer_server_key_add_parallelized);
+
+  printf("smart_add_parallelized\n");
+  test_integer_binary_op(cks, sks, modulus, add,
+                         (BinaryCallback)integer_server_key_smart_add_parallelized);
+
+  printf("add_assign_parallelized\n");
+  test_integer_binary_assign_op(cks, sks, modulus, add,
+                                (BinaryAssignCallback)integer_server_key_add_assign_parallelized);
+
+  printf("smart_add_assign_parallelized\n");
+  test_integer_binary_assign_op(
+      cks, sks, modulus, add,
+      (BinaryAssignCallback)integer_server_key_smart_add_assign_parallelized);
+
+  printf("mixed ciphertext kinds\n");
+  test_mixed_ciphertext_kinds_fail(cks, sks);
+
+  destroy_integer_radix_client_key(cks);
+  destroy_integer_server_key(sks);
+  destroy_shortint_parameters(params);
+}
+
+int main(void) {
+  test_server_key();
+  return EXIT_SUCCESS;
+}
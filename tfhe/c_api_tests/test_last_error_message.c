@@ -0,0 +1,57 @@
+#include "tfhe.h"
+#include <assert.h>
+#include <inttypes.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+
+void test_last_error_message(void) {
+  ShortintClientKey *cks = NULL;
+  ShortintServerKey *sks = NULL;
+  ShortintParameters *params = NULL;
+  ShortintCiphertext *ct = NULL;
+  char message[256];
+
+  int get_params_ok = shortint_get_parameters(2, 2, &params);
+  assert(get_params_ok == 0);
+
+  int gen_keys_ok = shortint_gen_keys_with_parameters(params, &cks, &sks);
+  assert(gen_keys_ok == 0);
+
+  // A failing call keeps its error message
+  int encrypt_ok = shortint_client_key_encrypt(NULL, 3, &ct);
+  assert(encrypt_ok != 0);
+
+  int get_message_ok = tfhe_last_error_message(message, sizeof(message));
+  assert(get_message_ok == 0);
+  printf("last error message: %s\n", message);
+  assert(strstr(message, "pointer is null") != NULL);
+
+  // The message is truncated to the given length
+  char truncated_message[8];
+  get_message_ok = tfhe_last_error_message(truncated_message, sizeof(truncated_message));
+  assert(get_message_ok == 0);
+  assert(strlen(truncated_message) == sizeof(truncated_message) - 1);
+  assert(strncmp(truncated_message, message, sizeof(truncated_message) - 1) == 0);
+
+  get_message_ok = tfhe_last_error_message(NULL, sizeof(message));
+  assert(get_message_ok != 0);
+
+  // A successful call clears it
+  encrypt_ok = shortint_client_key_encrypt(cks, 3, &ct);
+  assert(encrypt_ok == 0);
+
+  get_message_ok = tfhe_last_error_message(message, sizeof(message));
+  assert(get_message_ok == 0);
+  assert(strlen(message) == 0);
+
+  destroy_shortint_client_key(cks);
+  destroy_shortint_server_key(sks);
+  destroy_shortint_parameters(params);
+  destroy_shortint_ciphertext(ct);
+}
+
+int main(void) {
+  test_last_error_message();
+  return EXIT_SUCCESS;
+}
@@ -0,0 +1,163 @@
+use crate::parser::{parse_with_dot_newline, RegExpr};
+use anyhow::Result;
+use lazy_static::lazy_static;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// Default number of patterns kept by a `ParseCache`.
+pub const DEFAULT_PARSE_CACHE_CAPACITY: usize = 64;
+
+/// Cache of parsed patterns, keyed by pattern string and by whether `.`
+/// matches `\n`.
+///
+/// Parsing a pattern only depends on its string, so a service handling many
+/// requests over a small set of patterns can parse each of them once. The
+/// parsed patterns are shared behind an `Arc`, which makes them cheap to
+/// clone into parallel tasks, and the cache itself can be shared between
+/// threads.
+///
+/// At most `capacity` patterns are kept. When a new pattern is parsed while
+/// the cache is full, the least recently used pattern is evicted. Patterns
+/// that fail to parse are not cached.
+///
+/// All the functions taking a pattern string parse it through a process wide
+/// cache, see `parse_pattern`.
+pub struct ParseCache {
+    capacity: usize,
+    // Most recently used entries at the back
+    entries: Mutex<VecDeque<(CacheKey, Arc<RegExpr>)>>,
+}
+
+// The pattern and whether `.` matches `\n`
+type CacheKey = (String, bool);
+
+impl ParseCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            entries: Mutex::new(VecDeque::with_capacity(capacity)),
+        }
+    }
+
+    #[cfg(test)]
+    pub fn len(&self) -> usize {
+        self.entries.lock().unwrap().len()
+    }
+
+    #[cfg(test)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the parsed pattern, parsing it only if it is not in the cache.
+    pub(crate) fn get_or_parse(
+        &self,
+        pattern: &str,
+        dot_matches_newline: bool,
+    ) -> Result<Arc<RegExpr>> {
+        if let Some(re) = self.get(pattern, dot_matches_newline) {
+            return Ok(re);
+        }
+
+        // Parsing is done without holding the lock, so another thread may
+        // insert the same pattern in the meantime, in which case its entry is
+        // kept
+        let re = Arc::new(parse_with_dot_newline(pattern, dot_matches_newline)?);
+        let mut entries = self.entries.lock().unwrap();
+        if let Some(i) = entries
+            .iter()
+            .position(|(key, _)| is_key(key, pattern, dot_matches_newline))
+        {
+            return Ok(entries[i].1.clone());
+        }
+        if self.capacity == 0 {
+            return Ok(re);
+        }
+        if entries.len() == self.capacity {
+            entries.pop_front();
+        }
+        entries.push_back(((pattern.to_string(), dot_matches_newline), re.clone()));
+        Ok(re)
+    }
+
+    fn get(&self, pattern: &str, dot_matches_newline: bool) -> Option<Arc<RegExpr>> {
+        let mut entries = self.entries.lock().unwrap();
+        let i = entries
+            .iter()
+            .position(|(key, _)| is_key(key, pattern, dot_matches_newline))?;
+        let entry = entries.remove(i).unwrap();
+        let re = entry.1.clone();
+        entries.push_back(entry);
+        Some(re)
+    }
+}
+
+impl Default for ParseCache {
+    fn default() -> Self {
+        Self::new(DEFAULT_PARSE_CACHE_CAPACITY)
+    }
+}
+
+fn is_key(key: &CacheKey, pattern: &str, dot_matches_newline: bool) -> bool {
+    key.0 == pattern && key.1 == dot_matches_newline
+}
+
+lazy_static! {
+    static ref PARSE_CACHE: ParseCache = ParseCache::default();
+}
+
+/// Parses the pattern, through a process wide `ParseCache`.
+pub(crate) fn parse_pattern(pattern: &str, dot_matches_newline: bool) -> Result<Arc<RegExpr>> {
+    PARSE_CACHE.get_or_parse(pattern, dot_matches_newline)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cache::ParseCache;
+    use crate::parser::parse;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_parse_cache_hit() {
+        let cache = ParseCache::default();
+
+        let first = cache.get_or_parse("/ab+c/", false).unwrap();
+        let second = cache.get_or_parse("/ab+c/", false).unwrap();
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(parse("/ab+c/").unwrap(), *second);
+        assert_eq!(1, cache.len());
+    }
+
+    #[test]
+    fn test_parse_cache_evicts_least_recently_used() {
+        let cache = ParseCache::new(2);
+
+        let a = cache.get_or_parse("/a/", false).unwrap();
+        let b = cache.get_or_parse("/b/", false).unwrap();
+        // Makes /b/ the least recently used
+        cache.get_or_parse("/a/", false).unwrap();
+        cache.get_or_parse("/c/", false).unwrap();
+        assert_eq!(2, cache.len());
+
+        assert!(Arc::ptr_eq(&a, &cache.get_or_parse("/a/", false).unwrap()));
+        assert!(!Arc::ptr_eq(&b, &cache.get_or_parse("/b/", false).unwrap()));
+    }
+
+    #[test]
+    fn test_parse_cache_keyed_by_dot_newline() {
+        let cache = ParseCache::default();
+
+        let dot = cache.get_or_parse("/a.b/", false).unwrap();
+        let dot_newline = cache.get_or_parse("/a.b/", true).unwrap();
+        assert!(!Arc::ptr_eq(&dot, &dot_newline));
+        assert_eq!(2, cache.len());
+    }
+
+    #[test]
+    fn test_parse_cache_error_not_cached() {
+        let cache = ParseCache::default();
+
+        assert!(cache.get_or_parse("/\\xGG/", false).is_err());
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,270 @@
+use crate::cache::parse_pattern;
+use crate::ciphertext::{number_layout, StringCiphertext};
+use crate::engine::{build_capturing_branches, or_branches};
+use crate::execution::{Execution, LazyExecution};
+use crate::result::MatchResult;
+use anyhow::{anyhow, Result};
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use tfhe::integer::{RadixCiphertextBig, ServerKey};
+
+/// Extent of the content matched by a capture group, as a triple of encrypted
+/// values `(found, start, len)`.
+///
+/// `found` is an encrypted boolean telling whether the group took part in the
+/// match. `start` is the content position of the first character matched by
+/// the group and `len` the number of characters it matched. When `found` is
+/// false, both `start` and `len` are an encrypted 0, so `found` must be used to
+/// tell apart "not captured" from "captured the empty string at position 0".
+pub type CaptureExtent = (RadixCiphertextBig, RadixCiphertextBig, RadixCiphertextBig);
+
+/// Matches the pattern against the content and returns the extent of each of
+/// its capture groups.
+///
+/// Capture groups are the parenthesized sub-expressions of the pattern,
+/// numbered in order of their opening parentheses. The result always holds one
+/// extent per group, as the number of groups is known from the pattern alone.
+/// Because the content is encrypted, the extents are encrypted positions and
+/// lengths rather than substrings.
+///
+/// The extents are those of a single match, the same one `find` selects: the
+/// leftmost-longest one, i.e. among the matches starting at the leftmost
+/// position where the pattern matches, the one ending last. So the
+/// repetitions are greedy, `(a+)` capturing all of `aaa`. When several ways of
+/// matching end at the same position, the first one in the order the pattern
+/// is written gives the extents, with alternatives tried from left to right
+/// and repetitions preferring more iterations, e.g. `(a*)(a*)` captures `aa`
+/// then the empty string in `aa`. A group inside a repetition captures its
+/// last iteration. If the pattern does not match, every group has `found` set
+/// to false.
+///
+/// The positions and lengths have as many blocks as the content characters,
+/// and an error is returned if the content is too long for its positions to
+/// be represented on them (256 characters or more with the default keys).
+#[allow(dead_code)]
+pub fn captures(
+    sk: &ServerKey,
+    content: &StringCiphertext,
+    pattern: &str,
+) -> Result<Vec<CaptureExtent>> {
+    let num_blocks = check_content_len(sk, content)?;
+
+    let re = parse_pattern(pattern, false)?;
+    let groups_count = re.groups_count();
+
+    // The branches starting at each position are tried from the one ending
+    // last, the sort being stable so the branches ending at the same position
+    // keep the order the pattern is written in
+    let branches: Vec<_> = (0..content.len())
+        .flat_map(|i| {
+            let mut branches = build_capturing_branches(content, &re, i);
+            branches.sort_by_key(|(_, end, _)| Reverse(*end));
+            branches
+        })
+        .collect();
+
+    let mut exec = Execution::new(sk.clone(), content);
+    let mut res: Vec<CaptureExtent> = (0..groups_count)
+        .map(|_| empty_extent(sk, num_blocks))
+        .collect();
+
+    // Only the first matching branch contributes its extents, so at most one
+    // term of each sum below is non zero
+    let mut matched = exec.ct_false();
+    for (branch, _, branch_captures) in branches {
+        let branch_res = branch(&mut exec);
+        let not_matched_before = exec.ct_not(matched.clone());
+        let is_first = exec.ct_and(branch_res.clone(), not_matched_before).0;
+
+        for (extent, capture) in res.iter_mut().zip(branch_captures) {
+            if let Some((start, end)) = capture {
+                add_extent_if(sk, extent, &is_first, start, end);
+            }
+        }
+
+        matched = exec.ct_or(matched, branch_res);
+    }
+    for extent in res.iter_mut() {
+        propagate_extent(sk, extent);
+    }
+
+    exec.log_ct_operations();
+    Ok(res)
+}
+
+/// Matches the pattern against the content and returns the extent of the
+/// match, bundled as a `MatchResult`.
+///
+/// The match is the leftmost-longest one: among the matches starting at the
+/// leftmost position where the pattern matches, the one ending last. So the
+/// repetitions are greedy, `a+` matching all of `aaa` and `.*` running up to
+/// the end of the content. This is the match `captures` selects, so a group
+/// around the whole pattern captures the same extent. If the pattern does not
+/// match, `found` is false and both `start` and `len` are an encrypted 0.
+///
+/// The positions and lengths have as many blocks as the content characters,
+/// and an error is returned if the content is too long for its positions to
+/// be represented on them (256 characters or more with the default keys).
+#[allow(dead_code)]
+pub fn find(sk: &ServerKey, content: &StringCiphertext, pattern: &str) -> Result<MatchResult> {
+    let num_blocks = check_content_len(sk, content)?;
+
+    let re = parse_pattern(pattern, false)?;
+
+    let mut exec = Execution::new(sk.clone(), content);
+    let mut res = empty_extent(sk, num_blocks);
+
+    // Only the longest match at the leftmost matching position is selected, so
+    // at most one term of each sum below is non zero
+    let mut matched = exec.ct_false();
+    for i in 0..content.len() {
+        let mut branches_by_end: BTreeMap<usize, Vec<LazyExecution>> = BTreeMap::new();
+        for (branch, end, _) in build_capturing_branches(content, &re, i) {
+            branches_by_end.entry(end).or_default().push(branch);
+        }
+
+        let not_matched_before = exec.ct_not(matched.clone());
+        let mut matched_longer = exec.ct_false();
+        for (end, branches) in branches_by_end.into_iter().rev() {
+            let end_res = or_branches(&mut exec, &branches);
+            let not_matched_longer = exec.ct_not(matched_longer.clone());
+            let is_longest = exec.ct_and(end_res.clone(), not_matched_longer);
+            let is_selected = exec.ct_and(is_longest, not_matched_before.clone()).0;
+            add_extent_if(sk, &mut res, &is_selected, i, end);
+
+            matched_longer = exec.ct_or(matched_longer, end_res);
+        }
+
+        matched = exec.ct_or(matched, matched_longer);
+    }
+    propagate_extent(sk, &mut res);
+
+    exec.log_ct_operations();
+    let (found, start, len) = res;
+    Ok(MatchResult { found, start, len })
+}
+
+// Checks that the content positions fit in the returned positions, and
+// returns their number of blocks
+fn check_content_len(sk: &ServerKey, content: &[RadixCiphertextBig]) -> Result<usize> {
+    let (num_blocks, max_position) = number_layout(sk, content, 0);
+    if content.len() as u64 > max_position {
+        return Err(anyhow!(
+            "content too long, at most {} characters are supported",
+            max_position
+        ));
+    }
+    Ok(num_blocks)
+}
+
+fn empty_extent(sk: &ServerKey, num_blocks: usize) -> CaptureExtent {
+    (
+        sk.create_trivial_zero_radix(num_blocks),
+        sk.create_trivial_zero_radix(num_blocks),
+        sk.create_trivial_zero_radix(num_blocks),
+    )
+}
+
+// Adds the extent from start to end to `extent` if `is_selected` is true, and
+// nothing otherwise
+fn add_extent_if(
+    sk: &ServerKey,
+    extent: &mut CaptureExtent,
+    is_selected: &RadixCiphertextBig,
+    start: usize,
+    end: usize,
+) {
+    let (found, ct_start, ct_len) = extent;
+    let mut is_selected = is_selected.clone();
+    let mut ct_branch_start = sk.smart_scalar_mul(&mut is_selected, start as u64);
+    let mut ct_branch_len = sk.smart_scalar_mul(&mut is_selected, (end - start) as u64);
+    sk.smart_add_assign(found, &mut is_selected);
+    sk.smart_add_assign(ct_start, &mut ct_branch_start);
+    sk.smart_add_assign(ct_len, &mut ct_branch_len);
+}
+
+fn propagate_extent(sk: &ServerKey, extent: &mut CaptureExtent) {
+    let (found, ct_start, ct_len) = extent;
+    sk.full_propagate(found);
+    sk.full_propagate(ct_start);
+    sk.full_propagate(ct_len);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::captures::{captures, find};
+    use crate::engine::tests::KEYS;
+    use test_case::test_case;
+
+    use crate::ciphertext::{encrypt_str, StringCiphertext};
+
+    #[test_case("abcd", "/(ab)(cd)/", &[(1, 0, 2), (1, 2, 2)] ; "two adjacent groups")]
+    #[test_case("xabcd", "/(ab)(cd)/", &[(1, 1, 2), (1, 3, 2)] ; "match not at the start")]
+    #[test_case("abce", "/(ab)(cd)/", &[(0, 0, 0), (0, 0, 0)] ; "no match")]
+    #[test_case("abcd", "/(a(b))c/", &[(1, 0, 2), (1, 1, 1)] ; "nested groups")]
+    #[test_case("bd", "/(a)|(b)/", &[(0, 0, 0), (1, 0, 1)] ; "group not taking part")]
+    #[test_case("abab", "/^(ab)*$/", &[(1, 2, 2)] ; "last iteration of a repetition")]
+    #[test_case("abcd", "/(?:ab)(c)/", &[(1, 2, 1)] ; "non-capturing group not numbered")]
+    #[test_case("xaab", "/(a+)(b?)/", &[(1, 1, 2), (1, 3, 1)] ; "greedy repetitions")]
+    #[test_case("aa", "/(a*)(a*)/", &[(1, 0, 2), (1, 2, 0)] ; "same end greedy")]
+    fn test_captures(content: &str, pattern: &str, exp: &[(u64, u64, u64)]) {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, content).unwrap();
+        let ct_res = captures(&KEYS.1, &ct_content, pattern).unwrap();
+
+        let got: Vec<(u64, u64, u64)> = ct_res
+            .iter()
+            .map(|(found, start, len)| {
+                (
+                    KEYS.0.decrypt(found),
+                    KEYS.0.decrypt(start),
+                    KEYS.0.decrypt(len),
+                )
+            })
+            .collect();
+        assert_eq!(exp, got.as_slice());
+    }
+
+    #[test_case("xabcd", "/b+c/", (1, 2, 2) ; "match not at the start")]
+    #[test_case("abab", "/b|ab/", (1, 0, 2) ; "leftmost match")]
+    #[test_case("xaaab", "/a+/", (1, 1, 3) ; "greedy repetition")]
+    #[test_case("abcd", "/b.*/", (1, 1, 3) ; "trailing any char repetition")]
+    #[test_case("abcd", "/a|ab|abc/", (1, 0, 3) ; "longest alternative")]
+    #[test_case("abcd", "/ab?/", (1, 0, 2) ; "optional")]
+    #[test_case("abcd", "/x/", (0, 0, 0) ; "no match")]
+    fn test_find(content: &str, pattern: &str, exp: (u64, u64, u64)) {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, content).unwrap();
+        let ct_res = find(&KEYS.1, &ct_content, pattern).unwrap();
+
+        let got = (
+            KEYS.0.decrypt(&ct_res.found),
+            KEYS.0.decrypt(&ct_res.start),
+            KEYS.0.decrypt(&ct_res.len),
+        );
+        assert_eq!(exp, got);
+    }
+
+    // A group around the whole pattern captures the match find selects
+    #[test_case("xaaab", "/(a+)/", (1, 1, 3) ; "one or more")]
+    #[test_case("aab", "/(a*)/", (1, 0, 2) ; "zero or more")]
+    #[test_case("abcd", "/(a|ab)/", (1, 0, 2) ; "alternatives")]
+    #[test_case("abcd", "/(x)/", (0, 0, 0) ; "no match")]
+    fn test_captures_and_find_lengths(content: &str, pattern: &str, exp: (u64, u64, u64)) {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, content).unwrap();
+        let ct_captures = captures(&KEYS.1, &ct_content, pattern).unwrap();
+        let ct_find = find(&KEYS.1, &ct_content, pattern).unwrap();
+
+        let (found, start, len) = &ct_captures[0];
+        let got_captures: (u64, u64, u64) = (
+            KEYS.0.decrypt(found),
+            KEYS.0.decrypt(start),
+            KEYS.0.decrypt(len),
+        );
+        let got_find: (u64, u64, u64) = (
+            KEYS.0.decrypt(&ct_find.found),
+            KEYS.0.decrypt(&ct_find.start),
+            KEYS.0.decrypt(&ct_find.len),
+        );
+        assert_eq!(exp, got_captures);
+        assert_eq!(exp, got_find);
+    }
+}
@@ -1,20 +1,678 @@
 use anyhow::{anyhow, Result};
-use tfhe::integer::{gen_keys_radix, RadixCiphertextBig, RadixClientKey, ServerKey};
-use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::ops::Deref;
+use tfhe::integer::ciphertext::RadixCiphertext;
+use tfhe::integer::{
+    gen_keys_radix, gen_keys_radix_compressed, CompressedServerKey, IntegerCiphertext,
+    RadixCiphertextBig, RadixClientKey, ServerKey,
+};
+use tfhe::shortint::ciphertext::{BootstrapKeyswitch, KeyswitchBootstrap};
+use tfhe::shortint::parameters::{PBSParameters, PARAM_MESSAGE_1_CARRY_1, PARAM_MESSAGE_2_CARRY_2};
+use tfhe::shortint::PBSOrderMarker;
 
-pub type StringCiphertext = Vec<RadixCiphertextBig>;
+// Number of radix blocks of each encrypted character by default, which the
+// engine compares against trivially encrypted characters of the same width
+const CHAR_NUM_BLOCKS: usize = 4;
+
+// Number of bits of an ascii character, which are all `encrypt_str_packed`
+// encrypts
+const ASCII_NUM_BITS: usize = 7;
+
+/// Encrypted string, made of one encrypted character per content position.
+///
+/// Only the characters are encrypted: the length of the string is public, as
+/// it is the number of ciphertexts. All the characters have the same number of
+/// blocks, as mixing characters of different widths would make the
+/// comparisons fail: this is checked whenever a string is built, extended or
+/// deserialized. The engine functions take it as content, and it dereferences
+/// to a slice of the encrypted characters.
+///
+/// The characters are `RadixCiphertextBig` ciphertexts unless told otherwise,
+/// see `encrypt_str_small`.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(
+    bound(
+        serialize = "",
+        deserialize = "RadixCiphertext<PBSOrder>: IntegerCiphertext"
+    ),
+    try_from = "Vec<RadixCiphertext<PBSOrder>>"
+)]
+pub struct StringCiphertext<PBSOrder: PBSOrderMarker = KeyswitchBootstrap>(
+    Vec<RadixCiphertext<PBSOrder>>,
+);
+
+impl<PBSOrder: PBSOrderMarker> StringCiphertext<PBSOrder> {
+    /// Creates an encrypted string without any characters, to be extended.
+    pub fn empty() -> Self {
+        Self(vec![])
+    }
+
+    /// Returns the number of characters, which is not encrypted.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns whether there are no characters, which is not encrypted.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<PBSOrder: PBSOrderMarker> Default for StringCiphertext<PBSOrder> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<PBSOrder: PBSOrderMarker> Deref for StringCiphertext<PBSOrder> {
+    type Target = [RadixCiphertext<PBSOrder>];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Builds an encrypted string from encrypted characters, checking that all of
+/// them have the same number of blocks.
+impl<PBSOrder> TryFrom<Vec<RadixCiphertext<PBSOrder>>> for StringCiphertext<PBSOrder>
+where
+    PBSOrder: PBSOrderMarker,
+    RadixCiphertext<PBSOrder>: IntegerCiphertext,
+{
+    type Error = anyhow::Error;
+
+    fn try_from(chars: Vec<RadixCiphertext<PBSOrder>>) -> Result<Self> {
+        check_char_num_blocks(&chars, char_num_blocks(&chars))?;
+        Ok(Self(chars))
+    }
+}
+
+/// Same as `TryFrom<Vec<_>>`, cloning the characters.
+impl<PBSOrder> TryFrom<&[RadixCiphertext<PBSOrder>]> for StringCiphertext<PBSOrder>
+where
+    PBSOrder: PBSOrderMarker,
+    RadixCiphertext<PBSOrder>: IntegerCiphertext,
+{
+    type Error = anyhow::Error;
+
+    fn try_from(chars: &[RadixCiphertext<PBSOrder>]) -> Result<Self> {
+        Self::try_from(chars.to_vec())
+    }
+}
+
+/// # Panics
+///
+/// Panics if the characters don't all have the same number of blocks, use
+/// `TryFrom` to get an error instead.
+impl<PBSOrder> FromIterator<RadixCiphertext<PBSOrder>> for StringCiphertext<PBSOrder>
+where
+    PBSOrder: PBSOrderMarker,
+    RadixCiphertext<PBSOrder>: IntegerCiphertext,
+{
+    fn from_iter<I: IntoIterator<Item = RadixCiphertext<PBSOrder>>>(iter: I) -> Self {
+        Self::try_from(iter.into_iter().collect::<Vec<_>>()).unwrap()
+    }
+}
+
+/// # Panics
+///
+/// Panics if the new characters don't all have the same number of blocks as
+/// the characters of the string.
+impl<PBSOrder> Extend<RadixCiphertext<PBSOrder>> for StringCiphertext<PBSOrder>
+where
+    PBSOrder: PBSOrderMarker,
+    RadixCiphertext<PBSOrder>: IntegerCiphertext,
+{
+    fn extend<I: IntoIterator<Item = RadixCiphertext<PBSOrder>>>(&mut self, iter: I) {
+        let start = self.0.len();
+        self.0.extend(iter);
+        if let Err(err) = check_char_num_blocks(&self.0, char_num_blocks(&self.0)) {
+            self.0.truncate(start);
+            panic!("{}", err);
+        }
+    }
+}
+
+// Checks that all the characters have `num_blocks` blocks
+fn check_char_num_blocks<PBSOrder>(
+    chars: &[RadixCiphertext<PBSOrder>],
+    num_blocks: usize,
+) -> Result<()>
+where
+    PBSOrder: PBSOrderMarker,
+    RadixCiphertext<PBSOrder>: IntegerCiphertext,
+{
+    if let Some(i) = chars
+        .iter()
+        .position(|ct_char| ct_char.blocks().len() != num_blocks)
+    {
+        return Err(anyhow!(
+            "character at position {} has {} blocks instead of {}",
+            i,
+            chars[i].blocks().len(),
+            num_blocks
+        ));
+    }
+    Ok(())
+}
 
 pub fn encrypt_str(client_key: &RadixClientKey, s: &str) -> Result<StringCiphertext> {
     if !s.is_ascii() {
         return Err(anyhow!("content contains non-ascii characters"));
     }
-    Ok(s.as_bytes()
+    Ok(encrypt_bytes(client_key, s.as_bytes()))
+}
+
+/// Same as `encrypt_str`, but the characters are `RadixCiphertextSmall`
+/// ciphertexts, i.e. with the bootstrap-then-keyswitch PBS order.
+///
+/// They can be matched with `has_match` and its variants, which are generic
+/// over the PBS order. The other functions of the engine only take
+/// `RadixCiphertextBig` characters.
+#[allow(dead_code)]
+pub fn encrypt_str_small(
+    client_key: &RadixClientKey,
+    s: &str,
+) -> Result<StringCiphertext<BootstrapKeyswitch>> {
+    if !s.is_ascii() {
+        return Err(anyhow!("content contains non-ascii characters"));
+    }
+    Ok(s.bytes()
+        .map(|byte| client_key.encrypt_small(byte as u64))
+        .collect())
+}
+
+/// Same as `encrypt_str`, but encrypts each character on just enough blocks
+/// to hold 7 bits, which is all an ascii character needs.
+///
+/// This only saves blocks with messages of 1 bit, such as the parameters of
+/// `gen_keys_packed`, where the characters take 7 blocks instead of 8. With
+/// the parameters of `gen_keys_default`, and any other messages of 2 bits or
+/// more, 7 bits take as many blocks as 8, so the characters are the same as
+/// the ones of `encrypt_str`. Fewer blocks make both the ciphertexts and the
+/// comparisons of the engine cheaper, although blocks of 1 bit make the
+/// characters larger than blocks of 2 bits overall.
+///
+/// The engine creates its constants with as many blocks as the content
+/// characters, and the comparisons against constants that don't fit in 7 bits
+/// (such as `\xFF`) are evaluated in the clear, as no ascii character equals
+/// them. The positions and counts computed over the content have as many
+/// blocks as its characters too, so they are bounded by 127 rather than 255.
+#[allow(dead_code)]
+pub fn encrypt_str_packed(client_key: &RadixClientKey, s: &str) -> Result<StringCiphertext> {
+    if !s.is_ascii() {
+        return Err(anyhow!("content contains non-ascii characters"));
+    }
+    let num_bits_in_block = client_key.parameters().message_modulus.0.ilog2() as usize;
+    let num_blocks = (ASCII_NUM_BITS + num_bits_in_block - 1) / num_bits_in_block;
+    Ok(s.bytes()
+        .map(|byte| client_key.as_ref().encrypt_radix(byte as u64, num_blocks))
+        .collect())
+}
+
+/// Encrypts each of the bytes, without any check on them being valid
+/// characters.
+pub fn encrypt_bytes(client_key: &RadixClientKey, bytes: &[u8]) -> StringCiphertext {
+    bytes
         .iter()
         .map(|byte| client_key.encrypt(*byte as u64))
-        .collect())
+        .collect()
+}
+
+/// Decrypts the content one character at a time, passing each of them in
+/// order to `f`, so that the decrypted content never has to be held in memory
+/// as a whole.
+///
+/// Stops at the first character that is not ascii, returning an error. The
+/// characters before it will already have been passed to `f`. Errors returned
+/// by `f` also stop the decryption and are passed on.
+#[allow(dead_code)]
+pub fn decrypt_str_streaming<F>(
+    client_key: &RadixClientKey,
+    content: &StringCiphertext,
+    mut f: F,
+) -> Result<()>
+where
+    F: FnMut(u8) -> Result<()>,
+{
+    for (i, ct_char) in content.iter().enumerate() {
+        let c: u64 = client_key.decrypt(ct_char);
+        if c > 0x7F {
+            return Err(anyhow!(
+                "decrypted content contains a non-ascii character at position {}",
+                i
+            ));
+        }
+        f(c as u8)?;
+    }
+    Ok(())
+}
+
+/// Serializes the encrypted string into `writer`, prefixed with its number of
+/// characters, so that a reader knows how many characters to expect.
+///
+/// As each string carries its own length, several strings can be written one
+/// after the other and read back in order with `deserialize_string_ciphertext`.
+#[allow(dead_code)]
+pub fn serialize_string_ciphertext<W: Write>(
+    content: &StringCiphertext,
+    mut writer: W,
+) -> Result<()> {
+    bincode::serialize_into(&mut writer, &(content.len() as u64))?;
+    for ct_char in content {
+        bincode::serialize_into(&mut writer, ct_char)?;
+    }
+    Ok(())
+}
+
+/// Deserializes an encrypted string written by `serialize_string_ciphertext`
+/// from `reader`, reading exactly its characters.
+///
+/// Returns an error if the input ends before the announced number of
+/// characters, or if the characters don't all have the same number of blocks.
+#[allow(dead_code)]
+pub fn deserialize_string_ciphertext<R: Read>(mut reader: R) -> Result<StringCiphertext> {
+    let len: u64 = bincode::deserialize_from(&mut reader)?;
+    // The length is not trusted to allocate memory, a truncated input fails
+    // on its first missing character instead
+    let mut chars = vec![];
+    for i in 0..len {
+        let ct_char: RadixCiphertextBig = bincode::deserialize_from(&mut reader)
+            .map_err(|err| anyhow!("character {} of {}: {}", i, len, err))?;
+        chars.push(ct_char);
+    }
+    StringCiphertext::try_from(chars)
+}
+
+/// Concatenates the segments, with the separator in between each of them.
+///
+/// As the segment lengths and the separator are public, this only assembles
+/// the ciphertexts in the clear, the separator characters being trivially
+/// encrypted. An error is returned if the separator contains non-ascii
+/// characters.
+#[allow(dead_code)]
+pub fn join(sk: &ServerKey, segments: &[StringCiphertext], sep: &str) -> Result<StringCiphertext> {
+    if !sep.is_ascii() {
+        return Err(anyhow!("separator contains non-ascii characters"));
+    }
+    // The separator characters have the width of the segments ones
+    let num_blocks = segments
+        .iter()
+        .find(|segment| !segment.is_empty())
+        .map_or(CHAR_NUM_BLOCKS, |segment| char_num_blocks(segment));
+    let ct_sep: StringCiphertext = sep
+        .bytes()
+        .map(|byte| sk.create_trivial_radix(byte as u64, num_blocks))
+        .collect();
+
+    let mut res = StringCiphertext::empty();
+    for (i, segment) in segments.iter().enumerate() {
+        if i > 0 {
+            res.extend(ct_sep.iter().cloned());
+        }
+        res.extend(segment.iter().cloned());
+    }
+    Ok(res)
+}
+
+/// Number of blocks of the characters of the content, or `CHAR_NUM_BLOCKS` if
+/// it has none.
+pub(crate) fn char_num_blocks<PBSOrder>(content: &[RadixCiphertext<PBSOrder>]) -> usize
+where
+    PBSOrder: PBSOrderMarker,
+    RadixCiphertext<PBSOrder>: IntegerCiphertext,
+{
+    content
+        .first()
+        .map_or(CHAR_NUM_BLOCKS, |ct_char| ct_char.blocks().len())
+}
+
+/// Largest character that the characters of the content can hold.
+pub(crate) fn max_char<PBSOrder>(content: &[RadixCiphertext<PBSOrder>]) -> u64
+where
+    PBSOrder: PBSOrderMarker,
+    RadixCiphertext<PBSOrder>: IntegerCiphertext,
+{
+    let ct_char = match content.first() {
+        Some(ct_char) => ct_char,
+        None => return u8::MAX as u64,
+    };
+    let num_bits_in_block = ct_char.blocks()[0].message_modulus.0.ilog2() as usize;
+    let num_bits = num_bits_in_block * ct_char.blocks().len();
+    if num_bits >= 8 {
+        u8::MAX as u64
+    } else {
+        (1 << num_bits) - 1
+    }
+}
+
+/// Number of blocks and largest value of the numbers computed over the
+/// content, such as positions, lengths and counts.
+///
+/// They have as many blocks as the content characters, plus `extra_blocks`,
+/// each block holding as many bits as the message modulus of the key allows.
+pub(crate) fn number_layout(
+    sk: &ServerKey,
+    content: &[RadixCiphertextBig],
+    extra_blocks: usize,
+) -> (usize, u64) {
+    let num_blocks = char_num_blocks(content) + extra_blocks;
+    let ct_zero: RadixCiphertextBig = sk.create_trivial_zero_radix(1);
+    let num_bits = ct_zero.blocks()[0].message_modulus.0.ilog2() as usize * num_blocks;
+    let max_value = if num_bits >= u64::BITS as usize {
+        u64::MAX
+    } else {
+        (1 << num_bits) - 1
+    };
+    (num_blocks, max_value)
 }
 
-pub fn gen_keys() -> (RadixClientKey, ServerKey) {
-    let num_block = 4;
-    gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, num_block)
+/// Generates the keys with the given parameters, the characters being
+/// encrypted on `num_block` blocks.
+///
+/// Returns an error if `num_block` blocks of the parameters' message modulus
+/// can't hold a byte, as `encrypt_str` would then lose bits of the
+/// characters. The engine creates the constants it compares the characters
+/// against with as many blocks as the content characters, so matching works
+/// with any of the block counts accepted here, as well as with the 7-bit
+/// characters of `encrypt_str_packed`.
+pub fn gen_keys(params: PBSParameters, num_block: usize) -> Result<(RadixClientKey, ServerKey)> {
+    let num_bits = params.message_modulus.0.ilog2() as usize * num_block;
+    if num_bits < 8 {
+        return Err(anyhow!(
+            "{} blocks hold {} bits, which is less than a byte",
+            num_block,
+            num_bits
+        ));
+    }
+    Ok(gen_keys_radix(params, num_block))
+}
+
+/// Generates the keys with `PARAM_MESSAGE_2_CARRY_2` and `CHAR_NUM_BLOCKS`
+/// blocks per character, which is what the engine works with.
+pub fn gen_keys_default() -> (RadixClientKey, ServerKey) {
+    gen_keys(PARAM_MESSAGE_2_CARRY_2, CHAR_NUM_BLOCKS).unwrap()
+}
+
+/// Generates the keys with `PARAM_MESSAGE_1_CARRY_1` and 8 blocks per
+/// character, for which `encrypt_str_packed` saves a block per character.
+#[allow(dead_code)]
+pub fn gen_keys_packed() -> (RadixClientKey, ServerKey) {
+    gen_keys(PARAM_MESSAGE_1_CARRY_1, 8).unwrap()
+}
+
+/// Same as `gen_keys_default`, but returns a compressed server key, which is much
+/// smaller to send to the server.
+///
+/// The server has to decompress it before running the engine, with
+/// `ServerKey::from(compressed_server_key)`.
+#[allow(dead_code)]
+pub fn gen_keys_compressed() -> (RadixClientKey, CompressedServerKey) {
+    gen_keys_radix_compressed(PARAM_MESSAGE_2_CARRY_2, CHAR_NUM_BLOCKS)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ciphertext::{
+        decrypt_str_streaming, deserialize_string_ciphertext, encrypt_bytes, encrypt_str,
+        encrypt_str_packed, gen_keys, gen_keys_compressed, gen_keys_packed, join,
+        serialize_string_ciphertext, StringCiphertext,
+    };
+    use crate::engine::has_match;
+    use crate::engine::tests::KEYS;
+    use test_case::test_case;
+    use tfhe::integer::{IntegerCiphertext, RadixCiphertextBig, ServerKey};
+    use tfhe::shortint::parameters::{PARAM_MESSAGE_1_CARRY_1, PARAM_MESSAGE_2_CARRY_2};
+
+    #[test]
+    fn test_gen_keys_other_parameters() {
+        let (client_key, _) = gen_keys(PARAM_MESSAGE_1_CARRY_1, 8).unwrap();
+
+        let ct_content = encrypt_str(&client_key, "a~").unwrap();
+        let got: Vec<u8> = ct_content
+            .iter()
+            .map(|ct| client_key.decrypt::<u64, _>(ct) as u8)
+            .collect();
+        assert_eq!(b"a~".to_vec(), got);
+    }
+
+    #[test]
+    fn test_encrypt_str_packed() {
+        let (client_key, server_key) = gen_keys_packed();
+
+        let ct_content = encrypt_str_packed(&client_key, "a~b").unwrap();
+        assert!(ct_content.iter().all(|ct| ct.blocks().len() == 7));
+        let got: Vec<u8> = ct_content
+            .iter()
+            .map(|ct| client_key.decrypt::<u64, _>(ct) as u8)
+            .collect();
+        assert_eq!(b"a~b".to_vec(), got);
+
+        // Includes constants that don't fit in 7 bits
+        for (pattern, exp) in [
+            ("/~b$/", 1),
+            ("/\\xFF|c/", 0),
+            ("/^[a-\\xFF]~/", 1),
+            ("/[^\\x80-\\xFF]{3}/", 1),
+        ] {
+            let ct_res = has_match(&server_key, &ct_content, pattern).unwrap();
+            let got: u64 = client_key.decrypt(&ct_res);
+            assert_eq!(exp, got, "pattern {pattern}");
+        }
+    }
+
+    #[test]
+    fn test_encrypt_str_packed_default_parameters() {
+        // 7 bits take as many blocks of 2 bits as 8 bits, nothing is saved
+        let ct_content = encrypt_str_packed(&KEYS.0, "abc").unwrap();
+        let ct_unpacked = encrypt_str(&KEYS.0, "abc").unwrap();
+        assert!(ct_content.iter().all(|ct| ct.blocks().len() == 4));
+        assert!(ct_unpacked.iter().all(|ct| ct.blocks().len() == 4));
+
+        let ct_res = has_match(&KEYS.1, &ct_content, "/b/").unwrap();
+        assert!(KEYS.0.decrypt_bool(&ct_res).unwrap());
+    }
+
+    #[test]
+    fn test_gen_keys_less_than_a_byte() {
+        assert!(gen_keys(PARAM_MESSAGE_2_CARRY_2, 3).is_err());
+    }
+
+    #[test]
+    fn test_encrypt_bytes() {
+        let bytes = [0u8, 7, b'a', 0x7F, 0x80, 200, u8::MAX];
+        let ct_content: StringCiphertext = encrypt_bytes(&KEYS.0, &bytes);
+
+        let got: Vec<u8> = ct_content
+            .iter()
+            .map(|ct| KEYS.0.decrypt::<u64, _>(ct) as u8)
+            .collect();
+        assert_eq!(bytes.to_vec(), got);
+    }
+
+    #[test]
+    fn test_decrypt_str_streaming() {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, "hello").unwrap();
+
+        let mut got = vec![];
+        decrypt_str_streaming(&KEYS.0, &ct_content, |c| {
+            got.push(c);
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(b"hello".to_vec(), got);
+    }
+
+    #[test]
+    fn test_decrypt_str_streaming_stops_at_non_ascii() {
+        let ct_content: StringCiphertext = encrypt_bytes(&KEYS.0, &[b'a', b'b', 200, b'c']);
+
+        let mut got = vec![];
+        let res = decrypt_str_streaming(&KEYS.0, &ct_content, |c| {
+            got.push(c);
+            Ok(())
+        });
+        assert!(res.is_err());
+        assert_eq!(b"ab".to_vec(), got);
+    }
+
+    #[test_case(&["ab", "c", "def"], ", " ; "several segments")]
+    #[test_case(&["ab", "", "c"], "-" ; "empty segment")]
+    #[test_case(&["ab"], "-" ; "single segment")]
+    #[test_case(&[], "-" ; "no segments")]
+    #[test_case(&["ab", "c"], "" ; "empty separator")]
+    fn test_join(segments: &[&str], sep: &str) {
+        let ct_segments: Vec<StringCiphertext> = segments
+            .iter()
+            .map(|segment| encrypt_str(&KEYS.0, segment).unwrap())
+            .collect();
+        let ct_res = join(&KEYS.1, &ct_segments, sep).unwrap();
+
+        let mut got = vec![];
+        decrypt_str_streaming(&KEYS.0, &ct_res, |c| {
+            got.push(c);
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(segments.join(sep).into_bytes(), got);
+    }
+
+    #[test]
+    fn test_empty() {
+        let ct_content: StringCiphertext = StringCiphertext::empty();
+
+        assert_eq!(0, ct_content.len());
+        assert!(ct_content.is_empty());
+    }
+
+    #[test_case("" ; "empty")]
+    #[test_case("a" ; "single character")]
+    #[test_case("hello" ; "several characters")]
+    fn test_len(content: &str) {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, content).unwrap();
+
+        assert_eq!(content.len(), ct_content.len());
+        assert_eq!(content.is_empty(), ct_content.is_empty());
+    }
+
+    #[test]
+    fn test_extend_empty() {
+        let mut ct_content = StringCiphertext::empty();
+        ct_content.extend(encrypt_str(&KEYS.0, "ab").unwrap().iter().cloned());
+        ct_content.extend(encrypt_str(&KEYS.0, "c").unwrap().iter().cloned());
+        assert_eq!(3, ct_content.len());
+
+        let mut got = vec![];
+        decrypt_str_streaming(&KEYS.0, &ct_content, |c| {
+            got.push(c);
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(b"abc".to_vec(), got);
+    }
+
+    #[test]
+    fn test_gen_keys_compressed() {
+        let (client_key, compressed_server_key) = gen_keys_compressed();
+        let server_key = ServerKey::from(compressed_server_key);
+        let fresh_server_key = ServerKey::new(&client_key);
+
+        for (content, exp) in [("abc", 1), ("acb", 0)] {
+            let ct_content = encrypt_str(&client_key, content).unwrap();
+            let ct_res = has_match(&server_key, &ct_content, "/bc/").unwrap();
+            let ct_fresh_res = has_match(&fresh_server_key, &ct_content, "/bc/").unwrap();
+
+            let got: u64 = client_key.decrypt(&ct_res);
+            let got_fresh: u64 = client_key.decrypt(&ct_fresh_res);
+            assert_eq!(exp, got);
+            assert_eq!(got_fresh, got);
+        }
+    }
+
+    #[test]
+    fn test_try_from_slice() {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, "abc").unwrap();
+
+        let ct_res = StringCiphertext::try_from(&ct_content[..]).unwrap();
+        assert_eq!(3, ct_res.len());
+
+        let ct_empty = StringCiphertext::try_from(&[] as &[RadixCiphertextBig]).unwrap();
+        assert!(ct_empty.is_empty());
+    }
+
+    #[test]
+    fn test_try_from_inconsistent_slice() {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, "abc").unwrap();
+        let mut chars: Vec<RadixCiphertextBig> = ct_content.to_vec();
+        chars.insert(1, KEYS.1.create_trivial_radix(b'x' as u64, 2));
+
+        assert!(StringCiphertext::try_from(&chars[..]).is_err());
+    }
+
+    #[test]
+    fn test_deserialize_inconsistent_chars() {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, "abc").unwrap();
+        let mut chars: Vec<RadixCiphertextBig> = ct_content.to_vec();
+        chars.push(KEYS.1.create_trivial_radix(b'x' as u64, 2));
+
+        let serialized = bincode::serialize(&chars).unwrap();
+        assert!(bincode::deserialize::<StringCiphertext>(&serialized).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "character at position 3 has 2 blocks instead of 4")]
+    fn test_extend_inconsistent_chars() {
+        let mut ct_content: StringCiphertext = encrypt_str(&KEYS.0, "abc").unwrap();
+        ct_content.extend([KEYS.1.create_trivial_radix(b'x' as u64, 2)]);
+    }
+
+    #[test_case("" ; "empty")]
+    #[test_case("abc" ; "several characters")]
+    fn test_serialize_round_trip(content: &str) {
+        let ct_content = encrypt_str(&KEYS.0, content).unwrap();
+
+        let mut buffer = vec![];
+        serialize_string_ciphertext(&ct_content, &mut buffer).unwrap();
+        let ct_deserialized = deserialize_string_ciphertext(&buffer[..]).unwrap();
+        assert_eq!(content.len(), ct_deserialized.len());
+
+        let ct_res = has_match(&KEYS.1, &ct_deserialized, "/^abc$/").unwrap();
+        let got: u64 = KEYS.0.decrypt(&ct_res);
+        assert_eq!((content == "abc") as u64, got);
+    }
+
+    #[test]
+    fn test_serialize_several_strings() {
+        let mut buffer = vec![];
+        for content in ["ab", "", "cde"] {
+            let ct_content = encrypt_str(&KEYS.0, content).unwrap();
+            serialize_string_ciphertext(&ct_content, &mut buffer).unwrap();
+        }
+
+        let mut reader = &buffer[..];
+        for content in ["ab", "", "cde"] {
+            let ct_content = deserialize_string_ciphertext(&mut reader).unwrap();
+
+            let mut got = vec![];
+            decrypt_str_streaming(&KEYS.0, &ct_content, |c| {
+                got.push(c);
+                Ok(())
+            })
+            .unwrap();
+            assert_eq!(content.as_bytes().to_vec(), got);
+        }
+        assert!(reader.is_empty());
+    }
+
+    #[test]
+    fn test_deserialize_truncated() {
+        let ct_content = encrypt_str(&KEYS.0, "abc").unwrap();
+
+        let mut buffer = vec![];
+        serialize_string_ciphertext(&ct_content, &mut buffer).unwrap();
+        buffer.truncate(buffer.len() - 1);
+
+        assert!(deserialize_string_ciphertext(&buffer[..]).is_err());
+    }
 }
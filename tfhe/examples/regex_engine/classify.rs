@@ -0,0 +1,298 @@
+use crate::ciphertext::StringCiphertext;
+use crate::engine::or_branches;
+use crate::execution::{lazy_branches, CharComparison, Execution, LazyExecution};
+use crate::parser::RegExpr;
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+use tfhe::integer::{IntegerCiphertext, RadixCiphertextBig, ServerKey};
+
+/// Classifies each content character on being an ascii digit (`0-9`).
+///
+/// Results in one encrypted boolean per content character.
+#[allow(dead_code)]
+pub fn classify_digits(sk: &ServerKey, content: &StringCiphertext) -> Vec<RadixCiphertextBig> {
+    classify(sk, content, &digit_class())
+}
+
+/// Classifies each content character on being an ascii letter (`a-z` or
+/// `A-Z`).
+///
+/// Results in one encrypted boolean per content character.
+#[allow(dead_code)]
+pub fn classify_alpha(sk: &ServerKey, content: &StringCiphertext) -> Vec<RadixCiphertextBig> {
+    classify(sk, content, &alpha_class())
+}
+
+/// Classifies each content character on being an ascii letter or an ascii
+/// digit.
+///
+/// Results in one encrypted boolean per content character.
+#[allow(dead_code)]
+pub fn classify_alphanumeric(
+    sk: &ServerKey,
+    content: &StringCiphertext,
+) -> Vec<RadixCiphertextBig> {
+    classify(sk, content, &[alpha_class(), digit_class()].concat())
+}
+
+/// Classifies each content character on being ascii whitespace, following the
+/// same definition as [u8::is_ascii_whitespace] (space, `\t`, `\n`, form feed
+/// and `\r`).
+///
+/// Results in one encrypted boolean per content character.
+#[allow(dead_code)]
+pub fn classify_whitespace(sk: &ServerKey, content: &StringCiphertext) -> Vec<RadixCiphertextBig> {
+    classify(sk, content, &[b' ', b'\t', b'\n', 0x0C, b'\r'])
+}
+
+/// Checks whether any content character is one of the given bytes.
+///
+/// Results in an encrypted boolean, false if `bytes` is empty.
+#[allow(dead_code)]
+pub fn contains_any_of_bytes(
+    sk: &ServerKey,
+    content: &StringCiphertext,
+    bytes: &[u8],
+) -> RadixCiphertextBig {
+    let mut cs = bytes.to_vec();
+    cs.sort();
+    cs.dedup();
+
+    let mut exec = Execution::new(sk.clone(), content);
+    if cs.is_empty() {
+        return exec.ct_false().0;
+    }
+
+    exec.prefill_char_comparisons(
+        content,
+        &cs.iter()
+            .map(|c| CharComparison::Equal { c: *c })
+            .collect::<Vec<_>>(),
+    );
+    let set_re = RegExpr::Range { cs };
+    let branches: Vec<LazyExecution> = (0..content.len())
+        .flat_map(|c_pos| lazy_branches(content, &set_re, c_pos))
+        .collect();
+
+    let res = or_branches(&mut exec, &branches).0;
+    exec.log_ct_operations();
+    res
+}
+
+fn digit_class() -> Vec<u8> {
+    (b'0'..=b'9').collect()
+}
+
+fn alpha_class() -> Vec<u8> {
+    (b'a'..=b'z').chain(b'A'..=b'Z').collect()
+}
+
+// Tests each content character separately on being in the class. As opposed to
+// `has_match`, the per position results are not joined together.
+//
+// The characters are independent, so they are tested in parallel, each with
+// `is_in_set`, which evaluates a lookup table of the class per block of the
+// character rather than comparing it to the class bounds.
+fn classify(
+    sk: &ServerKey,
+    content: &[RadixCiphertextBig],
+    class: &[u8],
+) -> Vec<RadixCiphertextBig> {
+    // The lookup tables are only available on the shortint key
+    let shortint_sk = tfhe::shortint::ServerKey::from(sk.clone());
+    let set: Vec<u64> = class.iter().map(|c| *c as u64).collect();
+    content
+        .par_iter()
+        .map(|ct_char| is_in_set(sk, &shortint_sk, ct_char, &set))
+        .collect()
+}
+
+// Index of a block, and the digits it must be one of.
+type BlockTest = (usize, Vec<u64>);
+
+// Checks whether `ct` encrypts one of the values of `set`, resulting in an
+// encrypted boolean with as many blocks as `ct`.
+//
+// The set is split into products of per block sets of digits, e.g. on two
+// blocks of 2 bits, `0..=9` is made of `0..=7` (any first block, second block
+// in `0..=1`) and `8..=9` (first block in `0..=1`, second block `2`). Whether a
+// block is in its set of digits takes a single PBS, with a lookup table of
+// that set. The block results are then joined with `and_reduce` per product,
+// and with `or_reduce` over the products. When the set is made of a few
+// ranges, such as a class of characters, this is much cheaper than comparing
+// `ct` to the bounds of each range.
+fn is_in_set(
+    sk: &ServerKey,
+    shortint_sk: &tfhe::shortint::ServerKey,
+    ct: &RadixCiphertextBig,
+    set: &[u64],
+) -> RadixCiphertextBig {
+    let num_blocks = ct.blocks().len();
+    let message_modulus = shortint_sk.message_modulus.0 as u64;
+    let mut values: Vec<u64> = set
+        .iter()
+        .copied()
+        .filter(|value| {
+            message_modulus
+                .checked_pow(num_blocks as u32)
+                .map_or(true, |modulus| *value < modulus)
+        })
+        .collect();
+    values.sort_unstable();
+    values.dedup();
+
+    let products = split_into_products(&values, num_blocks, message_modulus);
+    if num_blocks == 0 || products.is_empty() {
+        return sk.create_trivial_zero_radix(num_blocks);
+    }
+
+    let mut tmp_ct: RadixCiphertextBig;
+    let ct = if ct.block_carries_are_empty() {
+        ct
+    } else {
+        tmp_ct = ct.clone();
+        sk.full_propagate_parallelized(&mut tmp_ct);
+        &tmp_ct
+    };
+
+    let mut block_tests: Vec<BlockTest> = products.iter().flatten().cloned().collect();
+    block_tests.sort();
+    block_tests.dedup();
+    let block_results: Vec<RadixCiphertextBig> = block_tests
+        .par_iter()
+        .map(|(block_index, digits)| {
+            let lut = shortint_sk.generate_accumulator(|x| digits.contains(&x) as u64);
+            let block = shortint_sk.apply_lookup_table(&ct.blocks()[*block_index], &lut);
+            RadixCiphertextBig::from(vec![block])
+        })
+        .collect();
+
+    let product_results: Vec<RadixCiphertextBig> = products
+        .par_iter()
+        .map(|product| {
+            let booleans: Vec<RadixCiphertextBig> = product
+                .iter()
+                .map(|block_test| {
+                    let i = block_tests.binary_search(block_test).unwrap();
+                    block_results[i].clone()
+                })
+                .collect();
+            // A product without any block test holds all the values
+            sk.and_reduce(&booleans)
+                .unwrap_or_else(|| sk.create_trivial_radix(1u64, 1))
+        })
+        .collect();
+
+    let mut result: RadixCiphertextBig = sk.create_trivial_zero_radix(num_blocks);
+    result.blocks_mut()[0] = sk.or_reduce(&product_results).unwrap().blocks()[0].clone();
+    result
+}
+
+// Splits the sorted `values` of `num_blocks` blocks into products of per block
+// sets of digits, each product being given by the tests of the blocks whose
+// digits are restricted.
+fn split_into_products(
+    values: &[u64],
+    num_blocks: usize,
+    message_modulus: u64,
+) -> Vec<Vec<BlockTest>> {
+    if values.is_empty() {
+        return vec![];
+    }
+    if num_blocks == 0 {
+        return vec![vec![]];
+    }
+
+    // The values of the lower blocks, for each digit of the most significant block
+    let block_index = num_blocks - 1;
+    let lower_modulus = message_modulus.checked_pow(block_index as u32);
+    let mut lowers_by_digit: BTreeMap<u64, Vec<u64>> = BTreeMap::new();
+    for value in values {
+        let (digit, lower) = lower_modulus.map_or((0, *value), |lower_modulus| {
+            (value / lower_modulus, value % lower_modulus)
+        });
+        lowers_by_digit.entry(digit).or_default().push(lower);
+    }
+
+    // The digits sharing the same values of the lower blocks make a single product
+    let mut digits_by_lowers: BTreeMap<Vec<u64>, Vec<u64>> = BTreeMap::new();
+    for (digit, lowers) in lowers_by_digit {
+        digits_by_lowers.entry(lowers).or_default().push(digit);
+    }
+
+    let mut products = vec![];
+    for (lowers, digits) in digits_by_lowers {
+        for mut product in split_into_products(&lowers, block_index, message_modulus) {
+            if (digits.len() as u64) < message_modulus {
+                product.push((block_index, digits.clone()));
+            }
+            products.push(product);
+        }
+    }
+    products
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::classify::split_into_products;
+    use crate::classify::{
+        classify_alpha, classify_alphanumeric, classify_digits, classify_whitespace,
+        contains_any_of_bytes,
+    };
+    use crate::engine::tests::KEYS;
+    use test_case::test_case;
+
+    use crate::ciphertext::{encrypt_str, StringCiphertext};
+    use tfhe::integer::{RadixCiphertextBig, ServerKey};
+
+    type Classifier = fn(&ServerKey, &StringCiphertext) -> Vec<RadixCiphertextBig>;
+
+    #[test_case("a1 Z_", classify_digits, u8::is_ascii_digit ; "digits")]
+    #[test_case("a1 Z_", classify_alpha, u8::is_ascii_alphabetic ; "alpha")]
+    #[test_case("a1 Z_", classify_alphanumeric, u8::is_ascii_alphanumeric ; "alphanumeric")]
+    #[test_case("a\t1 \n", classify_whitespace, u8::is_ascii_whitespace ; "whitespace")]
+    fn test_classify(content: &str, classifier: Classifier, exp_fn: fn(&u8) -> bool) {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, content).unwrap();
+        let ct_res = classifier(&KEYS.1, &ct_content);
+
+        let got: Vec<u64> = ct_res.iter().map(|ct| KEYS.0.decrypt(ct)).collect();
+        let exp: Vec<u64> = content
+            .as_bytes()
+            .iter()
+            .map(|b| exp_fn(b) as u64)
+            .collect();
+        assert_eq!(exp, got);
+    }
+
+    #[test_case("hello; world", b";<>", 1 ; "present")]
+    #[test_case("hello world", b";<>", 0 ; "absent")]
+    #[test_case("hello world", b"", 0 ; "empty set")]
+    #[test_case("", b"a", 0 ; "empty content")]
+    fn test_contains_any_of_bytes(content: &str, bytes: &[u8], exp: u64) {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, content).unwrap();
+        let ct_res = contains_any_of_bytes(&KEYS.1, &ct_content, bytes);
+
+        let got = KEYS.0.decrypt(&ct_res);
+        assert_eq!(exp, got);
+    }
+
+    #[test]
+    fn test_split_into_products() {
+        // On two blocks of 2 bits, 0..=9 is 0..=7 (second block in 0..=1) and
+        // 8..=9 (first block in 0..=1, second block 2)
+        let values: Vec<u64> = (0..=9).collect();
+        let products = split_into_products(&values, 2, 4);
+        assert_eq!(
+            vec![vec![(0, vec![0, 1]), (1, vec![2])], vec![(1, vec![0, 1])]],
+            products
+        );
+
+        // All the values make a single product without block tests, no values make no products
+        let all: Vec<u64> = (0..16).collect();
+        assert_eq!(
+            vec![Vec::<(usize, Vec<u64>)>::new()],
+            split_into_products(&all, 2, 4)
+        );
+        assert!(split_into_products(&[], 2, 4).is_empty());
+    }
+}
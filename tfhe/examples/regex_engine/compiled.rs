@@ -0,0 +1,149 @@
+use crate::cache::parse_pattern;
+use crate::engine::{char_comparisons, MatchOptions};
+use crate::execution::{estimate_ct_operations, CharComparison};
+use crate::parser::RegExpr;
+use anyhow::Result;
+
+/// A pattern parsed once, to be matched against many contents.
+///
+/// Compiling a pattern does all the work that only depends on the pattern:
+/// parsing it, applying the `MatchOptions` to the parsed regex, computing the
+/// length of its shortest match, and listing the comparisons of content
+/// characters against constants that the regex needs, which the engine
+/// evaluates for all positions up front. The compiled pattern can then be
+/// matched with `has_match_compiled` as many times as needed. The lookup
+/// tables of the homomorphic operations depend on the server key rather than
+/// on the pattern, so there are none to precompute here.
+#[derive(Clone)]
+pub struct CompiledRegex {
+    pub(crate) re: RegExpr,
+    // Empty when the regex is anchored at the start, as only the first
+    // positions are ever compared then, so comparing all positions up front
+    // would add more work than it saves
+    pub(crate) prefill_comparisons: Vec<CharComparison>,
+    // Length of the shortest match, matches can't start at the positions
+    // closer than this to the end of the content
+    pub(crate) min_len: usize,
+}
+
+impl CompiledRegex {
+    pub fn compile(pattern: &str) -> Result<Self> {
+        Self::compile_with_options(pattern, MatchOptions::default())
+    }
+
+    pub fn compile_with_options(pattern: &str, options: MatchOptions) -> Result<Self> {
+        // The options rewrite the pattern, so the cached one is left untouched
+        let mut re = RegExpr::clone(&parse_pattern(pattern, options.dot_matches_newline)?);
+        if options.case_insensitive {
+            re = re.case_insensitive();
+        }
+        if let Some(max_repeat) = options.max_repeat {
+            re = re.max_repeat(max_repeat);
+        }
+
+        let prefill_comparisons = if is_anchored_at_start(&re) {
+            vec![]
+        } else {
+            char_comparisons(&re)
+        };
+        Ok(Self {
+            min_len: re.min_len(),
+            re,
+            prefill_comparisons,
+        })
+    }
+
+    /// Estimates the number of ciphertext operations of matching the pattern
+    /// anywhere in a content of `content_len` characters, as `has_match_compiled`
+    /// does.
+    ///
+    /// The estimate only depends on the regex and the content length, so it
+    /// can be checked against a budget before matching anything, see
+    /// `MatchOptions::max_ct_operations`. It adds up the prefilled character
+    /// comparisons, the operations of the branches from each position that can
+    /// start a match (only the first one when the pattern is anchored with `^`)
+    /// and the final `or` of their results. The operations of the branches are
+    /// overestimated, as they ignore the cache: this is an upper bound, to be
+    /// compared with the number of operations the engine logs.
+    pub fn estimate_ct_operations(&self, content_len: usize) -> u64 {
+        let num_starts = (content_len + 1).saturating_sub(self.min_len);
+        if num_starts == 0 {
+            return 0;
+        }
+        let num_evaluated_starts = if is_anchored_at_start(&self.re) {
+            1
+        } else {
+            num_starts as u64
+        };
+
+        let prefill_ct_ops =
+            (self.prefill_comparisons.len() as u64).saturating_mul(content_len as u64);
+        estimate_ct_operations(&self.re, content_len)
+            .saturating_mul(num_evaluated_starts)
+            .saturating_add(prefill_ct_ops)
+            .saturating_add((num_starts > 1) as u64)
+    }
+}
+
+// Whether the regex can only match from the start of the content
+pub(crate) fn is_anchored_at_start(re: &RegExpr) -> bool {
+    matches!(re, RegExpr::Seq { re_xs } if re_xs.first() == Some(&RegExpr::Sof))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::compiled::CompiledRegex;
+    use crate::engine::tests::KEYS;
+    use crate::engine::{has_match, has_match_compiled};
+    use test_case::test_case;
+
+    use crate::ciphertext::{encrypt_str, StringCiphertext};
+
+    #[test_case("/ab?c/" ; "unanchored")]
+    #[test_case("/^a+b$/" ; "anchored")]
+    fn test_has_match_compiled(pattern: &str) {
+        let compiled = CompiledRegex::compile(pattern).unwrap();
+        for content in ["abc", "ac", "aab", "b", ""] {
+            let ct_content: StringCiphertext = encrypt_str(&KEYS.0, content).unwrap();
+
+            let exp: u64 = KEYS
+                .0
+                .decrypt(&has_match(&KEYS.1, &ct_content, pattern).unwrap());
+            let got: u64 = KEYS
+                .0
+                .decrypt(&has_match_compiled(&KEYS.1, &ct_content, &compiled));
+            assert_eq!(exp, got, "content {content:?}");
+        }
+    }
+
+    // 3 prefilled comparisons per character, 2 starts of 3 comparisons and 2
+    // `and`s each, and the `or` of the starts
+    #[test_case("/abc/", 4, 3 * 4 + 2 * (3 + 2) + 1 ; "sequence")]
+    // Nothing prefilled, a single start evaluated but still ORed with the other
+    #[test_case("/^abc/", 4, (3 + 3) + 1 ; "anchored sequence")]
+    // 2 prefilled comparisons per character, 2 starts of 2 comparisons and
+    // their `or` each, and the `or` of the starts
+    #[test_case("/a|b/", 2, 2 * 2 + 2 * (2 + 1) + 1 ; "alternatives")]
+    #[test_case("/abc/", 2, 0 ; "content shorter than the pattern")]
+    fn test_estimate_ct_operations(pattern: &str, content_len: usize, exp: u64) {
+        let compiled = CompiledRegex::compile(pattern).unwrap();
+        assert_eq!(exp, compiled.estimate_ct_operations(content_len));
+    }
+
+    #[test]
+    fn test_estimate_ct_operations_grows_with_unbounded_repetitions() {
+        let estimate = |pattern: &str, content_len: usize| {
+            CompiledRegex::compile(pattern)
+                .unwrap()
+                .estimate_ct_operations(content_len)
+        };
+        assert!(estimate("/a.*b/", 64) > 4 * estimate("/a.*b/", 32));
+        assert!(estimate("/.*.*.*/", 64) > 64 * estimate("/.*/", 64));
+        assert_eq!(u64::MAX, estimate("/(.*.*.*)*/", 1000));
+    }
+
+    #[test]
+    fn test_compile_error() {
+        assert!(CompiledRegex::compile("/a{3,1}/").is_err());
+    }
+}
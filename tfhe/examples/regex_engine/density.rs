@@ -0,0 +1,183 @@
+use crate::cache::parse_pattern;
+use crate::ciphertext::{number_layout, StringCiphertext};
+use crate::engine::{char_comparisons, or_branches};
+use crate::execution::{lazy_branches, Execution, LazyExecution};
+use anyhow::{anyhow, Result};
+use tfhe::integer::{IntegerCiphertext, RadixCiphertextBig, ServerKey};
+
+/// Counts the matches of the pattern per window of content positions.
+///
+/// Results in one encrypted count per window. The windows are consecutive and
+/// non overlapping: window `k` covers the content positions
+/// `k * window..(k + 1) * window`, so the stride equals `window`. When the
+/// content length is not a multiple of `window`, the last window is shorter
+/// and only covers the remaining positions. Empty content results in no
+/// windows.
+///
+/// A match is counted in the window holding its start position, whatever its
+/// length, so matches crossing a window boundary are counted once. Matches
+/// starting at the same position are counted once, as the count is a sum over
+/// the per position match mask. Summing all windows gives the number of
+/// positions at which a match starts.
+///
+/// The mask is summed once, as a running prefix sum read at each window
+/// boundary, and each window count is the difference of the prefix sums at
+/// its two boundaries.
+///
+/// The counts have as many blocks as the content characters, and an error is
+/// returned if `window` is 0, or if it is too large for its count to be
+/// represented on them (256 or more with the default keys).
+#[allow(dead_code)]
+pub fn match_density(
+    sk: &ServerKey,
+    content: &StringCiphertext,
+    pattern: &str,
+    window: usize,
+) -> Result<Vec<RadixCiphertextBig>> {
+    if window == 0 {
+        return Err(anyhow!("window must not be empty"));
+    }
+    let (num_blocks, max_count) = number_layout(sk, content, 0);
+    if window as u64 > max_count {
+        return Err(anyhow!(
+            "window too large, at most {} positions are supported",
+            max_count
+        ));
+    }
+
+    let re = parse_pattern(pattern, false)?;
+    let mut exec = Execution::new(sk.clone(), content);
+    exec.prefill_char_comparisons(content, &char_comparisons(&re));
+
+    // Encrypted boolean per content position, true if a match starts there
+    let match_mask: Vec<RadixCiphertextBig> = (0..content.len())
+        .map(|i| {
+            let branches: Vec<LazyExecution> = lazy_branches(content, &re, i);
+            or_branches(&mut exec, &branches).0
+        })
+        .collect();
+
+    // Prefix sum of the mask at each window boundary, each window count being
+    // the difference of the sums at its two boundaries. The sums wrap around
+    // past the width of a count, which the differences don't mind as no window
+    // holds more positions than a count can represent
+    let mut ct_prefix_sum: RadixCiphertextBig = sk.create_trivial_zero_radix(num_blocks);
+    let mut res = vec![];
+    for window_mask in match_mask.chunks(window) {
+        let ct_window_start = ct_prefix_sum.clone();
+        for ct_match in window_mask {
+            sk.smart_add_assign(&mut ct_prefix_sum, &mut ct_match.clone());
+        }
+        sk.full_propagate(&mut ct_prefix_sum);
+        res.push(sk.sub_parallelized(&ct_prefix_sum, &ct_window_start));
+    }
+
+    exec.log_ct_operations();
+    Ok(res)
+}
+
+/// Counts the positions at which a match of the pattern starts, saturating at
+/// `cap`.
+///
+/// Results in an encrypted count equal to `min(number of matches, cap)`. The
+/// count only has as many blocks as needed to hold `cap + 1`, and each match
+/// flag is added with a saturating add (an add followed by a minimum with
+/// `cap`), so the count never overflows that narrow width. Finding the matches
+/// costs as much as for a full count, only the additions and minimums on the
+/// count are cheaper, as they work on fewer blocks.
+///
+/// As for `match_density`, matches starting at the same position are counted
+/// once, while overlapping matches starting at different positions are all
+/// counted, so `/aa/` is counted four times in `aaaaa`, unlike with
+/// `count_matches`.
+#[allow(dead_code)]
+pub fn count_match_positions_capped(
+    sk: &ServerKey,
+    content: &StringCiphertext,
+    pattern: &str,
+    cap: u64,
+) -> Result<RadixCiphertextBig> {
+    let re = parse_pattern(pattern, false)?;
+
+    // The match flags have as many blocks as the content characters, and the
+    // count as few blocks as hold `cap + 1`
+    let (flag_num_blocks, _) = number_layout(sk, content, 0);
+    let ct_zero: RadixCiphertextBig = sk.create_trivial_zero_radix(1);
+    let num_bits_in_block = ct_zero.blocks()[0].message_modulus.0.ilog2();
+    let num_count_bits = u64::BITS - cap.saturating_add(1).leading_zeros();
+    let num_blocks = ((num_count_bits + num_bits_in_block - 1) / num_bits_in_block).max(1) as usize;
+    let ct_cap: RadixCiphertextBig = sk.create_trivial_radix(cap, num_blocks);
+
+    let mut exec = Execution::new(sk.clone(), content);
+    exec.prefill_char_comparisons(content, &char_comparisons(&re));
+
+    let mut ct_count: RadixCiphertextBig = sk.create_trivial_zero_radix(num_blocks);
+    for i in 0..content.len() {
+        let branches: Vec<LazyExecution> = lazy_branches(content, &re, i);
+        let mut ct_match = or_branches(&mut exec, &branches).0;
+        // The match flag is a boolean, so its extra blocks are all zero
+        if num_blocks < flag_num_blocks {
+            sk.trim_radix_blocks_msb_assign(&mut ct_match, flag_num_blocks - num_blocks);
+        } else {
+            sk.extend_radix_with_trivial_zero_blocks_msb_assign(
+                &mut ct_match,
+                num_blocks - flag_num_blocks,
+            );
+        }
+
+        sk.add_assign_parallelized(&mut ct_count, &ct_match);
+        ct_count = sk.min_parallelized(&ct_count, &ct_cap);
+    }
+
+    exec.log_ct_operations();
+    Ok(ct_count)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::density::{count_match_positions_capped, match_density};
+    use crate::engine::tests::{assert_decrypted_result, KEYS};
+    use test_case::test_case;
+
+    use crate::ciphertext::{encrypt_str, StringCiphertext};
+
+    #[test_case("abxab", "/ab/", 2, &[1, 1, 0], 2 ; "matches in separate windows")]
+    #[test_case("abab", "/ab/", 4, &[2], 2 ; "single window")]
+    #[test_case("xabxx", "/ab/", 2, &[1, 0, 0], 1 ; "match crossing a window boundary")]
+    #[test_case("aaa", "/a/", 1, &[1, 1, 1], 3 ; "one position per window")]
+    #[test_case("aaaaa", "/a/", 3, &[3, 2], 5 ; "shorter last window")]
+    #[test_case("xyz", "/ab/", 2, &[0, 0], 0 ; "no match")]
+    fn test_match_density(
+        content: &str,
+        pattern: &str,
+        window: usize,
+        exp: &[u64],
+        exp_total: u64,
+    ) {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, content).unwrap();
+        let ct_res = match_density(&KEYS.1, &ct_content, pattern, window).unwrap();
+
+        let got: Vec<u64> = ct_res.iter().map(|ct| KEYS.0.decrypt(ct)).collect();
+        assert_eq!(exp, got.as_slice());
+        assert_eq!(exp_total, got.iter().sum::<u64>());
+    }
+
+    #[test]
+    fn test_match_density_rejects_empty_window() {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, "ab").unwrap();
+        assert!(match_density(&KEYS.1, &ct_content, "/ab/", 0).is_err());
+    }
+
+    #[test_case("abab", "/ab/", 3, 2 ; "below the cap")]
+    #[test_case("ababab", "/ab/", 3, 3 ; "at the cap")]
+    #[test_case("abababab", "/ab/", 1, 1 ; "above the cap")]
+    #[test_case("aaaaaa", "/a/", 5, 5 ; "above a cap not a power of two")]
+    #[test_case("abab", "/ab/", 0, 0 ; "zero cap")]
+    #[test_case("xyz", "/ab/", 2, 0 ; "no match")]
+    #[test_case("abab", "/ab/", u64::MAX, 2 ; "largest cap")]
+    fn test_count_match_positions_capped(content: &str, pattern: &str, cap: u64, exp: u64) {
+        assert_decrypted_result(content, exp, |sk, ct_content| {
+            count_match_positions_capped(sk, ct_content, pattern, cap).unwrap()
+        });
+    }
+}
@@ -0,0 +1,130 @@
+use crate::ciphertext::{number_layout, StringCiphertext};
+use anyhow::{anyhow, Result};
+use rayon::prelude::*;
+use tfhe::integer::{RadixCiphertextBig, ServerKey};
+
+/// Checks whether the edit (Levenshtein) distance between `a` and `b` is at
+/// most `k`.
+///
+/// Results in an encrypted boolean. The distance is computed with the usual
+/// dynamic programming recurrence over encrypted cells, restricted to a band of
+/// width `2k + 1` around the diagonal: cells further than `k` away from the
+/// diagonal can only hold a distance above `k`, so they are fixed to `k + 1`
+/// instead of being computed. Every computed cell is capped at `k + 1` as well.
+///
+/// For strings of length `n` and `m` this costs at most `n * (2k + 1)` cells,
+/// each requiring one encrypted equality, three additions or subtractions and
+/// three minimums. The cells of a row are computed in parallel, apart from the
+/// insertion step which depends on the cell to its left.
+///
+/// If the lengths of `a` and `b` differ by more than `k`, the result is known
+/// from the cleartext lengths alone and an encrypted false is returned
+/// directly.
+#[allow(dead_code)]
+pub fn edit_distance_at_most(
+    sk: &ServerKey,
+    a: &StringCiphertext,
+    b: &StringCiphertext,
+    k: usize,
+) -> Result<RadixCiphertextBig> {
+    let cap = k + 1;
+    // The cells have as many blocks as the characters, and cap + 1 must still
+    // fit in a cell before being capped
+    let (num_blocks, max_cell) = number_layout(sk, if a.is_empty() { b } else { a }, 0);
+    if (cap + 1) as u64 > max_cell {
+        return Err(anyhow!(
+            "maximum distance too large, at most {} is supported",
+            max_cell - 2
+        ));
+    }
+    if a.len().abs_diff(b.len()) > k {
+        return Ok(sk.create_trivial_radix(0u64, num_blocks));
+    }
+
+    let ct_capped =
+        |v: usize| -> RadixCiphertextBig { sk.create_trivial_radix(v.min(cap) as u64, num_blocks) };
+    let in_band = |i: usize, j: usize| i.abs_diff(j) <= k;
+
+    // First row: distances between the empty prefix of a and each prefix of b
+    let mut prev_row: Vec<RadixCiphertextBig> = (0..=b.len()).map(ct_capped).collect();
+    for i in 1..=a.len() {
+        // Deletions and substitutions only depend on the previous row
+        let from_prev_row: Vec<Option<RadixCiphertextBig>> = (0..=b.len())
+            .into_par_iter()
+            .map(|j| {
+                if j == 0 || !in_band(i, j) {
+                    return None;
+                }
+                let (deletion, substitution) = rayon::join(
+                    || sk.scalar_add_parallelized(&prev_row[j], 1),
+                    || {
+                        let (ct_eq, diag_plus_one) = rayon::join(
+                            || sk.eq_parallelized(&a[i - 1], &b[j - 1]),
+                            || sk.scalar_add_parallelized(&prev_row[j - 1], 1),
+                        );
+                        sk.sub_parallelized(&diag_plus_one, &ct_eq)
+                    },
+                );
+                Some(sk.min_parallelized(&deletion, &substitution))
+            })
+            .collect();
+
+        // Insertions depend on the cell to the left
+        let mut row = Vec::with_capacity(b.len() + 1);
+        row.push(ct_capped(i));
+        for (j, cell) in from_prev_row.into_iter().enumerate().skip(1) {
+            let cell = match cell {
+                Some(cell) => {
+                    let insertion = sk.scalar_add_parallelized(&row[j - 1], 1);
+                    let cell = sk.min_parallelized(&cell, &insertion);
+                    sk.min_parallelized(&cell, &ct_capped(cap))
+                }
+                None => ct_capped(cap),
+            };
+            row.push(cell);
+        }
+        prev_row = row;
+    }
+
+    Ok(sk.le_parallelized(&prev_row[b.len()], &ct_capped(k)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::distance::edit_distance_at_most;
+    use crate::engine::tests::KEYS;
+    use test_case::test_case;
+
+    use crate::ciphertext::{encrypt_str, StringCiphertext};
+
+    fn levenshtein(a: &[u8], b: &[u8]) -> usize {
+        let mut prev_row: Vec<usize> = (0..=b.len()).collect();
+        for i in 1..=a.len() {
+            let mut row = vec![i];
+            for j in 1..=b.len() {
+                let substitution = prev_row[j - 1] + (a[i - 1] != b[j - 1]) as usize;
+                row.push(substitution.min(prev_row[j] + 1).min(row[j - 1] + 1));
+            }
+            prev_row = row;
+        }
+        prev_row[b.len()]
+    }
+
+    #[test_case("abc", "abc", 0)]
+    #[test_case("abc", "abd", 0 ; "one substitution, k too small")]
+    #[test_case("abc", "abd", 1 ; "one substitution")]
+    #[test_case("abc", "ac", 1 ; "one deletion")]
+    #[test_case("ac", "abc", 1 ; "one insertion")]
+    #[test_case("kitten", "sitting", 2 ; "distance above k")]
+    #[test_case("kitten", "sitting", 3)]
+    #[test_case("a", "abcd", 2 ; "lengths too far apart")]
+    fn test_edit_distance_at_most(a: &str, b: &str, k: usize) {
+        let ct_a: StringCiphertext = encrypt_str(&KEYS.0, a).unwrap();
+        let ct_b: StringCiphertext = encrypt_str(&KEYS.0, b).unwrap();
+        let ct_res = edit_distance_at_most(&KEYS.1, &ct_a, &ct_b, k).unwrap();
+
+        let exp = (levenshtein(a.as_bytes(), b.as_bytes()) <= k) as u64;
+        let got: u64 = KEYS.0.decrypt(&ct_res);
+        assert_eq!(exp, got);
+    }
+}
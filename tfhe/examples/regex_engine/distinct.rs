@@ -0,0 +1,287 @@
+use crate::ciphertext::{number_layout, StringCiphertext};
+use crate::engine::or_branches;
+use crate::execution::{lazy_branches, CharComparison, Execution, LazyExecution};
+use crate::parser::RegExpr;
+use anyhow::{anyhow, Result};
+use rayon::prelude::*;
+use tfhe::integer::{RadixCiphertextBig, ServerKey};
+
+/// Counts the distinct byte values present in the content.
+///
+/// Results in an encrypted count. For each of the 256 byte values, a flag
+/// telling whether the value is present is computed by comparing it to every
+/// content character and ORing the comparisons, and the flags are summed.
+/// This costs 256 × content length comparisons, regardless of the content, so
+/// `count_distinct_bytes_among` should be preferred when the content is known
+/// to only hold bytes from a smaller alphabet.
+#[allow(dead_code)]
+pub fn count_distinct_bytes(sk: &ServerKey, content: &StringCiphertext) -> RadixCiphertextBig {
+    let all_bytes: Vec<u8> = (0..=u8::MAX).collect();
+    count_distinct_bytes_among(sk, content, &all_bytes)
+}
+
+/// Counts the distinct byte values among `candidates` present in the content.
+///
+/// Results in an encrypted count, computed as for `count_distinct_bytes` but
+/// only checking the candidates, so it costs `candidates.len()` × content
+/// length comparisons. Bytes of the content that are not candidates are not
+/// counted, and duplicate candidates are only counted once.
+pub fn count_distinct_bytes_among(
+    sk: &ServerKey,
+    content: &StringCiphertext,
+    candidates: &[u8],
+) -> RadixCiphertextBig {
+    let mut cs = candidates.to_vec();
+    cs.sort();
+    cs.dedup();
+
+    let mut exec = Execution::new(sk.clone(), content);
+    exec.prefill_char_comparisons(
+        content,
+        &cs.iter()
+            .map(|c| CharComparison::Equal { c: *c })
+            .collect::<Vec<_>>(),
+    );
+
+    // The flags have as many blocks as the content characters, and the count
+    // as many more blocks as needed to count all the candidates, e.g. one more
+    // for the 256 byte values
+    let (flag_num_blocks, _) = number_layout(sk, content, 0);
+    let (num_blocks, _) = (0..)
+        .map(|extra_blocks| number_layout(sk, content, extra_blocks))
+        .find(|(_, max_count)| *max_count >= cs.len() as u64)
+        .unwrap();
+
+    let mut ct_count: RadixCiphertextBig = sk.create_trivial_zero_radix(num_blocks);
+    for c in cs {
+        let mut ct_present = is_present(&mut exec, content, c);
+        sk.extend_radix_with_trivial_zero_blocks_msb_assign(
+            &mut ct_present,
+            num_blocks - flag_num_blocks,
+        );
+        sk.smart_add_assign(&mut ct_count, &mut ct_present);
+    }
+    sk.full_propagate(&mut ct_count);
+
+    exec.log_ct_operations();
+    ct_count
+}
+
+/// Computes the set of byte values present in the content, as 256 encrypted
+/// booleans.
+///
+/// The boolean at index `b` is true if the byte value `b` is present in the
+/// content. The booleans are computed as the presence flags of
+/// `count_distinct_bytes`, so this costs 256 × content length comparisons,
+/// regardless of the content. The sets of two contents can then be combined
+/// with `byte_set_union`, `byte_set_intersection`, `byte_set_difference` and
+/// `byte_set_symmetric_difference`, which only cost one operation per byte
+/// value, and result in an error if either set doesn't have 256 booleans.
+#[allow(dead_code)]
+pub fn byte_set_presence(sk: &ServerKey, content: &StringCiphertext) -> Vec<RadixCiphertextBig> {
+    let mut exec = Execution::new(sk.clone(), content);
+    exec.prefill_char_comparisons(
+        content,
+        &(0..=u8::MAX)
+            .map(|c| CharComparison::Equal { c })
+            .collect::<Vec<_>>(),
+    );
+
+    let res = (0..=u8::MAX)
+        .map(|c| is_present(&mut exec, content, c))
+        .collect();
+    exec.log_ct_operations();
+    res
+}
+
+/// Computes the union of two sets returned by `byte_set_presence`, i.e. the
+/// bytes present in either content.
+#[allow(dead_code)]
+pub fn byte_set_union(
+    sk: &ServerKey,
+    a: &[RadixCiphertextBig],
+    b: &[RadixCiphertextBig],
+) -> Result<Vec<RadixCiphertextBig>> {
+    combine_byte_sets(a, b, |ct_a, ct_b| sk.bitor_parallelized(ct_a, ct_b))
+}
+
+/// Computes the intersection of two sets returned by `byte_set_presence`,
+/// i.e. the bytes present in both contents.
+#[allow(dead_code)]
+pub fn byte_set_intersection(
+    sk: &ServerKey,
+    a: &[RadixCiphertextBig],
+    b: &[RadixCiphertextBig],
+) -> Result<Vec<RadixCiphertextBig>> {
+    combine_byte_sets(a, b, |ct_a, ct_b| sk.bitand_parallelized(ct_a, ct_b))
+}
+
+/// Computes the difference of two sets returned by `byte_set_presence`, i.e.
+/// the bytes present in the content of `a` but not in the one of `b`.
+#[allow(dead_code)]
+pub fn byte_set_difference(
+    sk: &ServerKey,
+    a: &[RadixCiphertextBig],
+    b: &[RadixCiphertextBig],
+) -> Result<Vec<RadixCiphertextBig>> {
+    combine_byte_sets(a, b, |ct_a, ct_b| {
+        let ct_both = sk.bitand_parallelized(ct_a, ct_b);
+        sk.sub_parallelized(ct_a, &ct_both)
+    })
+}
+
+/// Computes the symmetric difference of two sets returned by
+/// `byte_set_presence`, i.e. the bytes present in exactly one of the
+/// contents.
+#[allow(dead_code)]
+pub fn byte_set_symmetric_difference(
+    sk: &ServerKey,
+    a: &[RadixCiphertextBig],
+    b: &[RadixCiphertextBig],
+) -> Result<Vec<RadixCiphertextBig>> {
+    combine_byte_sets(a, b, |ct_a, ct_b| sk.bitxor_parallelized(ct_a, ct_b))
+}
+
+// Combines the flags of each byte value, all of them in parallel, or results
+// in an error if the sets don't both have one flag per byte value
+fn combine_byte_sets<F>(
+    a: &[RadixCiphertextBig],
+    b: &[RadixCiphertextBig],
+    f: F,
+) -> Result<Vec<RadixCiphertextBig>>
+where
+    F: Fn(&RadixCiphertextBig, &RadixCiphertextBig) -> RadixCiphertextBig + Sync,
+{
+    let num_byte_values = u8::MAX as usize + 1;
+    if a.len() != num_byte_values || b.len() != num_byte_values {
+        return Err(anyhow!(
+            "byte sets of {} and {} flags, instead of {}",
+            a.len(),
+            b.len(),
+            num_byte_values
+        ));
+    }
+    Ok(a.par_iter()
+        .zip(b.par_iter())
+        .map(|(ct_a, ct_b)| f(ct_a, ct_b))
+        .collect())
+}
+
+// Encrypted boolean telling whether `c` is present in the content, the
+// comparisons to `c` being expected to be prefilled in `exec`
+fn is_present(exec: &mut Execution, content: &[RadixCiphertextBig], c: u8) -> RadixCiphertextBig {
+    let re = RegExpr::Char { c };
+    let branches: Vec<LazyExecution> = (0..content.len())
+        .flat_map(|i| lazy_branches(content, &re, i))
+        .collect();
+    or_branches(exec, &branches).0
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::distinct::{
+        byte_set_difference, byte_set_intersection, byte_set_presence,
+        byte_set_symmetric_difference, byte_set_union, count_distinct_bytes,
+        count_distinct_bytes_among,
+    };
+    use crate::engine::tests::KEYS;
+    use std::collections::BTreeSet;
+    use test_case::test_case;
+    use tfhe::integer::RadixCiphertextBig;
+
+    use crate::ciphertext::{encrypt_str, StringCiphertext};
+
+    #[test_case("abcab", b"abcd" ; "all present candidates")]
+    #[test_case("abcab", b"bx" ; "content bytes outside the candidates")]
+    #[test_case("aaaa", b"aab" ; "duplicate candidates")]
+    #[test_case("abc", b"" ; "no candidates")]
+    #[test_case("", b"abc" ; "empty content")]
+    fn test_count_distinct_bytes_among(content: &str, candidates: &[u8]) {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, content).unwrap();
+        let ct_res = count_distinct_bytes_among(&KEYS.1, &ct_content, candidates);
+
+        let exp = content
+            .bytes()
+            .filter(|c| candidates.contains(c))
+            .collect::<BTreeSet<u8>>()
+            .len() as u64;
+        let got: u64 = KEYS.0.decrypt(&ct_res);
+        assert_eq!(exp, got);
+    }
+
+    #[test]
+    fn test_count_distinct_bytes() {
+        let content = "a b a";
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, content).unwrap();
+        let ct_res = count_distinct_bytes(&KEYS.1, &ct_content);
+
+        let exp = content.bytes().collect::<BTreeSet<u8>>().len() as u64;
+        let got: u64 = KEYS.0.decrypt(&ct_res);
+        assert_eq!(exp, got);
+    }
+
+    fn encrypt_byte_set(set: &BTreeSet<u8>) -> Vec<RadixCiphertextBig> {
+        (0..=u8::MAX)
+            .map(|c| KEYS.0.encrypt(set.contains(&c) as u64))
+            .collect()
+    }
+
+    fn decrypt_byte_set(cts: &[RadixCiphertextBig]) -> BTreeSet<u8> {
+        assert_eq!(256, cts.len());
+        (0..=u8::MAX)
+            .filter(|c| KEYS.0.decrypt_bool(&cts[*c as usize]).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_byte_set_presence() {
+        let content = "abca";
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, content).unwrap();
+        let ct_res = byte_set_presence(&KEYS.1, &ct_content);
+
+        assert_eq!(
+            content.bytes().collect::<BTreeSet<u8>>(),
+            decrypt_byte_set(&ct_res)
+        );
+    }
+
+    #[test_case("abc", "bcd" ; "overlapping")]
+    #[test_case("ab", "xy" ; "disjoint")]
+    #[test_case("abc", "" ; "empty")]
+    fn test_byte_set_operations(a: &str, b: &str) {
+        let a: BTreeSet<u8> = a.bytes().collect();
+        let b: BTreeSet<u8> = b.bytes().collect();
+        let ct_a = encrypt_byte_set(&a);
+        let ct_b = encrypt_byte_set(&b);
+
+        assert_eq!(
+            a.union(&b).copied().collect::<BTreeSet<u8>>(),
+            decrypt_byte_set(&byte_set_union(&KEYS.1, &ct_a, &ct_b).unwrap())
+        );
+        assert_eq!(
+            a.intersection(&b).copied().collect::<BTreeSet<u8>>(),
+            decrypt_byte_set(&byte_set_intersection(&KEYS.1, &ct_a, &ct_b).unwrap())
+        );
+        assert_eq!(
+            a.difference(&b).copied().collect::<BTreeSet<u8>>(),
+            decrypt_byte_set(&byte_set_difference(&KEYS.1, &ct_a, &ct_b).unwrap())
+        );
+        assert_eq!(
+            a.symmetric_difference(&b)
+                .copied()
+                .collect::<BTreeSet<u8>>(),
+            decrypt_byte_set(&byte_set_symmetric_difference(&KEYS.1, &ct_a, &ct_b).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_byte_set_operations_reject_other_sizes() {
+        let ct_a = encrypt_byte_set(&b"ab".iter().copied().collect());
+        let ct_b = ct_a[..255].to_vec();
+
+        assert!(byte_set_union(&KEYS.1, &ct_a, &ct_b).is_err());
+        assert!(byte_set_intersection(&KEYS.1, &ct_b, &ct_a).is_err());
+        assert!(byte_set_difference(&KEYS.1, &ct_b, &ct_b).is_err());
+        assert!(byte_set_symmetric_difference(&KEYS.1, &ct_a, &[]).is_err());
+    }
+}
@@ -1,62 +1,663 @@
-use crate::execution::{Executed, Execution, LazyExecution};
-use crate::parser::{parse, RegExpr};
-use anyhow::Result;
+use crate::cache::parse_pattern;
+use crate::ciphertext::{char_num_blocks, StringCiphertext};
+use crate::compiled::{is_anchored_at_start, CompiledRegex};
+use crate::execution::{
+    lazy_branches, log_ct_operations, CharComparison, Executed, ExecutedResult, Execution,
+    LazyExecution,
+};
+use crate::parser::RegExpr;
+use anyhow::{anyhow, Result};
+use rayon::prelude::*;
+use rayon::ThreadPool;
+use std::collections::BTreeMap;
 use std::rc::Rc;
-use tfhe::integer::{RadixCiphertextBig, ServerKey};
+use tfhe::integer::ciphertext::RadixCiphertext;
+use tfhe::integer::{IntegerCiphertext, RadixCiphertextBig, ServerKey};
+use tfhe::shortint::PBSOrderMarker;
 
-pub fn has_match(
+/// Options changing how `has_match_with_options` matches the pattern.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MatchOptions {
+    /// Ignores the case of ASCII letters, as the `i` flag of the pattern
+    /// (`/ab/i`) does: each letter of the pattern matches both its lowercase
+    /// and uppercase variants, and the other characters are left untouched.
+    pub case_insensitive: bool,
+    /// Expands unbounded quantifiers to at most this many repetitions, see
+    /// `has_match_capped`.
+    pub max_repeat: Option<usize>,
+    /// Makes `.` match any character, including `\n`, as the `s` flag of the
+    /// pattern (`/a.b/s`) does. By default `.` matches any character but `\n`.
+    pub dot_matches_newline: bool,
+    /// Rejects the pattern with an error, before matching anything, when the
+    /// estimated number of ciphertext operations of matching it against the
+    /// content is above this budget, see `CompiledRegex::estimate_ct_operations`.
+    pub max_ct_operations: Option<u64>,
+}
+
+/// Checks whether the pattern matches anywhere in the content.
+///
+/// Results in an encrypted boolean, or an error if the pattern fails to parse.
+/// The content is borrowed, so it can be matched against as many patterns as
+/// needed. A sub-range of it can be matched by building a `StringCiphertext`
+/// from the sub-slice of its characters.
+pub fn has_match<PBSOrder>(
     sk: &ServerKey,
-    content: &[RadixCiphertextBig],
+    content: &StringCiphertext<PBSOrder>,
+    pattern: &str,
+) -> Result<RadixCiphertext<PBSOrder>>
+where
+    PBSOrder: PBSOrderMarker,
+    RadixCiphertext<PBSOrder>: IntegerCiphertext,
+{
+    has_match_with_options(sk, content, pattern, MatchOptions::default())
+}
+
+/// Same as `has_match`, but unbounded quantifiers (`*`, `+`, `{n,}`) are
+/// expanded to at most `max_repeat` repetitions instead of up to the content
+/// length. This bounds the number of homomorphic operations for long content,
+/// at the cost of missing any match that requires more repetitions than the
+/// cap. With `max_repeat` set to `None` there is no cap.
+#[allow(dead_code)]
+pub fn has_match_capped<PBSOrder>(
+    sk: &ServerKey,
+    content: &StringCiphertext<PBSOrder>,
+    pattern: &str,
+    max_repeat: Option<usize>,
+) -> Result<RadixCiphertext<PBSOrder>>
+where
+    PBSOrder: PBSOrderMarker,
+    RadixCiphertext<PBSOrder>: IntegerCiphertext,
+{
+    let options = MatchOptions {
+        max_repeat,
+        ..MatchOptions::default()
+    };
+    has_match_with_options(sk, content, pattern, options)
+}
+
+/// Same as `has_match`, with the matching changed by `options`.
+///
+/// Case insensitivity is applied to the parsed pattern, each letter being
+/// turned into the set of its two case variants. Each content character is
+/// then compared for equality to both variants and the results ORed, as for a
+/// `[aA]` character set, so it costs one more comparison per letter of the
+/// pattern but nothing for the other characters.
+///
+/// With a budget of ciphertext operations set, an error is returned instead
+/// when the estimated cost of the match is above it. As quantifiers are
+/// unrolled up to the content length, patterns such as `/.*.*.*/` cost far
+/// more on long content than their size suggests.
+pub fn has_match_with_options<PBSOrder>(
+    sk: &ServerKey,
+    content: &StringCiphertext<PBSOrder>,
+    pattern: &str,
+    options: MatchOptions,
+) -> Result<RadixCiphertext<PBSOrder>>
+where
+    PBSOrder: PBSOrderMarker,
+    RadixCiphertext<PBSOrder>: IntegerCiphertext,
+{
+    let compiled = CompiledRegex::compile_with_options(pattern, options)?;
+    check_ct_operations_budget(&compiled, pattern, content.len(), options)?;
+    Ok(has_match_compiled(sk, content, &compiled))
+}
+
+// Checks the estimated cost of matching the compiled pattern anywhere in a
+// content of `content_len` characters against the budget of `options`, if any
+fn check_ct_operations_budget(
+    compiled: &CompiledRegex,
+    pattern: &str,
+    content_len: usize,
+    options: MatchOptions,
+) -> Result<()> {
+    if let Some(max_ct_operations) = options.max_ct_operations {
+        let estimate = compiled.estimate_ct_operations(content_len);
+        if estimate > max_ct_operations {
+            return Err(anyhow!(
+                "matching {} on {} characters needs about {} ciphertext operations, over the \
+                budget of {}",
+                pattern,
+                content_len,
+                estimate,
+                max_ct_operations
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Same as `has_match`, for a pattern compiled beforehand.
+///
+/// Matching the same pattern against many contents this way parses the
+/// pattern only once, see `CompiledRegex`.
+///
+/// The matches starting at each position don't depend on each other, so the
+/// positions are evaluated in parallel on the current rayon thread pool, each
+/// with its own copy of the cache holding the prefilled character
+/// comparisons, and their results are then joined with `ServerKey::or_reduce`.
+/// Only the positions at least as far from the end of the content as the
+/// shortest match of the pattern are evaluated. When there is a single such
+/// position, it is evaluated directly.
+pub fn has_match_compiled<PBSOrder>(
+    sk: &ServerKey,
+    content: &StringCiphertext<PBSOrder>,
+    compiled: &CompiledRegex,
+) -> RadixCiphertext<PBSOrder>
+where
+    PBSOrder: PBSOrderMarker,
+    RadixCiphertext<PBSOrder>: IntegerCiphertext,
+{
+    let mut exec = Execution::new(sk.clone(), content);
+
+    // Matches may also start at the end of the content, where only the empty
+    // ones do, e.g. for `/$/` or `/^$/` on an empty content. The positions
+    // closer to the end than the shortest match can't start one, which only
+    // depends on the public content length
+    let num_starts = (content.len() + 1).saturating_sub(compiled.min_len);
+    if num_starts == 0 {
+        return exec.ct_false().0;
+    }
+    exec.prefill_char_comparisons(content, &compiled.prefill_comparisons);
+
+    if num_starts == 1 {
+        let branches: Vec<LazyExecution<PBSOrder>> = lazy_branches(content, &compiled.re, 0);
+        let res = or_branches(&mut exec, &branches).0;
+        exec.log_ct_operations();
+        return res;
+    }
+
+    let results: Vec<(RadixCiphertext<PBSOrder>, usize, usize)> = (0..num_starts)
+        .into_par_iter()
+        .map(|i| {
+            let mut exec = exec.fork();
+            let branches: Vec<LazyExecution<PBSOrder>> = lazy_branches(content, &compiled.re, i);
+            let flag = or_branches(&mut exec, &branches).0;
+            (flag, exec.ct_operations_count(), exec.cache_hits())
+        })
+        .collect();
+    let ct_ops: usize = results.iter().map(|(_, ct_ops, _)| ct_ops).sum();
+    let cache_hits: usize = results.iter().map(|(_, _, cache_hits)| cache_hits).sum();
+    let flags: Vec<RadixCiphertext<PBSOrder>> =
+        results.into_iter().map(|(flag, _, _)| flag).collect();
+
+    let res = sk.or_reduce(&flags).unwrap();
+    log_ct_operations(exec.ct_operations_count() + ct_ops + 1, cache_hits);
+    res
+}
+
+/// Same as `has_match`, but the matching runs on `pool` instead of the current
+/// rayon thread pool, see `has_match_compiled_in_pool`.
+#[allow(dead_code)]
+pub fn has_match_in_pool(
+    sk: &ServerKey,
+    content: &StringCiphertext,
     pattern: &str,
+    pool: Option<&ThreadPool>,
 ) -> Result<RadixCiphertextBig> {
-    let re = parse(pattern)?;
+    let compiled = CompiledRegex::compile(pattern)?;
+    Ok(has_match_compiled_in_pool(sk, content, &compiled, pool))
+}
+
+/// Same as `has_match_compiled`, but the matching runs on `pool` instead of the
+/// current rayon thread pool.
+///
+/// Both the evaluation of the positions and the integer operations it relies
+/// on then use at most the threads of `pool`, without changing the global
+/// pool, so that independent matches can each be confined to their own pool.
+/// With `pool` set to `None`, this is the same as `has_match_compiled`.
+pub fn has_match_compiled_in_pool(
+    sk: &ServerKey,
+    content: &StringCiphertext,
+    compiled: &CompiledRegex,
+    pool: Option<&ThreadPool>,
+) -> RadixCiphertextBig {
+    match pool {
+        Some(pool) => pool.install(|| has_match_compiled(sk, content, compiled)),
+        None => has_match_compiled(sk, content, compiled),
+    }
+}
+
+/// Checks whether the pattern matches in a chunk of the content, and ORs the
+/// result into `running`, the result accumulated over the previous chunks.
+///
+/// Results in the updated running result, an encrypted boolean telling whether
+/// a match was found in any of the chunks so far. `running` is `None` for the
+/// first chunk. This allows scanning long content a chunk at a time, and
+/// stopping as soon as a match is found instead of evaluating every position:
+/// after each chunk the running result is sent to the client, which decrypts
+/// it and tells the server whether to go on with the next chunk. Stopping
+/// early tells the server in which chunk the first match is, so this is only
+/// suitable when that is not sensitive.
+///
+/// Each chunk is matched on its own, as by `has_match`. So a match spanning
+/// two chunks is only found if the chunks overlap by at least the length of
+/// the match minus one, and `^` and `$` anchor at the start and the end of
+/// each chunk rather than of the whole content.
+#[allow(dead_code)]
+pub fn has_match_any(
+    sk: &ServerKey,
+    running: Option<&RadixCiphertextBig>,
+    chunk: &StringCiphertext,
+    pattern: &str,
+) -> Result<RadixCiphertextBig> {
+    let res = has_match(sk, chunk, pattern)?;
+    Ok(match running {
+        Some(running) => sk.bitor_parallelized(running, &res),
+        None => res,
+    })
+}
+
+/// Checks whether any of the patterns matches anywhere in the content, as for
+/// a blocklist.
+///
+/// Results in a single encrypted boolean, the OR of the results of
+/// `has_match_each`, or an encrypted false if there are no patterns. An error
+/// is returned if any of the patterns fails to parse.
+#[allow(dead_code)]
+pub fn has_match_any_of(
+    sk: &ServerKey,
+    content: &StringCiphertext,
+    patterns: &[&str],
+) -> Result<RadixCiphertextBig> {
+    let flags = has_match_each(sk, content, patterns)?;
+    Ok(sk
+        .or_reduce(&flags)
+        .unwrap_or_else(|| sk.create_trivial_radix(0, char_num_blocks(content))))
+}
+
+/// Checks whether each of the patterns matches anywhere in the content.
+///
+/// Results in one encrypted boolean per pattern, in the order of the patterns.
+/// All the patterns are compiled first, so an error is returned before
+/// evaluating anything if any of them fails to parse. The patterns are then
+/// matched in parallel on the current rayon thread pool, all borrowing the
+/// same content, so it is neither cloned nor encrypted again per pattern.
+pub fn has_match_each(
+    sk: &ServerKey,
+    content: &StringCiphertext,
+    patterns: &[&str],
+) -> Result<Vec<RadixCiphertextBig>> {
+    let compiled = patterns
+        .iter()
+        .map(|pattern| CompiledRegex::compile(pattern))
+        .collect::<Result<Vec<CompiledRegex>>>()?;
+    Ok(compiled
+        .par_iter()
+        .map(|compiled| has_match_compiled(sk, content, compiled))
+        .collect())
+}
+
+/// Checks whether the content starts with the literal `prefix`.
+///
+/// Results in an encrypted boolean, see `literal_at` for how it is computed.
+/// Returns an error if the prefix contains non-ascii characters, as
+/// `encrypt_str` does for the content.
+#[allow(dead_code)]
+pub fn starts_with(
+    sk: &ServerKey,
+    content: &StringCiphertext,
+    prefix: &str,
+) -> Result<RadixCiphertextBig> {
+    literal_at(sk, content, prefix, 0)
+}
+
+/// Checks whether the content ends with the literal `suffix`.
+///
+/// The counterpart of `starts_with`, with the same errors.
+#[allow(dead_code)]
+pub fn ends_with(
+    sk: &ServerKey,
+    content: &StringCiphertext,
+    suffix: &str,
+) -> Result<RadixCiphertextBig> {
+    let start = content.len().saturating_sub(suffix.len());
+    literal_at(sk, content, suffix, start)
+}
+
+// Checks whether the literal is found at content position `start`. The content
+// length being public, a literal that doesn't fit in the content from `start`
+// results in an encrypted false right away, and an empty literal in an
+// encrypted true. Otherwise, rather than building and walking the branches of
+// a regex, each character of the literal is compared for equality to the
+// content character at its position, and the results are joined at once with
+// `ServerKey::and_reduce`
+fn literal_at(
+    sk: &ServerKey,
+    content: &[RadixCiphertextBig],
+    literal: &str,
+    start: usize,
+) -> Result<RadixCiphertextBig> {
+    if !literal.is_ascii() {
+        return Err(anyhow!("literal contains non-ascii characters"));
+    }
 
-    let branches: Vec<LazyExecution> = (0..content.len())
-        .flat_map(|i| build_branches(content, &re, i))
-        .map(|(lazy_branch_res, _)| lazy_branch_res)
+    let mut exec = Execution::new(sk.clone(), content);
+    if start + literal.len() > content.len() {
+        return Ok(exec.ct_false().0);
+    }
+    if literal.is_empty() {
+        return Ok(exec.ct_true().0);
+    }
+
+    let flags: Vec<RadixCiphertextBig> = literal
+        .bytes()
+        .enumerate()
+        .map(|(i, c)| {
+            let c_pos = start + i;
+            let c_char = (content[c_pos].clone(), Executed::ct_pos(c_pos));
+            let c_literal = exec.ct_constant(c);
+            exec.ct_eq(c_char, c_literal).0
+        })
         .collect();
+    let res = sk.and_reduce(&flags).unwrap();
+    log_ct_operations(exec.ct_operations_count() + 1, exec.cache_hits());
+    Ok(res)
+}
 
-    let mut exec = Execution::new(sk.clone());
+/// Checks whether a match of the pattern starts at content position `pos`.
+///
+/// Results in an encrypted boolean. Only the matches starting at `pos` are
+/// evaluated, instead of those starting at every position as with `has_match`,
+/// which is much cheaper when the position is known beforehand. A pattern
+/// anchored with `^` can only match at position 0.
+///
+/// When the content remaining from `pos` is shorter than any match of the
+/// pattern can be, an encrypted false is returned without evaluating anything.
+/// An error is returned if `pos` is past the end of the content.
+#[allow(dead_code)]
+pub fn matches_at(
+    sk: &ServerKey,
+    content: &StringCiphertext,
+    pattern: &str,
+    pos: usize,
+) -> Result<RadixCiphertextBig> {
+    matches_at_with_options(sk, content, pattern, pos, MatchOptions::default())
+}
 
-    let res = if branches.len() <= 1 {
-        branches
-            .get(0)
-            .map_or(exec.ct_false(), |branch| branch(&mut exec))
-            .0
-    } else {
-        branches[1..]
-            .iter()
-            .fold(branches[0](&mut exec), |res, branch| {
-                let branch_res = branch(&mut exec);
-                exec.ct_or(res, branch_res)
-            })
-            .0
-    };
-    info!(
-        "{} ciphertext operations, {} cache hits",
-        exec.ct_operations_count(),
-        exec.cache_hits(),
-    );
+/// Same as `matches_at`, with the matching changed by `options`, as for
+/// `has_match_with_options`.
+///
+/// The character comparisons the pattern needs are prefilled as for
+/// `has_match_compiled`, but only from `pos` on, as no branch reaches the
+/// characters before it. The budget of ciphertext operations, if any, is
+/// checked against the estimate of matching anywhere in the content from
+/// `pos` on, which is an upper bound of matching at `pos` only.
+pub fn matches_at_with_options(
+    sk: &ServerKey,
+    content: &StringCiphertext,
+    pattern: &str,
+    pos: usize,
+    options: MatchOptions,
+) -> Result<RadixCiphertextBig> {
+    if pos > content.len() {
+        return Err(anyhow!(
+            "position {} out of range for content of length {}",
+            pos,
+            content.len()
+        ));
+    }
+    let compiled = CompiledRegex::compile_with_options(pattern, options)?;
+    check_ct_operations_budget(&compiled, pattern, content.len() - pos, options)?;
+
+    let mut exec = Execution::new(sk.clone(), content);
+    if compiled.min_len > content.len() - pos {
+        return Ok(exec.ct_false().0);
+    }
+    exec.prefill_char_comparisons_from(content, pos, &compiled.prefill_comparisons);
+
+    let branches: Vec<LazyExecution> = lazy_branches(content, &compiled.re, pos);
+
+    let res = or_branches(&mut exec, &branches).0;
+    exec.log_ct_operations();
     Ok(res)
 }
 
-fn build_branches(
-    content: &[RadixCiphertextBig],
+/// Counts the non overlapping matches of the pattern in the content.
+///
+/// Results in an encrypted count. The matches counted are those a regex
+/// library would find when iterating over them: the content is scanned from
+/// left to right, taking at each position the longest match starting there,
+/// and resuming the scan after its end, as `replace_all_masked` does. So
+/// `/aa/` occurs twice in `aaaaa`, not four times. Empty matches are not
+/// counted, as they consume no character, hence `/x*/` occurs once in `xyz`
+/// and never in `abc`, even though it matches the empty string at every
+/// position. To count the positions at which a match starts instead, see
+/// `count_match_positions_capped`.
+///
+/// Each position gets an encrypted boolean telling whether a counted match
+/// starts there, i.e. a non empty match starts there and the position is not
+/// covered by a counted match starting before it. These booleans are widened
+/// to hold the content length and summed with
+/// `ServerKey::default_binary_op_seq_parallelized`.
+#[allow(dead_code)]
+pub fn count_matches(
+    sk: &ServerKey,
+    content: &StringCiphertext,
+    pattern: &str,
+) -> Result<RadixCiphertextBig> {
+    let re = parse_pattern(pattern, false)?;
+    let mut exec = Execution::new(sk.clone(), content);
+    exec.prefill_char_comparisons(content, &char_comparisons(&re));
+
+    // For each position, the flags of the counted matches covering it after
+    // their start
+    let mut covered: Vec<Vec<ExecutedResult>> = vec![vec![]; content.len()];
+    let mut starts: Vec<RadixCiphertextBig> = Vec::with_capacity(content.len());
+    for i in 0..content.len() {
+        let mut branches_by_end: BTreeMap<usize, Vec<LazyExecution>> = BTreeMap::new();
+        for (branch, end) in build_branches(content, &re, i) {
+            if end > i {
+                branches_by_end.entry(end).or_default().push(branch);
+            }
+        }
+
+        // All the counted matches covering i start before it, so its flags
+        // are complete at this point
+        let is_covered = exec.ct_or_all(covered[i].clone());
+        let is_free = exec.ct_not(is_covered);
+        let mut matched_longer = exec.ct_false();
+        for (end, branches) in branches_by_end.into_iter().rev() {
+            let end_res = or_branches(&mut exec, &branches);
+            let not_matched_longer = exec.ct_not(matched_longer.clone());
+            let is_longest = exec.ct_and(end_res.clone(), not_matched_longer);
+            let is_selected = exec.ct_and(is_longest, is_free.clone());
+            for pos_covered in covered.iter_mut().take(end).skip(i + 1) {
+                pos_covered.push(is_selected.clone());
+            }
+            matched_longer = exec.ct_or(matched_longer, end_res);
+        }
+        starts.push(exec.ct_and(matched_longer, is_free).0);
+    }
+    exec.log_ct_operations();
+
+    let ct_zero = exec.ct_false().0;
+    let num_bits_in_block = ct_zero.blocks()[0].message_modulus.0.ilog2();
+    let num_count_bits = usize::BITS - content.len().leading_zeros();
+    let num_blocks = ((num_count_bits + num_bits_in_block - 1) / num_bits_in_block) as usize;
+    let num_extra_blocks = num_blocks.saturating_sub(ct_zero.blocks().len());
+
+    let starts: Vec<RadixCiphertextBig> = starts
+        .par_iter()
+        .map(|ct_start| sk.extend_radix_with_trivial_zero_blocks_msb(ct_start, num_extra_blocks))
+        .collect();
+    Ok(sk
+        .default_binary_op_seq_parallelized(&starts, ServerKey::add_parallelized)
+        .unwrap_or_else(|| {
+            sk.extend_radix_with_trivial_zero_blocks_msb(&ct_zero, num_extra_blocks)
+        }))
+}
+
+/// Evaluates, for each content position, whether a match of the pattern
+/// starts there.
+///
+/// Results in a parallel iterator over `(position, flag)` pairs, where the
+/// flag is an encrypted boolean. The positions go up to the content length
+/// included, where only an empty match can start. ORing all the flags together gives the same
+/// result as `has_match`. The pairs come in no particular order unless
+/// collected into an ordered collection.
+///
+/// The character comparisons shared by all positions are evaluated up front,
+/// before this function returns, on the current rayon thread pool. The rest of
+/// the work is lazy: each position is only evaluated when the iterator is
+/// driven, on the thread pool driving it (so wrapping the consumer in
+/// `ThreadPool::install` also moves the matching there). Each position is
+/// evaluated with its own copy of the cache, hence intermediate results are
+/// not shared between positions and some operations may be repeated compared
+/// to `has_match`.
+#[allow(dead_code)]
+pub fn match_flags_par_iter<'a>(
+    sk: &ServerKey,
+    content: &'a StringCiphertext,
+    pattern: &str,
+) -> Result<impl ParallelIterator<Item = (usize, RadixCiphertextBig)> + 'a> {
+    let re = parse_pattern(pattern, false)?;
+
+    let mut exec = Execution::new(sk.clone(), content);
+    if !is_anchored_at_start(&re) {
+        exec.prefill_char_comparisons(content, &char_comparisons(&re));
+    }
+
+    Ok((0..=content.len()).into_par_iter().map(move |i| {
+        let mut exec = exec.fork();
+        let branches: Vec<LazyExecution> = lazy_branches(content, &re, i);
+        (i, or_branches(&mut exec, &branches).0)
+    }))
+}
+
+/// Evaluates, for each content position, whether a match of the pattern
+/// starts there.
+///
+/// Results in one encrypted boolean per content character, in content order,
+/// the `i`-th one being true if a match starts at position `i`, or an error if
+/// the pattern fails to parse. These are the per position results that
+/// `has_match` ORs together, but for the end of the content, where only an
+/// empty match can start. Decrypting the mask gives every match start, e.g.
+/// for highlighting, and thus also how dense the matches are.
+///
+/// The server only learns the length of the mask, which is the (already
+/// public) content length: every position is evaluated whatever the content.
+/// The positions closer to the end than the shortest match of the pattern
+/// can't start one, and are encrypted false without evaluating anything. The
+/// others are evaluated in parallel, as for `has_match_compiled`.
+pub fn match_mask(
+    sk: &ServerKey,
+    content: &StringCiphertext,
+    pattern: &str,
+) -> Result<Vec<RadixCiphertextBig>> {
+    let compiled = CompiledRegex::compile(pattern)?;
+
+    let mut exec = Execution::new(sk.clone(), content);
+    let num_starts = (content.len() + 1)
+        .saturating_sub(compiled.min_len)
+        .min(content.len());
+    if num_starts > 0 {
+        exec.prefill_char_comparisons(content, &compiled.prefill_comparisons);
+    }
+
+    Ok((0..content.len())
+        .into_par_iter()
+        .map(|i| {
+            let mut exec = exec.fork();
+            if i >= num_starts {
+                return exec.ct_false().0;
+            }
+            let branches: Vec<LazyExecution> = lazy_branches(content, &compiled.re, i);
+            or_branches(&mut exec, &branches).0
+        })
+        .collect())
+}
+
+/// Executes each of the branches and joins their results together with a
+/// homomorphic `or`. Results in an encrypted false when there are no
+/// branches.
+pub(crate) fn or_branches<PBSOrder>(
+    exec: &mut Execution<PBSOrder>,
+    branches: &[LazyExecution<PBSOrder>],
+) -> ExecutedResult<PBSOrder>
+where
+    PBSOrder: PBSOrderMarker,
+    RadixCiphertext<PBSOrder>: IntegerCiphertext,
+{
+    let branch_results = branches.iter().map(|branch| branch(exec)).collect();
+    exec.ct_or_all(branch_results)
+}
+
+/// Collects the comparisons against constant characters that the regex
+/// performs on each content character it is applied to.
+pub(crate) fn char_comparisons(re: &RegExpr) -> Vec<CharComparison> {
+    fn collect(re: &RegExpr, res: &mut Vec<CharComparison>) {
+        match re {
+            RegExpr::Char { c } => res.push(CharComparison::Equal { c: *c }),
+            RegExpr::Range { cs } => res.extend(cs.iter().map(|c| CharComparison::Equal { c: *c })),
+            RegExpr::Between { from, to } => {
+                res.push(CharComparison::GreaterOrEqual { c: *from });
+                res.push(CharComparison::LessOrEqual { c: *to });
+            }
+            RegExpr::Not { not_re } => collect(not_re, res),
+            RegExpr::Either { l_re, r_re } => {
+                collect(l_re, res);
+                collect(r_re, res);
+            }
+            RegExpr::Optional { opt_re } => collect(opt_re, res),
+            RegExpr::Repeated { repeat_re, .. } => collect(repeat_re, res),
+            RegExpr::Seq { re_xs } => re_xs.iter().for_each(|re_x| collect(re_x, res)),
+            RegExpr::Group { group_re, .. } => collect(group_re, res),
+            RegExpr::Sof | RegExpr::Eof | RegExpr::AnyChar => (),
+        }
+    }
+
+    let mut res = vec![];
+    collect(re, &mut res);
+    res.sort();
+    res.dedup();
+    res
+}
+
+/// Extents of the capture groups matched by a branch, indexed by group number.
+/// A group's extent is the content position range `(start, end)` it matched,
+/// or `None` if the branch does not go through the group.
+pub(crate) type Captures = Vec<Option<(usize, usize)>>;
+
+pub(crate) fn build_branches<PBSOrder>(
+    content: &[RadixCiphertext<PBSOrder>],
+    re: &RegExpr,
+    c_pos: usize,
+) -> Vec<(LazyExecution<PBSOrder>, usize)>
+where
+    PBSOrder: PBSOrderMarker,
+    RadixCiphertext<PBSOrder>: IntegerCiphertext,
+{
+    build_capturing_branches(content, re, c_pos)
+        .into_iter()
+        .map(|(branch, c_pos, _)| (branch, c_pos))
+        .collect()
+}
+
+/// Same as `build_branches`, but each branch also carries the extents of the
+/// capture groups it went through. As the extents only depend on the regex
+/// structure and on the content positions, they are known in the clear for
+/// each branch.
+pub(crate) fn build_capturing_branches<PBSOrder>(
+    content: &[RadixCiphertext<PBSOrder>],
     re: &RegExpr,
     c_pos: usize,
-) -> Vec<(LazyExecution, usize)> {
+) -> Vec<(LazyExecution<PBSOrder>, usize, Captures)>
+where
+    PBSOrder: PBSOrderMarker,
+    RadixCiphertext<PBSOrder>: IntegerCiphertext,
+{
     trace!("program pointer: regex={:?}, content pos={}", re, c_pos);
     match re {
         RegExpr::Sof => {
             if c_pos == 0 {
-                return vec![(Rc::new(|exec| exec.ct_true()), c_pos)];
+                return vec![(Rc::new(|exec| exec.ct_true()), c_pos, vec![])];
             } else {
                 return vec![];
             }
         }
         RegExpr::Eof => {
             if c_pos == content.len() {
-                return vec![(Rc::new(|exec| exec.ct_true()), c_pos)];
+                return vec![(Rc::new(|exec| exec.ct_true()), c_pos, vec![])];
             } else {
                 return vec![];
             }
@@ -64,7 +665,17 @@ fn build_branches(
         _ => (),
     };
 
-    if c_pos >= content.len() {
+    // Past the last character, only the constructs that can match the empty
+    // string (e.g. `a*` or `(a?)`) still have branches
+    let consumes_char = matches!(
+        re,
+        RegExpr::Char { .. }
+            | RegExpr::AnyChar
+            | RegExpr::Not { .. }
+            | RegExpr::Between { .. }
+            | RegExpr::Range { .. }
+    );
+    if c_pos > content.len() || (c_pos == content.len() && consumes_char) {
         return vec![];
     }
 
@@ -74,24 +685,36 @@ fn build_branches(
             vec![(
                 Rc::new(move |exec| exec.ct_eq(c_char.clone(), exec.ct_constant(c))),
                 c_pos + 1,
+                vec![],
             )]
         }
-        RegExpr::AnyChar => vec![(Rc::new(|exec| exec.ct_true()), c_pos + 1)],
-        RegExpr::Not { not_re } => build_branches(content, &not_re, c_pos)
-            .into_iter()
-            .map(|(branch, c_pos)| {
-                (
-                    Rc::new(move |exec: &mut Execution| {
-                        let branch_res = branch(exec);
-                        exec.ct_not(branch_res)
-                    }) as LazyExecution,
-                    c_pos,
-                )
-            })
-            .collect(),
+        RegExpr::AnyChar => vec![(Rc::new(|exec| exec.ct_true()), c_pos + 1, vec![])],
+        RegExpr::Not { not_re } => {
+            // The negated branches ending at the same position are ORed before
+            // being negated, so that a negated class made of several ranges
+            // (an `Either`) matches none of them rather than not all of them
+            let mut branches_by_end: BTreeMap<usize, Vec<LazyExecution<PBSOrder>>> =
+                BTreeMap::new();
+            for (branch, c_pos, _) in build_capturing_branches(content, &not_re, c_pos) {
+                branches_by_end.entry(c_pos).or_default().push(branch);
+            }
+            branches_by_end
+                .into_iter()
+                .map(|(c_pos, branches)| {
+                    (
+                        Rc::new(move |exec: &mut Execution<PBSOrder>| {
+                            let branch_res = or_branches(exec, &branches);
+                            exec.ct_not(branch_res)
+                        }) as LazyExecution<PBSOrder>,
+                        c_pos,
+                        vec![],
+                    )
+                })
+                .collect()
+        }
         RegExpr::Either { l_re, r_re } => {
-            let mut res = build_branches(content, &l_re, c_pos);
-            res.append(&mut build_branches(content, &r_re, c_pos));
+            let mut res = build_capturing_branches(content, &l_re, c_pos);
+            res.append(&mut build_capturing_branches(content, &r_re, c_pos));
             res
         }
         RegExpr::Between { from, to } => {
@@ -105,6 +728,7 @@ fn build_branches(
                     exec.ct_and(ge_from, le_to)
                 }),
                 c_pos + 1,
+                vec![],
             )]
         }
         RegExpr::Range { cs } => {
@@ -120,6 +744,7 @@ fn build_branches(
                     )
                 }),
                 c_pos + 1,
+                vec![],
             )]
         }
         RegExpr::Repeated {
@@ -137,13 +762,15 @@ fn build_branches(
             let mut res = vec![
                 if at_least == 0 {
                     vec![(
-                        Rc::new(|exec: &mut Execution| exec.ct_true()) as LazyExecution,
+                        Rc::new(|exec: &mut Execution<PBSOrder>| exec.ct_true())
+                            as LazyExecution<PBSOrder>,
                         c_pos,
+                        vec![],
                     )]
                 } else {
                     vec![]
                 },
-                build_branches(
+                build_capturing_branches(
                     content,
                     &(RegExpr::Seq {
                         re_xs: std::iter::repeat(*repeat_re.clone())
@@ -155,73 +782,108 @@ fn build_branches(
             ];
 
             for _ in (at_least + 1)..(at_most + 1) {
-                res.push(
-                    res.last()
-                        .unwrap()
-                        .iter()
-                        .flat_map(|(branch_prev, branch_c_pos)| {
-                            build_branches(content, &repeat_re, *branch_c_pos)
-                                .into_iter()
-                                .map(move |(branch_x, branch_x_c_pos)| {
-                                    let branch_prev = branch_prev.clone();
-                                    (
-                                        Rc::new(move |exec: &mut Execution| {
-                                            let res_prev = branch_prev(exec);
-                                            let res_x = branch_x(exec);
-                                            exec.ct_and(res_prev, res_x)
-                                        }) as LazyExecution,
-                                        branch_x_c_pos,
-                                    )
-                                })
-                        })
-                        .collect(),
-                );
+                let next = and_then_branches(content, res.last().unwrap(), &repeat_re);
+                res.push(next);
             }
-            res.into_iter().flatten().collect()
+            // Most iterations first, so that the repetitions are greedy when
+            // the branches are tried in order, as `captures` does
+            res.into_iter().rev().flatten().collect()
         }
         RegExpr::Optional { opt_re } => {
-            let mut res = build_branches(content, &opt_re, c_pos);
-            res.push((Rc::new(|exec| exec.ct_true()), c_pos));
+            let mut res = build_capturing_branches(content, &opt_re, c_pos);
+            res.push((Rc::new(|exec| exec.ct_true()), c_pos, vec![]));
             res
         }
         RegExpr::Seq { re_xs } => re_xs[1..].iter().fold(
-            build_branches(content, &re_xs[0], c_pos),
-            |continuations, re_x| {
-                continuations
-                    .into_iter()
-                    .flat_map(|(branch_prev, branch_prev_c_pos)| {
-                        build_branches(content, re_x, branch_prev_c_pos)
-                            .into_iter()
-                            .map(move |(branch_x, branch_x_c_pos)| {
-                                let branch_prev = branch_prev.clone();
-                                (
-                                    Rc::new(move |exec: &mut Execution| {
-                                        let res_prev = branch_prev(exec);
-                                        let res_x = branch_x(exec);
-                                        exec.ct_and(res_prev, res_x)
-                                    }) as LazyExecution,
-                                    branch_x_c_pos,
-                                )
-                            })
-                    })
-                    .collect()
-            },
+            build_capturing_branches(content, &re_xs[0], c_pos),
+            |continuations, re_x| and_then_branches(content, &continuations, re_x),
         ),
+        RegExpr::Group { group_re, index } => build_capturing_branches(content, &group_re, c_pos)
+            .into_iter()
+            .map(|(branch, group_end, mut captures)| {
+                if captures.len() <= index {
+                    captures.resize(index + 1, None);
+                }
+                captures[index] = Some((c_pos, group_end));
+                (branch, group_end, captures)
+            })
+            .collect(),
         _ => panic!("unmatched regex variant"),
     }
 }
 
+/// Continues each of the branches with `re`, matched from where the branch
+/// ended. When a group is captured both before and in `re`, the extent from
+/// `re` wins, so a group inside a repetition captures its last iteration.
+fn and_then_branches<PBSOrder>(
+    content: &[RadixCiphertext<PBSOrder>],
+    continuations: &[(LazyExecution<PBSOrder>, usize, Captures)],
+    re: &RegExpr,
+) -> Vec<(LazyExecution<PBSOrder>, usize, Captures)>
+where
+    PBSOrder: PBSOrderMarker,
+    RadixCiphertext<PBSOrder>: IntegerCiphertext,
+{
+    continuations
+        .iter()
+        .flat_map(|(branch_prev, branch_prev_c_pos, captures_prev)| {
+            build_capturing_branches(content, re, *branch_prev_c_pos)
+                .into_iter()
+                .map(move |(branch_x, branch_x_c_pos, captures_x)| {
+                    let branch_prev = branch_prev.clone();
+                    let mut captures = captures_prev.clone();
+                    if captures.len() < captures_x.len() {
+                        captures.resize(captures_x.len(), None);
+                    }
+                    for (capture, capture_x) in captures.iter_mut().zip(captures_x) {
+                        if capture_x.is_some() {
+                            *capture = capture_x;
+                        }
+                    }
+                    (
+                        Rc::new(move |exec: &mut Execution<PBSOrder>| {
+                            let res_prev = branch_prev(exec);
+                            let res_x = branch_x(exec);
+                            exec.ct_and(res_prev, res_x)
+                        }) as LazyExecution<PBSOrder>,
+                        branch_x_c_pos,
+                        captures,
+                    )
+                })
+        })
+        .collect()
+}
+
 #[cfg(test)]
-mod tests {
-    use crate::engine::has_match;
+pub(crate) mod tests {
+    use crate::engine::{
+        count_matches, ends_with, has_match, has_match_any, has_match_any_of, has_match_capped,
+        has_match_each, has_match_in_pool, has_match_with_options, match_flags_par_iter,
+        match_mask, matches_at, matches_at_with_options, starts_with, MatchOptions,
+    };
+    use rayon::prelude::*;
     use test_case::test_case;
 
-    use crate::ciphertext::{encrypt_str, gen_keys, StringCiphertext};
+    use crate::ciphertext::{encrypt_str, encrypt_str_small, gen_keys_default, StringCiphertext};
     use lazy_static::lazy_static;
-    use tfhe::integer::{RadixClientKey, ServerKey};
+    use tfhe::integer::{RadixCiphertextBig, RadixCiphertextSmall, RadixClientKey, ServerKey};
+    use tfhe::shortint::ciphertext::BootstrapKeyswitch;
 
     lazy_static! {
-        pub static ref KEYS: (RadixClientKey, ServerKey) = gen_keys();
+        pub static ref KEYS: (RadixClientKey, ServerKey) = gen_keys_default();
+    }
+
+    /// Encrypts `content` with `KEYS`, computes `f` on it and checks that the
+    /// result decrypts to `exp`.
+    pub fn assert_decrypted_result<F>(content: &str, exp: u64, f: F)
+    where
+        F: FnOnce(&ServerKey, &StringCiphertext) -> RadixCiphertextBig,
+    {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, content).unwrap();
+        let ct_res = f(&KEYS.1, &ct_content);
+
+        let got: u64 = KEYS.0.decrypt(&ct_res);
+        assert_eq!(exp, got);
     }
 
     #[test_case("ab", "/ab/", 1)]
@@ -252,8 +914,59 @@ mod tests {
     #[test_case("4453", "/^[0-9]*$/", 1)]
     #[test_case("4453", "/^[09]*$/", 0)]
     #[test_case("09009", "/^[09]*$/", 1)]
+    #[test_case("aaa", "/a{2,4}/", 1 ; "repeat between")]
+    #[test_case("xax", "/^xa{2}/", 0 ; "exact repeat not reached")]
+    #[test_case("xaaab", "/^xa{2,}b$/", 1 ; "at least repeat")]
+    #[test_case("xaaab", "/^xa{,2}b$/", 0 ; "at most repeat exceeded")]
+    #[test_case("a", "/[a-d]/", 1 ; "range includes lower bound")]
+    #[test_case("x7", "/^[a-z][0-9]$/", 1 ; "consecutive classes")]
+    #[test_case("Q", "/[a-z0-9]/", 0 ; "ranges and no match")]
+    #[test_case("5", "/[a-z0-9_]/", 1 ; "ranges and single characters")]
+    #[test_case("a", "/[^0-9]/", 1 ; "negated range")]
+    #[test_case("5", "/[^0-9]/", 0 ; "negated range and no match")]
+    #[test_case("5", "/[^a-z0-9]/", 0 ; "negated ranges match none of them")]
+    #[test_case("-", "/[^a-z0-9]/", 1 ; "negated ranges")]
+    #[test_case("]", "/[]a]/", 1 ; "leading bracket is literal")]
+    #[test_case("b", "/[^]a]/", 1 ; "leading bracket is literal in negated class")]
+    #[test_case("]", "/[^]a]/", 0 ; "negated literal bracket")]
+    #[test_case("~", "/[\\x00-\\xff]/", 1 ; "full byte range")]
+    #[test_case("~", "/[^\\x00-\\xff]/", 0 ; "negated full byte range")]
     #[test_case("de", "/^ab|cd|de$/", 1 ; "multiple or")]
     #[test_case(" de", "/^ab|cd|de$/", 0 ; "multiple or nests below ^")]
+    #[test_case("ab", "/abc/", 0 ; "content shorter than the pattern")]
+    #[test_case("xabc", "/abc/", 1 ; "match ending at the end of the content")]
+    #[test_case("abc", "/a{2}|bc$/", 1 ; "shortest alternative ending at the end")]
+    #[test_case("aaa", "/^a+$/", 1 ; "one or more anchored")]
+    #[test_case("aab", "/^a+$/", 0 ; "one or more anchored followed by another char")]
+    #[test_case("", "/^a+$/", 0 ; "one or more anchored on empty content")]
+    #[test_case("", "/^a?$/", 1 ; "optional anchored on empty content")]
+    #[test_case("aa", "/^a?$/", 0 ; "optional anchored repeated")]
+    #[test_case("a bird", "/cat|dog|bird/", 1 ; "last of several alternatives")]
+    #[test_case("a7", "/\\d/", 1 ; "digit shorthand")]
+    #[test_case("ab", "/\\d/", 0 ; "digit shorthand without digits")]
+    #[test_case("7", "/\\D/", 0 ; "negated digit shorthand")]
+    #[test_case("~_", "/^\\W\\w$/", 1 ; "word shorthands")]
+    #[test_case("a\tb", "/a\\sb/", 1 ; "space shorthand")]
+    #[test_case("a b", "/a\\Sb/", 0 ; "negated space shorthand")]
+    #[test_case("12-3", "/^[\\d-]+$/", 1 ; "shorthand in class")]
+    #[test_case("3.14", "/^\\d\\.\\d+$/", 1 ; "shorthand and escaped dot")]
+    #[test_case("cde", "/(ab|cd)e/", 1 ; "alternatives in group")]
+    #[test_case("abd", "/(ab|cd)e/", 0 ; "alternatives in group followed by a char")]
+    #[test_case("acd", "/^a(b|c)d$/", 1 ; "alternatives of single chars in group")]
+    #[test_case("xAy", "/\\x41/", 1 ; "hex escape")]
+    #[test_case("xay", "/\\x41/", 0 ; "hex escape is case sensitive")]
+    #[test_case("a\tb", "/a\\tb/", 1 ; "tab escape")]
+    #[test_case("", "/^$/", 1 ; "empty pattern on empty content")]
+    #[test_case("a", "/^$/", 0 ; "empty pattern on non empty content")]
+    #[test_case("", "/$/", 1 ; "end anchor on empty content")]
+    #[test_case("ab", "/$/", 1 ; "end anchor alone")]
+    #[test_case("", "/^a*$/", 1 ; "empty repetition on empty content")]
+    #[test_case("", "/a/", 0 ; "char on empty content")]
+    #[test_case("xababy", "/^x(?:ab)+y$/", 1 ; "repeated non-capturing group")]
+    #[test_case("xaby", "/^x(?:ab){2}y$/", 0 ; "repeated non-capturing group too few times")]
+    #[test_case("abcabd", "/^(?:ab(?:c|d))+$/", 1 ; "nested non-capturing groups")]
+    #[test_case("abcabe", "/^(?:ab(?:c|d))+$/", 0 ; "nested non-capturing groups no match")]
+    #[test_case("aab", "/^(?:(a)|b)*$/", 1 ; "capturing group in non-capturing group")]
     fn test_has_match(content: &str, pattern: &str, exp: u64) {
         let ct_content: StringCiphertext = encrypt_str(&KEYS.0, content).unwrap();
         let ct_res = has_match(&KEYS.1, &ct_content, pattern).unwrap();
@@ -261,4 +974,323 @@ mod tests {
         let got = KEYS.0.decrypt(&ct_res);
         assert_eq!(exp, got);
     }
+
+    #[test_case("ab", "/ab/", 1)]
+    #[test_case("b", "/ab/", 0)]
+    #[test_case("cdaabc", "/a*bc/", 1)]
+    #[test_case("abcd", "/^ab|cd$/", 0)]
+    #[test_case("a.c", "/a[^b]c/", 1)]
+    fn test_has_match_small(content: &str, pattern: &str, exp: u64) {
+        let ct_content: StringCiphertext<BootstrapKeyswitch> =
+            encrypt_str_small(&KEYS.0, content).unwrap();
+        let ct_res: RadixCiphertextSmall = has_match(&KEYS.1, &ct_content, pattern).unwrap();
+
+        let got = KEYS.0.decrypt(&ct_res);
+        assert_eq!(exp, got);
+    }
+
+    #[test_case("aab", "/^a*b$/", 2, 1)]
+    #[test_case("aaab", "/^a*b$/", 2, 0 ; "more repetitions than the cap")]
+    #[test_case("aaab", "/^a+b$/", 3, 1)]
+    #[test_case("aaab", "/^a{3,}b$/", 2, 0 ; "least limit above the cap")]
+    fn test_has_match_capped(content: &str, pattern: &str, max_repeat: usize, exp: u64) {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, content).unwrap();
+        let ct_res = has_match_capped(&KEYS.1, &ct_content, pattern, Some(max_repeat)).unwrap();
+
+        let got = KEYS.0.decrypt(&ct_res);
+        assert_eq!(exp, got);
+    }
+
+    #[test]
+    fn test_has_match_sub_ranges() {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, "key=value").unwrap();
+        let (ct_key, ct_value) = ct_content.split_at(3);
+
+        for (ct_range, pattern, exp) in [
+            (ct_key, "/^key$/", true),
+            (&ct_value[1..], "/^value$/", true),
+            (&ct_content[..], "/^key$/", false),
+            (ct_value, "/key/", false),
+        ] {
+            let ct_range = StringCiphertext::try_from(ct_range).unwrap();
+            let ct_res = has_match(&KEYS.1, &ct_range, pattern).unwrap();
+            assert_eq!(exp, KEYS.0.decrypt_bool(&ct_res).unwrap(), "{pattern}");
+        }
+        assert!(has_match(&KEYS.1, &ct_content, "/a{2,1}/").is_err());
+    }
+
+    #[test_case("aaaaa", "/aa/", 2 ; "non overlapping")]
+    #[test_case("abcabxabc", "/abc/", 2 ; "literal")]
+    #[test_case("aab ab b", "/a*b/", 3 ; "longest match at each start")]
+    #[test_case("xyz", "/x*/", 1 ; "only non empty matches counted")]
+    #[test_case("abc", "/x*/", 0 ; "only empty matches")]
+    #[test_case("xyz", "/ab/", 0 ; "no match")]
+    #[test_case("", "/a/", 0 ; "empty content")]
+    fn test_count_matches(content: &str, pattern: &str, exp: u64) {
+        assert_decrypted_result(content, exp, |sk, ct_content| {
+            count_matches(sk, ct_content, pattern).unwrap()
+        });
+    }
+
+    #[test_case("ERROR: disk", "/error/", 1 ; "letters of both cases")]
+    #[test_case("Error 42!", "/r 42!/", 1 ; "digits and punctuation untouched")]
+    #[test_case("@", "/`/", 0 ; "no folding outside of letters")]
+    #[test_case("XaB", "/^xAb$/", 1 ; "anchored")]
+    fn test_has_match_case_insensitive(content: &str, pattern: &str, exp: u64) {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, content).unwrap();
+        let options = MatchOptions {
+            case_insensitive: true,
+            ..MatchOptions::default()
+        };
+        let ct_res = has_match_with_options(&KEYS.1, &ct_content, pattern, options).unwrap();
+
+        let got: u64 = KEYS.0.decrypt(&ct_res);
+        assert_eq!(exp, got);
+
+        // Same as with the pattern flag
+        let ct_res = has_match(&KEYS.1, &ct_content, &format!("{pattern}i")).unwrap();
+        let got: u64 = KEYS.0.decrypt(&ct_res);
+        assert_eq!(exp, got);
+    }
+
+    #[test_case("axb", "/a.b/", 1 ; "any character")]
+    #[test_case("a\nb", "/a.b/", 0 ; "any character but newline")]
+    #[test_case("a\nb", "/a.b/s", 1 ; "newline with the s flag")]
+    #[test_case("a.b", "/a\\.b/", 1 ; "escaped dot")]
+    #[test_case("axb", "/a\\.b/", 0 ; "escaped dot is literal")]
+    #[test_case("ab", "/a.b/", 0 ; "any character requires a character")]
+    #[test_case("a\nxb", "/^a.*b$/", 0 ; "repeated any character stops at newline")]
+    fn test_has_match_dot(content: &str, pattern: &str, exp: u64) {
+        assert_decrypted_result(content, exp, |sk, ct_content| {
+            has_match(sk, ct_content, pattern).unwrap()
+        });
+    }
+
+    #[test]
+    fn test_has_match_dot_matches_newline() {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, "a\nxb").unwrap();
+        let options = MatchOptions {
+            dot_matches_newline: true,
+            ..MatchOptions::default()
+        };
+        let ct_res = has_match_with_options(&KEYS.1, &ct_content, "/^a.*b$/", options).unwrap();
+
+        assert!(KEYS.0.decrypt_bool(&ct_res).unwrap());
+    }
+
+    #[test_case("ab", "/ab/")]
+    #[test_case("cdaabc", "/a*bc/")]
+    #[test_case("123abdc456", "/abc/")]
+    #[test_case("abcd", "/^ab|cd$/")]
+    fn test_match_flags_par_iter(content: &str, pattern: &str) {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, content).unwrap();
+
+        let mut flags: Vec<(usize, RadixCiphertextBig)> =
+            match_flags_par_iter(&KEYS.1, &ct_content, pattern)
+                .unwrap()
+                .collect();
+        flags.sort_by_key(|(i, _)| *i);
+        let positions: Vec<usize> = flags.iter().map(|(i, _)| *i).collect();
+        assert_eq!((0..=content.len()).collect::<Vec<_>>(), positions);
+
+        let ct_res = flags
+            .into_iter()
+            .map(|(_, flag)| flag)
+            .reduce(|res, flag| KEYS.1.bitor_parallelized(&res, &flag))
+            .unwrap();
+
+        let exp: u64 = KEYS
+            .0
+            .decrypt(&has_match(&KEYS.1, &ct_content, pattern).unwrap());
+        let got: u64 = KEYS.0.decrypt(&ct_res);
+        assert_eq!(exp, got);
+    }
+
+    #[test]
+    fn test_has_match_budget() {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, "xabcx").unwrap();
+        let options = MatchOptions {
+            max_ct_operations: Some(100),
+            ..MatchOptions::default()
+        };
+
+        let ct_res = has_match_with_options(&KEYS.1, &ct_content, "/abc/", options).unwrap();
+        let got: u64 = KEYS.0.decrypt(&ct_res);
+        assert_eq!(1, got);
+
+        assert!(has_match_with_options(&KEYS.1, &ct_content, "/.*.*.*/", options).is_err());
+    }
+
+    #[test_case("xabxab", "/ab/", &[0, 1, 0, 0, 1, 0] ; "several matches")]
+    #[test_case("aaa", "/aa/", &[1, 1, 0] ; "overlapping matches")]
+    #[test_case("abab", "/^ab/", &[1, 0, 0, 0] ; "anchored at the start")]
+    #[test_case("abab", "/ab$/", &[0, 0, 1, 0] ; "anchored at the end")]
+    #[test_case("abc", "/x/", &[0, 0, 0] ; "no match")]
+    #[test_case("ab", "/b*/", &[1, 1] ; "empty matches")]
+    #[test_case("", "/a/", &[] ; "empty content")]
+    fn test_match_mask(content: &str, pattern: &str, exp: &[u64]) {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, content).unwrap();
+        let ct_res = match_mask(&KEYS.1, &ct_content, pattern).unwrap();
+
+        let got: Vec<u64> = ct_res.iter().map(|ct| KEYS.0.decrypt(ct)).collect();
+        assert_eq!(exp, got);
+    }
+
+    #[test_case("xabx", "/ab/", 1, 1)]
+    #[test_case("xabx", "/ab/", 0, 0 ; "match elsewhere")]
+    #[test_case("xabx", "/ab/", 3, 0 ; "remaining content too short")]
+    #[test_case("xabx", "/a?bx/", 2, 1 ; "optional not taken")]
+    #[test_case("abab", "/^ab/", 2, 0 ; "anchored at the start")]
+    #[test_case("abab", "/ab$/", 2, 1 ; "anchored at the end")]
+    fn test_matches_at(content: &str, pattern: &str, pos: usize, exp: u64) {
+        assert_decrypted_result(content, exp, |sk, ct_content| {
+            matches_at(sk, ct_content, pattern, pos).unwrap()
+        });
+    }
+
+    #[test_case("cdaabc", "/a*bc/")]
+    #[test_case("abcd", "/^ab|cd$/")]
+    fn test_matches_at_all_positions_is_has_match(content: &str, pattern: &str) {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, content).unwrap();
+
+        let got = (0..content.len())
+            .map(|pos| -> u64 {
+                KEYS.0
+                    .decrypt(&matches_at(&KEYS.1, &ct_content, pattern, pos).unwrap())
+            })
+            .fold(0, |res, x| res | x);
+        let exp: u64 = KEYS
+            .0
+            .decrypt(&has_match(&KEYS.1, &ct_content, pattern).unwrap());
+        assert_eq!(exp, got);
+    }
+
+    #[test_case("xAbx", "/ab/", 1, 1 ; "case insensitive")]
+    #[test_case("xaaab", "/a+b/", 1, 0 ; "more repetitions than the cap")]
+    #[test_case("xaaab", "/a+b/", 2, 1 ; "repetitions within the cap")]
+    fn test_matches_at_with_options(content: &str, pattern: &str, pos: usize, exp: u64) {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, content).unwrap();
+        let options = MatchOptions {
+            case_insensitive: true,
+            max_repeat: Some(2),
+            ..MatchOptions::default()
+        };
+        let ct_res = matches_at_with_options(&KEYS.1, &ct_content, pattern, pos, options).unwrap();
+
+        let got: u64 = KEYS.0.decrypt(&ct_res);
+        assert_eq!(exp, got);
+    }
+
+    #[test]
+    fn test_matches_at_over_budget() {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, "abcdef").unwrap();
+        let options = MatchOptions {
+            max_ct_operations: Some(10),
+            ..MatchOptions::default()
+        };
+        assert!(matches_at_with_options(&KEYS.1, &ct_content, "/.*.*.*/", 0, options).is_err());
+    }
+
+    #[test]
+    fn test_matches_at_rejects_out_of_range_position() {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, "ab").unwrap();
+        assert!(matches_at(&KEYS.1, &ct_content, "/ab/", 3).is_err());
+    }
+
+    #[test_case(Some(1) ; "pool of one thread")]
+    #[test_case(Some(2) ; "pool of two threads")]
+    #[test_case(None ; "current pool")]
+    fn test_has_match_in_pool(num_threads: Option<usize>) {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, "cdaabc").unwrap();
+
+        let pool = num_threads.map(|num_threads| {
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(num_threads)
+                .build()
+                .unwrap()
+        });
+        let ct_res = has_match_in_pool(&KEYS.1, &ct_content, "/a*bc/", pool.as_ref()).unwrap();
+        let got: u64 = KEYS.0.decrypt(&ct_res);
+        assert_eq!(1, got);
+
+        let ct_res = has_match_in_pool(&KEYS.1, &ct_content, "/a*bd/", pool.as_ref()).unwrap();
+        let got: u64 = KEYS.0.decrypt(&ct_res);
+        assert_eq!(0, got);
+    }
+
+    #[test]
+    fn test_has_match_any_running_result() {
+        let chunks = ["xxab", "xcdx", "abxx", "xxxx"];
+        // Only the second chunk matches on its own
+        let exp_running = [0, 1, 1, 1];
+
+        let mut ct_running: Option<RadixCiphertextBig> = None;
+        for (chunk, exp) in chunks.iter().zip(exp_running) {
+            let ct_chunk: StringCiphertext = encrypt_str(&KEYS.0, chunk).unwrap();
+            let ct_res = has_match_any(&KEYS.1, ct_running.as_ref(), &ct_chunk, "/cd/").unwrap();
+
+            let got: u64 = KEYS.0.decrypt(&ct_res);
+            assert_eq!(exp, got);
+            ct_running = Some(ct_res);
+        }
+    }
+
+    #[test_case("abc", &["/d/", "/b/", "/^c/"], &[0, 1, 0])]
+    #[test_case("abc", &["/^ab/", "/c$/"], &[1, 1])]
+    #[test_case("abc", &[], &[])]
+    fn test_has_match_each(content: &str, patterns: &[&str], exp: &[u64]) {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, content).unwrap();
+        let ct_res = has_match_each(&KEYS.1, &ct_content, patterns).unwrap();
+
+        let got: Vec<u64> = ct_res.iter().map(|ct| KEYS.0.decrypt(ct)).collect();
+        assert_eq!(exp, got);
+    }
+
+    #[test_case("abc", &["/d/", "/b/", "/^c/"], 1)]
+    #[test_case("abc", &["/d/", "/^c/"], 0)]
+    #[test_case("abc", &[], 0)]
+    fn test_has_match_any_of(content: &str, patterns: &[&str], exp: u64) {
+        assert_decrypted_result(content, exp, |sk, ct_content| {
+            has_match_any_of(sk, ct_content, patterns).unwrap()
+        });
+    }
+
+    #[test]
+    fn test_has_match_each_invalid_pattern() {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, "abc").unwrap();
+        assert!(has_match_each(&KEYS.1, &ct_content, &["/a/", "/a{2,1}/"]).is_err());
+    }
+
+    #[test_case("abcd", "ab", 1 ; "prefix")]
+    #[test_case("abcd", "abcd", 1 ; "whole content")]
+    #[test_case("abcd", "bc", 0 ; "not at the start")]
+    #[test_case("abcd", "abcde", 0 ; "longer than the content")]
+    #[test_case("abcd", "", 1 ; "empty prefix")]
+    #[test_case("", "", 1 ; "empty prefix on empty content")]
+    #[test_case("a.cd", "a.", 1 ; "symbols matched literally")]
+    #[test_case("abcd", "a.", 0 ; "symbols not matched as a pattern")]
+    fn test_starts_with(content: &str, prefix: &str, exp: u64) {
+        assert_decrypted_result(content, exp, |sk, ct_content| {
+            starts_with(sk, ct_content, prefix).unwrap()
+        });
+    }
+
+    #[test_case("abcd", "cd", 1 ; "suffix")]
+    #[test_case("abcd", "abcd", 1 ; "whole content")]
+    #[test_case("abcd", "bc", 0 ; "not at the end")]
+    #[test_case("abcd", "xabcd", 0 ; "longer than the content")]
+    #[test_case("abcd", "", 1 ; "empty suffix")]
+    #[test_case("ab$", "b$", 1 ; "symbols matched literally")]
+    fn test_ends_with(content: &str, suffix: &str, exp: u64) {
+        assert_decrypted_result(content, exp, |sk, ct_content| {
+            ends_with(sk, ct_content, suffix).unwrap()
+        });
+    }
+
+    #[test]
+    fn test_starts_with_rejects_non_ascii() {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, "abcd").unwrap();
+        assert!(starts_with(&KEYS.1, &ct_content, "é").is_err());
+        assert!(ends_with(&KEYS.1, &ct_content, "é").is_err());
+    }
 }
@@ -1,8 +1,15 @@
+use rayon::prelude::*;
 use std::collections::HashMap;
 use std::rc::Rc;
-use tfhe::integer::{RadixCiphertextBig, ServerKey};
+use std::sync::Arc;
+use tfhe::integer::ciphertext::RadixCiphertext;
+use tfhe::integer::{IntegerCiphertext, ServerKey};
+use tfhe::shortint::ciphertext::KeyswitchBootstrap;
+use tfhe::shortint::PBSOrderMarker;
 
-use crate::parser::u8_to_char;
+use crate::ciphertext::{char_num_blocks, max_char};
+use crate::engine::build_branches;
+use crate::parser::{u8_to_char, RegExpr};
 
 #[derive(Clone, PartialEq, Eq, Hash)]
 pub(crate) enum Executed {
@@ -15,7 +22,10 @@ pub(crate) enum Executed {
     LessOrEqual { a: Box<Executed>, b: Box<Executed> },
     Not { a: Box<Executed> },
 }
-type ExecutedResult = (RadixCiphertextBig, Executed);
+// The engine works on `RadixCiphertextBig` characters unless told otherwise,
+// see `encrypt_str_small`
+pub(crate) type ExecutedResult<PBSOrder = KeyswitchBootstrap> =
+    (RadixCiphertext<PBSOrder>, Executed);
 
 impl Executed {
     pub(crate) fn ct_pos(at: usize) -> Self {
@@ -30,28 +40,94 @@ impl Executed {
     }
 }
 
+/// Comparison of a content character against a constant character.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub(crate) enum CharComparison {
+    Equal { c: u8 },
+    GreaterOrEqual { c: u8 },
+    LessOrEqual { c: u8 },
+}
+
 const CT_FALSE: u8 = 0;
 const CT_TRUE: u8 = 1;
 
-pub(crate) struct Execution {
-    sk: ServerKey,
-    cache: HashMap<Executed, RadixCiphertextBig>,
+pub(crate) struct Execution<PBSOrder: PBSOrderMarker = KeyswitchBootstrap> {
+    sk: Arc<ServerKey>,
+    cache: HashMap<Executed, RadixCiphertext<PBSOrder>>,
+    // Width of the content characters, which the constants are created with,
+    // and largest character these blocks can hold
+    num_blocks: usize,
+    max_char: u64,
 
     ct_ops: usize,
     cache_hits: usize,
 }
-pub(crate) type LazyExecution = Rc<dyn Fn(&mut Execution) -> ExecutedResult>;
+pub(crate) type LazyExecution<PBSOrder = KeyswitchBootstrap> =
+    Rc<dyn Fn(&mut Execution<PBSOrder>) -> ExecutedResult<PBSOrder>>;
+
+/// Builds the branches of the regex matching from content position `c_pos`,
+/// without the positions they end at.
+pub(crate) fn lazy_branches<PBSOrder>(
+    content: &[RadixCiphertext<PBSOrder>],
+    re: &RegExpr,
+    c_pos: usize,
+) -> Vec<LazyExecution<PBSOrder>>
+where
+    PBSOrder: PBSOrderMarker,
+    RadixCiphertext<PBSOrder>: IntegerCiphertext,
+{
+    build_branches(content, re, c_pos)
+        .into_iter()
+        .map(|(lazy_branch_res, _)| lazy_branch_res)
+        .collect()
+}
+
+/// Logs the number of ciphertext operations of a computation, and how many
+/// more were saved by the cache.
+pub(crate) fn log_ct_operations(ct_ops: usize, cache_hits: usize) {
+    info!(
+        "{} ciphertext operations, {} cache hits",
+        ct_ops, cache_hits
+    );
+}
 
-impl Execution {
-    pub(crate) fn new(sk: ServerKey) -> Self {
+impl<PBSOrder: PBSOrderMarker> Execution<PBSOrder>
+where
+    RadixCiphertext<PBSOrder>: IntegerCiphertext,
+{
+    /// Creates an execution on the characters of `content`, whose constants
+    /// have the same number of blocks as these characters.
+    pub(crate) fn new(sk: ServerKey, content: &[RadixCiphertext<PBSOrder>]) -> Self {
         Self {
-            sk,
+            sk: Arc::new(sk),
             cache: HashMap::new(),
+            num_blocks: char_num_blocks(content),
+            max_char: max_char(content),
             ct_ops: 0,
             cache_hits: 0,
         }
     }
 
+    /// Creates an independent execution sharing the server key, starting from
+    /// a copy of the results cached so far. Results computed by the fork are
+    /// not shared back.
+    pub(crate) fn fork(&self) -> Self {
+        Self {
+            sk: self.sk.clone(),
+            cache: self.cache.clone(),
+            num_blocks: self.num_blocks,
+            max_char: self.max_char,
+            ct_ops: 0,
+            cache_hits: 0,
+        }
+    }
+
+    /// Logs the ciphertext operations and the cache hits of the execution so
+    /// far, see `log_ct_operations`.
+    pub(crate) fn log_ct_operations(&self) {
+        log_ct_operations(self.ct_ops, self.cache_hits);
+    }
+
     pub(crate) fn ct_operations_count(&self) -> usize {
         self.ct_ops
     }
@@ -60,14 +136,98 @@ impl Execution {
         self.cache_hits
     }
 
-    pub(crate) fn ct_eq(&mut self, a: ExecutedResult, b: ExecutedResult) -> ExecutedResult {
+    /// Evaluates each of the comparisons against every content character in a
+    /// single parallel stage, and stores the results in the cache.
+    ///
+    /// These comparisons don't depend on each other, but when evaluated through
+    /// the lazy branches their bootstraps are issued one at a time. Evaluating
+    /// them up front lowers the latency, at the cost of also evaluating the
+    /// comparisons at positions that no branch would have reached.
+    pub(crate) fn prefill_char_comparisons(
+        &mut self,
+        content: &[RadixCiphertext<PBSOrder>],
+        comparisons: &[CharComparison],
+    ) {
+        self.prefill_char_comparisons_from(content, 0, comparisons)
+    }
+
+    /// Same as `prefill_char_comparisons`, but only for the content characters
+    /// from position `from`, for when no branch starts before it.
+    pub(crate) fn prefill_char_comparisons_from(
+        &mut self,
+        content: &[RadixCiphertext<PBSOrder>],
+        from: usize,
+        comparisons: &[CharComparison],
+    ) {
+        let sk = &self.sk;
+        let cache = &self.cache;
+        let num_blocks = self.num_blocks;
+        let max_char = self.max_char;
+        let res: Vec<(Executed, RadixCiphertext<PBSOrder>)> = (from..content.len())
+            .flat_map(|at| comparisons.iter().map(move |cmp| (at, *cmp)))
+            .collect::<Vec<_>>()
+            .into_par_iter()
+            .filter_map(|(at, cmp)| {
+                let (ctx, c) = match cmp {
+                    CharComparison::Equal { c } => (
+                        Executed::Equal {
+                            a: Box::new(Executed::ct_pos(at)),
+                            b: Box::new(Executed::Constant { c }),
+                        },
+                        c,
+                    ),
+                    CharComparison::GreaterOrEqual { c } => (
+                        Executed::GreaterOrEqual {
+                            a: Box::new(Executed::ct_pos(at)),
+                            b: Box::new(Executed::Constant { c }),
+                        },
+                        c,
+                    ),
+                    CharComparison::LessOrEqual { c } => (
+                        Executed::LessOrEqual {
+                            a: Box::new(Executed::ct_pos(at)),
+                            b: Box::new(Executed::Constant { c }),
+                        },
+                        c,
+                    ),
+                };
+                // The comparisons against constants the characters can't hold
+                // are known, and left to `ct_eq`, `ct_ge` and `ct_le`
+                if cache.contains_key(&ctx) || c as u64 > max_char {
+                    return None;
+                }
+
+                let mut ct_a = content[at].clone();
+                let mut ct_b = sk.create_trivial_radix(c as u64, num_blocks);
+                let ct_res = match cmp {
+                    CharComparison::Equal { .. } => sk.smart_eq(&mut ct_a, &mut ct_b),
+                    CharComparison::GreaterOrEqual { .. } => sk.smart_ge(&mut ct_a, &mut ct_b),
+                    CharComparison::LessOrEqual { .. } => sk.smart_le(&mut ct_a, &mut ct_b),
+                };
+                Some((ctx, ct_res))
+            })
+            .collect();
+
+        debug!("prefilled {} character comparisons", res.len());
+        self.ct_ops += res.len();
+        self.cache.extend(res);
+    }
+
+    pub(crate) fn ct_eq(
+        &mut self,
+        a: ExecutedResult<PBSOrder>,
+        b: ExecutedResult<PBSOrder>,
+    ) -> ExecutedResult<PBSOrder> {
+        if self.is_above_max_char(&b) {
+            return self.ct_false();
+        }
         let ctx = Executed::Equal {
             a: Box::new(a.1.clone()),
             b: Box::new(b.1.clone()),
         };
         self.with_cache(
             ctx.clone(),
-            Rc::new(move |exec: &mut Execution| {
+            Rc::new(move |exec: &mut Execution<PBSOrder>| {
                 exec.ct_ops += 1;
 
                 let mut ct_a = a.0.clone();
@@ -77,7 +237,14 @@ impl Execution {
         )
     }
 
-    pub(crate) fn ct_ge(&mut self, a: ExecutedResult, b: ExecutedResult) -> ExecutedResult {
+    pub(crate) fn ct_ge(
+        &mut self,
+        a: ExecutedResult<PBSOrder>,
+        b: ExecutedResult<PBSOrder>,
+    ) -> ExecutedResult<PBSOrder> {
+        if self.is_above_max_char(&b) {
+            return self.ct_false();
+        }
         let ctx = Executed::GreaterOrEqual {
             a: Box::new(a.1.clone()),
             b: Box::new(b.1.clone()),
@@ -89,12 +256,19 @@ impl Execution {
 
                 let mut ct_a = a.0.clone();
                 let mut ct_b = b.0.clone();
-                (exec.sk.smart_gt(&mut ct_a, &mut ct_b), ctx.clone())
+                (exec.sk.smart_ge(&mut ct_a, &mut ct_b), ctx.clone())
             }),
         )
     }
 
-    pub(crate) fn ct_le(&mut self, a: ExecutedResult, b: ExecutedResult) -> ExecutedResult {
+    pub(crate) fn ct_le(
+        &mut self,
+        a: ExecutedResult<PBSOrder>,
+        b: ExecutedResult<PBSOrder>,
+    ) -> ExecutedResult<PBSOrder> {
+        if self.is_above_max_char(&b) {
+            return self.ct_true();
+        }
         let ctx = Executed::LessOrEqual {
             a: Box::new(a.1.clone()),
             b: Box::new(b.1.clone()),
@@ -111,7 +285,11 @@ impl Execution {
         )
     }
 
-    pub(crate) fn ct_and(&mut self, a: ExecutedResult, b: ExecutedResult) -> ExecutedResult {
+    pub(crate) fn ct_and(
+        &mut self,
+        a: ExecutedResult<PBSOrder>,
+        b: ExecutedResult<PBSOrder>,
+    ) -> ExecutedResult<PBSOrder> {
         let ctx = Executed::And {
             a: Box::new(a.1.clone()),
             b: Box::new(b.1.clone()),
@@ -144,7 +322,11 @@ impl Execution {
         )
     }
 
-    pub(crate) fn ct_or(&mut self, a: ExecutedResult, b: ExecutedResult) -> ExecutedResult {
+    pub(crate) fn ct_or(
+        &mut self,
+        a: ExecutedResult<PBSOrder>,
+        b: ExecutedResult<PBSOrder>,
+    ) -> ExecutedResult<PBSOrder> {
         let ctx = Executed::Or {
             a: Box::new(a.1.clone()),
             b: Box::new(b.1.clone()),
@@ -174,7 +356,48 @@ impl Execution {
         )
     }
 
-    pub(crate) fn ct_not(&mut self, a: ExecutedResult) -> ExecutedResult {
+    /// Joins all the results together with a homomorphic `or`. Results in an
+    /// encrypted false when there are no results.
+    ///
+    /// The results are reduced all at once with `ServerKey::or_reduce`, which is
+    /// cheaper than one `ct_or` per result.
+    pub(crate) fn ct_or_all(
+        &mut self,
+        results: Vec<ExecutedResult<PBSOrder>>,
+    ) -> ExecutedResult<PBSOrder> {
+        if let Some(res) = results
+            .iter()
+            .find(|res| res.1.get_trivial_constant() == Some(CT_TRUE))
+        {
+            return res.clone();
+        }
+        let mut results: Vec<ExecutedResult<PBSOrder>> = results
+            .into_iter()
+            .filter(|res| res.1.get_trivial_constant() != Some(CT_FALSE))
+            .collect();
+        if results.len() <= 1 {
+            return results.pop().unwrap_or_else(|| self.ct_false());
+        }
+
+        let ctx = results[1..]
+            .iter()
+            .fold(results[0].1.clone(), |ctx, res| Executed::Or {
+                a: Box::new(ctx),
+                b: Box::new(res.1.clone()),
+            });
+        self.with_cache(
+            ctx.clone(),
+            Rc::new(move |exec| {
+                exec.ct_ops += 1;
+
+                let cts: Vec<RadixCiphertext<PBSOrder>> =
+                    results.iter().map(|res| res.0.clone()).collect();
+                (exec.sk.or_reduce(&cts).unwrap(), ctx.clone())
+            }),
+        )
+    }
+
+    pub(crate) fn ct_not(&mut self, a: ExecutedResult<PBSOrder>) -> ExecutedResult<PBSOrder> {
         let ctx = Executed::Not {
             a: Box::new(a.1.clone()),
         };
@@ -190,22 +413,34 @@ impl Execution {
         )
     }
 
-    pub(crate) fn ct_false(&self) -> ExecutedResult {
+    pub(crate) fn ct_false(&self) -> ExecutedResult<PBSOrder> {
         self.ct_constant(CT_FALSE)
     }
 
-    pub(crate) fn ct_true(&self) -> ExecutedResult {
+    pub(crate) fn ct_true(&self) -> ExecutedResult<PBSOrder> {
         self.ct_constant(CT_TRUE)
     }
 
-    pub(crate) fn ct_constant(&self, c: u8) -> ExecutedResult {
+    pub(crate) fn ct_constant(&self, c: u8) -> ExecutedResult<PBSOrder> {
         (
-            self.sk.create_trivial_radix(c as u64, 4),
+            self.sk.create_trivial_radix(c as u64, self.num_blocks),
             Executed::Constant { c },
         )
     }
 
-    fn with_cache(&mut self, ctx: Executed, f: LazyExecution) -> ExecutedResult {
+    // Whether `b` is a constant above the largest character the content can
+    // hold, such as `\xFF` for 7 bit characters. As it can't be represented on
+    // the blocks of the characters, the comparisons against it are evaluated
+    // in the clear: a character never equals it and is always lower
+    fn is_above_max_char(&self, b: &ExecutedResult<PBSOrder>) -> bool {
+        matches!(b.1.get_trivial_constant(), Some(c) if c as u64 > self.max_char)
+    }
+
+    fn with_cache(
+        &mut self,
+        ctx: Executed,
+        f: LazyExecution<PBSOrder>,
+    ) -> ExecutedResult<PBSOrder> {
         if let Some(res) = self.cache.get(&ctx) {
             trace!("cache hit: {:?}", &ctx);
             self.cache_hits += 1;
@@ -218,6 +453,122 @@ impl Execution {
     }
 }
 
+/// Number of branches a regex evaluates from a content position, and total
+/// number of ciphertext operations of these branches.
+#[derive(Clone, Copy)]
+struct BranchesCost {
+    branches: u64,
+    ct_ops: u64,
+}
+
+impl BranchesCost {
+    fn leaf(ct_ops: u64) -> Self {
+        Self {
+            branches: 1,
+            ct_ops,
+        }
+    }
+
+    fn or(self, other: Self) -> Self {
+        Self {
+            branches: self.branches.saturating_add(other.branches),
+            ct_ops: self.ct_ops.saturating_add(other.ct_ops),
+        }
+    }
+
+    // Each branch of `self` continued with each branch of `then`, the results
+    // of both being joined by one `ct_and`
+    fn and_then(self, then: Self) -> Self {
+        let branches = self.branches.saturating_mul(then.branches);
+        Self {
+            branches,
+            ct_ops: self
+                .ct_ops
+                .saturating_mul(then.branches)
+                .saturating_add(then.ct_ops.saturating_mul(self.branches))
+                .saturating_add(branches),
+        }
+    }
+}
+
+/// Estimates the number of ciphertext operations of the branches of the regex
+/// from a single content position, on a content of `content_len` characters,
+/// and joining their results.
+///
+/// The estimate follows how `build_branches` unrolls each node of the regex,
+/// with unbounded repetitions going up to the content length, but without
+/// tracking where each branch ends: every branch is assumed to have the whole
+/// content left. It also ignores the cache and the operations on trivial
+/// constants, which the execution skips. So it is an upper bound, which grows
+/// as fast as the number of branches does (e.g. with the content length to the
+/// power of the number of unbounded repetitions in sequence).
+pub(crate) fn estimate_ct_operations(re: &RegExpr, content_len: usize) -> u64 {
+    let cost = estimate_branches_cost(re, content_len);
+    cost.ct_ops.saturating_add((cost.branches > 1) as u64)
+}
+
+fn estimate_branches_cost(re: &RegExpr, content_len: usize) -> BranchesCost {
+    match re {
+        RegExpr::Sof | RegExpr::Eof | RegExpr::AnyChar => BranchesCost::leaf(0),
+        RegExpr::Char { .. } => BranchesCost::leaf(1),
+        // Two comparisons and the `ct_and` of their results
+        RegExpr::Between { .. } => BranchesCost::leaf(3),
+        // One comparison per character, all joined by a `ct_or`
+        RegExpr::Range { cs } => BranchesCost::leaf((2 * cs.len()).saturating_sub(1) as u64),
+        RegExpr::Not { not_re } => {
+            let inner = estimate_branches_cost(not_re, content_len);
+            BranchesCost::leaf(
+                inner
+                    .ct_ops
+                    .saturating_add((inner.branches > 1) as u64)
+                    .saturating_add(1),
+            )
+        }
+        RegExpr::Either { l_re, r_re } => {
+            estimate_branches_cost(l_re, content_len).or(estimate_branches_cost(r_re, content_len))
+        }
+        RegExpr::Optional { opt_re } => {
+            estimate_branches_cost(opt_re, content_len).or(BranchesCost::leaf(0))
+        }
+        RegExpr::Repeated {
+            repeat_re,
+            at_least,
+            at_most,
+        } => {
+            let at_least = at_least.unwrap_or(0);
+            // Repetitions consuming characters can't go beyond the content
+            let max_repeat = content_len / repeat_re.min_len().max(1);
+            let at_most = at_most.unwrap_or(content_len).min(max_repeat.max(at_least));
+
+            let repeat_cost = estimate_branches_cost(repeat_re, content_len);
+            let mut repeated = BranchesCost::leaf(0);
+            let mut res = BranchesCost {
+                branches: 0,
+                ct_ops: 0,
+            };
+            for i in 0..=at_most {
+                if i > 0 {
+                    repeated = repeated.and_then(repeat_cost);
+                }
+                if i >= at_least {
+                    res = res.or(repeated);
+                }
+                // Saturated, as long repetitions of costly regexes quickly are
+                if res.ct_ops == u64::MAX {
+                    break;
+                }
+            }
+            res
+        }
+        RegExpr::Seq { re_xs } => re_xs
+            .iter()
+            .map(|re_x| estimate_branches_cost(re_x, content_len))
+            .reduce(BranchesCost::and_then)
+            .unwrap_or(BranchesCost::leaf(0)),
+        RegExpr::Group { group_re, .. } => estimate_branches_cost(group_re, content_len),
+    }
+}
+
 impl std::fmt::Debug for Executed {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
@@ -270,3 +621,41 @@ impl std::fmt::Debug for Executed {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::tests::KEYS;
+    use crate::engine::{char_comparisons, or_branches};
+    use crate::execution::{lazy_branches, Execution, LazyExecution};
+    use crate::parser::parse;
+
+    use crate::ciphertext::{encrypt_str, StringCiphertext};
+
+    #[test]
+    fn test_prefill_char_comparisons() {
+        let content = "xyzabcxyzabd";
+        let re = parse("/ab[c-e]/").unwrap();
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, content).unwrap();
+
+        // Each position evaluated one after the other, with or without the
+        // comparisons evaluated up front
+        let match_all_positions = |prefill: bool| -> Vec<u64> {
+            let mut exec = Execution::new(KEYS.1.clone(), &ct_content);
+            if prefill {
+                exec.prefill_char_comparisons(&ct_content, &char_comparisons(&re));
+            }
+            (0..content.len())
+                .map(|i| {
+                    let branches: Vec<LazyExecution> = lazy_branches(&ct_content, &re, i);
+                    KEYS.0.decrypt(&or_branches(&mut exec, &branches).0)
+                })
+                .collect()
+        };
+
+        let scan_flags = match_all_positions(false);
+        let prefill_flags = match_all_positions(true);
+
+        assert_eq!(vec![0, 0, 0, 1, 0, 0, 0, 0, 0, 1, 0, 0], scan_flags);
+        assert_eq!(scan_flags, prefill_flags);
+    }
+}
@@ -0,0 +1,289 @@
+use crate::ciphertext::{number_layout, StringCiphertext};
+use crate::engine::or_branches;
+use crate::execution::{
+    lazy_branches, log_ct_operations, CharComparison, Execution, LazyExecution,
+};
+use crate::parser::RegExpr;
+use anyhow::{anyhow, Result};
+use rayon::prelude::*;
+use std::collections::BTreeSet;
+use tfhe::integer::{RadixCiphertextBig, ServerKey};
+
+/// Detects which of the keywords appears somewhere in the content.
+///
+/// Results in a pair of an encrypted index and an encrypted boolean. The index
+/// is the position in `keywords` of the first keyword (in list order, not in
+/// content order) that appears in the content. The boolean is true if any of
+/// the keywords appears in the content. When none of the keywords appears, the
+/// index is an encrypted 0 and the boolean is an encrypted false, so the
+/// boolean must be used to tell apart "no keyword" from "keyword 0".
+///
+/// The keywords are matched in parallel, and the booleans telling whether any
+/// of the first keywords appears are computed in parallel too with
+/// `ServerKey::or_reduce`, the index being derived from their count.
+///
+/// Keywords are matched literally, i.e. regex symbols in them have no special
+/// meaning. The index has as many blocks as the content characters, and an
+/// error is returned if any of the keywords is empty, or if there are more
+/// keywords than the index can represent (256 with the default keys).
+#[allow(dead_code)]
+pub fn which_keyword(
+    sk: &ServerKey,
+    content: &StringCiphertext,
+    keywords: &[&str],
+) -> Result<(RadixCiphertextBig, RadixCiphertextBig)> {
+    if keywords.iter().any(|keyword| keyword.is_empty()) {
+        return Err(anyhow!("keywords must not be empty"));
+    }
+    let (num_blocks, max_index) = number_layout(sk, content, 0);
+    let max_keywords = max_index.saturating_add(1);
+    if keywords.len() as u64 > max_keywords {
+        return Err(anyhow!(
+            "too many keywords, at most {} are supported",
+            max_keywords
+        ));
+    }
+
+    let mut exec = Execution::new(sk.clone(), content);
+    if keywords.is_empty() {
+        return Ok((sk.create_trivial_zero_radix(num_blocks), exec.ct_false().0));
+    }
+    let keyword_bytes: BTreeSet<u8> = keywords
+        .iter()
+        .flat_map(|keyword| keyword.bytes())
+        .collect();
+    exec.prefill_char_comparisons(
+        content,
+        &keyword_bytes
+            .into_iter()
+            .map(|c| CharComparison::Equal { c })
+            .collect::<Vec<_>>(),
+    );
+
+    // The keywords don't depend on each other, so they are matched in
+    // parallel, each with its own copy of the cache holding the prefilled
+    // character comparisons
+    let results: Vec<(RadixCiphertextBig, usize, usize)> = keywords
+        .par_iter()
+        .map(|keyword| {
+            let mut exec = exec.fork();
+            let re = RegExpr::Seq {
+                re_xs: keyword.bytes().map(|c| RegExpr::Char { c }).collect(),
+            };
+            let branches: Vec<LazyExecution> = (0..content.len())
+                .flat_map(|i| lazy_branches(content, &re, i))
+                .collect();
+            let flag = or_branches(&mut exec, &branches).0;
+            (flag, exec.ct_operations_count(), exec.cache_hits())
+        })
+        .collect();
+    let ct_ops: usize = results.iter().map(|(_, ct_ops, _)| ct_ops).sum();
+    let cache_hits: usize = results.iter().map(|(_, _, cache_hits)| cache_hits).sum();
+    let keyword_flags: Vec<RadixCiphertextBig> =
+        results.into_iter().map(|(flag, _, _)| flag).collect();
+
+    // Encrypted boolean per keyword, true if it or any keyword before it (in
+    // list order) appears in the content, the last one telling whether any
+    // keyword appears
+    let found_up_to: Vec<RadixCiphertextBig> = (1..=keyword_flags.len())
+        .into_par_iter()
+        .map(|num_keywords| sk.or_reduce(&keyword_flags[..num_keywords]).unwrap())
+        .collect();
+    let found = found_up_to.last().unwrap().clone();
+
+    // The index of the first keyword found is the number of keywords up to
+    // which none is found, i.e. the number of keywords minus the number of
+    // true booleans above. When no keyword is found, the index is 0 as found
+    // is false
+    let mut ct_found_count: RadixCiphertextBig = sk.create_trivial_zero_radix(num_blocks);
+    for ct_found in &found_up_to {
+        sk.smart_add_assign(&mut ct_found_count, &mut ct_found.clone());
+    }
+    sk.full_propagate(&mut ct_found_count);
+    let ct_keywords_count = sk.smart_scalar_mul(&mut found.clone(), keywords.len() as u64);
+    let ct_index = sk.sub_parallelized(&ct_keywords_count, &ct_found_count);
+
+    log_ct_operations(
+        exec.ct_operations_count() + ct_ops + found_up_to.len() + 1,
+        cache_hits,
+    );
+    Ok((ct_index, found))
+}
+
+/// Checks whether the content starts with any of the prefixes.
+///
+/// Results in an encrypted boolean. Prefixes are matched literally and are
+/// merged into a trie before being matched, so prefixes sharing their leading
+/// bytes compare and combine these bytes only once. An empty prefix always
+/// matches, and an empty list of prefixes never does.
+pub fn starts_with_any(
+    sk: &ServerKey,
+    content: &StringCiphertext,
+    prefixes: &[&str],
+) -> RadixCiphertextBig {
+    let mut exec = Execution::new(sk.clone(), content);
+    if prefixes.is_empty() {
+        return exec.ct_false().0;
+    }
+
+    let prefixes: Vec<&[u8]> = prefixes.iter().map(|prefix| prefix.as_bytes()).collect();
+    let trie = match prefix_trie(&prefixes) {
+        Some(trie) => trie,
+        None => return exec.ct_true().0,
+    };
+    let re = RegExpr::Seq {
+        re_xs: vec![RegExpr::Sof, trie],
+    };
+    let branches: Vec<LazyExecution> = lazy_branches(content, &re, 0);
+
+    let res = or_branches(&mut exec, &branches).0;
+    exec.log_ct_operations();
+    res
+}
+
+/// Checks whether the content equals `expected`, ignoring ascii case, as
+/// [str::eq_ignore_ascii_case] does.
+///
+/// Results in an encrypted boolean. As the content length is public, it is
+/// compared to the length of `expected` in the clear, and an encrypted false is
+/// returned right away when they differ. Otherwise each content character is
+/// compared to both cases of the expected character at its position (or to
+/// the character alone if it is not a letter), and the per-position results
+/// are ANDed together.
+#[allow(dead_code)]
+pub fn eq_ignore_ascii_case(
+    sk: &ServerKey,
+    content: &StringCiphertext,
+    expected: &str,
+) -> RadixCiphertextBig {
+    let mut exec = Execution::new(sk.clone(), content);
+    if content.len() != expected.len() {
+        return exec.ct_false().0;
+    }
+    if expected.is_empty() {
+        return exec.ct_true().0;
+    }
+
+    // The lengths being equal, matching the whole of `expected` from position 0
+    // covers the whole content, so no anchors are needed
+    let re = RegExpr::Seq {
+        re_xs: expected.bytes().map(|c| RegExpr::Char { c }).collect(),
+    }
+    .case_insensitive();
+    let branches: Vec<LazyExecution> = lazy_branches(content, &re, 0);
+
+    let res = or_branches(&mut exec, &branches).0;
+    exec.log_ct_operations();
+    res
+}
+
+/// Builds a regex matching any of the (non empty list of) prefixes, where
+/// prefixes with a common leading byte share a single branch for it. Results in
+/// `None` if one of the prefixes is empty, as the regex then always matches
+/// without consuming any character.
+fn prefix_trie(prefixes: &[&[u8]]) -> Option<RegExpr> {
+    if prefixes.iter().any(|prefix| prefix.is_empty()) {
+        return None;
+    }
+
+    let mut first_bytes: Vec<u8> = prefixes.iter().map(|prefix| prefix[0]).collect();
+    first_bytes.sort();
+    first_bytes.dedup();
+
+    let children: Vec<RegExpr> = first_bytes
+        .into_iter()
+        .map(|c| {
+            let rests: Vec<&[u8]> = prefixes
+                .iter()
+                .filter(|prefix| prefix[0] == c)
+                .map(|prefix| &prefix[1..])
+                .collect();
+            match prefix_trie(&rests) {
+                Some(rest_re) => RegExpr::Seq {
+                    re_xs: vec![RegExpr::Char { c }, rest_re],
+                },
+                None => RegExpr::Char { c },
+            }
+        })
+        .collect();
+
+    children
+        .into_iter()
+        .rev()
+        .reduce(|r_re, l_re| RegExpr::Either {
+            l_re: Box::new(l_re),
+            r_re: Box::new(r_re),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::tests::KEYS;
+    use crate::keyword::{eq_ignore_ascii_case, starts_with_any, which_keyword};
+    use test_case::test_case;
+
+    use crate::ciphertext::{encrypt_str, StringCiphertext};
+
+    #[test_case("foobar", &["bar", "foo"], 0, 1 ; "disjoint, first in list order")]
+    #[test_case("foobar", &["baz", "oba"], 1, 1 ; "disjoint, only second appears")]
+    #[test_case("foobar", &["foob", "oba"], 0, 1 ; "overlapping")]
+    #[test_case("foobar", &["xyz", "obar", "bar"], 1, 1 ; "overlapping, both after first")]
+    #[test_case("foobar", &["xyz", "abc"], 0, 0 ; "none appears")]
+    #[test_case("foobar", &[], 0, 0 ; "no keywords")]
+    fn test_which_keyword(content: &str, keywords: &[&str], exp_index: u64, exp_found: u64) {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, content).unwrap();
+        let (ct_index, ct_found) = which_keyword(&KEYS.1, &ct_content, keywords).unwrap();
+
+        let got_index: u64 = KEYS.0.decrypt(&ct_index);
+        let got_found: u64 = KEYS.0.decrypt(&ct_found);
+        assert_eq!((exp_index, exp_found), (got_index, got_found));
+    }
+
+    #[test]
+    fn test_which_keyword_rejects_empty_keyword() {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, "foo").unwrap();
+        assert!(which_keyword(&KEYS.1, &ct_content, &["foo", ""]).is_err());
+    }
+
+    #[test_case("GET /index", &["GET ", "GZIP"], 1 ; "first of prefixes sharing a byte")]
+    #[test_case("GZIP data", &["GET ", "GZIP"], 1 ; "second of prefixes sharing a byte")]
+    #[test_case("GEZIP", &["GET ", "GZIP"], 0 ; "mixing prefixes sharing a byte")]
+    #[test_case("POST /", &["GET ", "GZIP", "POST"], 1 ; "prefix not sharing a byte")]
+    #[test_case("GE", &["GET ", "GZIP"], 0 ; "content shorter than prefixes")]
+    #[test_case("GET", &["G", "GET"], 1 ; "prefix of another prefix")]
+    #[test_case("abc", &["", "x"], 1 ; "empty prefix")]
+    #[test_case("abc", &[], 0 ; "no prefixes")]
+    fn test_starts_with_any(content: &str, prefixes: &[&str], exp: u64) {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, content).unwrap();
+        let ct_res = starts_with_any(&KEYS.1, &ct_content, prefixes);
+
+        let got: u64 = KEYS.0.decrypt(&ct_res);
+        assert_eq!(exp, got);
+
+        // Sharing the leading bytes must give the same result as checking each
+        // prefix on its own
+        let got_separately = prefixes
+            .iter()
+            .map(|prefix| -> u64 {
+                KEYS.0
+                    .decrypt(&starts_with_any(&KEYS.1, &ct_content, &[*prefix]))
+            })
+            .fold(0, |acc, x| acc | x);
+        assert_eq!(exp, got_separately);
+    }
+
+    #[test_case("Content-Type", "content-type" ; "mixed case")]
+    #[test_case("GET", "get" ; "all uppercase")]
+    #[test_case("a-1_b", "A-1_B" ; "non letters")]
+    #[test_case("abc", "abd" ; "different letter")]
+    #[test_case("a[c", "a{c" ; "non letters differing by the case bit")]
+    #[test_case("abc", "ab" ; "different lengths")]
+    #[test_case("", "" ; "empty")]
+    fn test_eq_ignore_ascii_case(content: &str, expected: &str) {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, content).unwrap();
+        let ct_res = eq_ignore_ascii_case(&KEYS.1, &ct_content, expected);
+
+        let got: u64 = KEYS.0.decrypt(&ct_res);
+        assert_eq!(content.eq_ignore_ascii_case(expected) as u64, got);
+    }
+}
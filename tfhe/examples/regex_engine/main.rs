@@ -1,30 +1,52 @@
 #[macro_use]
 extern crate log;
 
+// Not all of the engine's API is exercised by this demo binary, the entry
+// points it doesn't call allow dead code individually
+mod cache;
+mod captures;
 mod ciphertext;
+mod classify;
+mod compiled;
+mod density;
+mod distance;
+mod distinct;
 mod engine;
 mod execution;
+mod keyword;
+mod palindrome;
 mod parser;
+mod positions;
+mod region;
+mod replace;
+mod result;
+mod streaming;
+mod trim;
+mod whitespace;
 
+use anyhow::{anyhow, Result};
 use env_logger::Env;
 use std::env;
 
-fn main() {
+fn main() -> Result<()> {
     let env = Env::default().filter_or("RUST_LOG", "info");
     env_logger::init_from_env(env);
 
     let args: Vec<String> = env::args().collect();
+    if args.len() != 3 {
+        return Err(anyhow!("usage: {} <content> <pattern>", args[0]));
+    }
     let content = &args[1];
     let pattern = &args[2];
 
-    let (client_key, server_key) = ciphertext::gen_keys();
-    let ct_content = ciphertext::encrypt_str(&client_key, content).unwrap();
+    let (client_key, server_key) = ciphertext::gen_keys_default();
+    let ct_content = ciphertext::encrypt_str(&client_key, content)?;
 
-    let ct_res = engine::has_match(&server_key, &ct_content, pattern).unwrap();
-    let res: u64 = client_key.decrypt(&ct_res);
-    if res == 0 {
-        println!("no match");
-    } else {
+    let ct_res = engine::has_match(&server_key, &ct_content, pattern)?;
+    if client_key.decrypt_bool(&ct_res)? {
         println!("match");
+    } else {
+        println!("no match");
     }
+    Ok(())
 }
@@ -0,0 +1,41 @@
+use crate::ciphertext::{char_num_blocks, StringCiphertext};
+use rayon::prelude::*;
+use tfhe::integer::{RadixCiphertextBig, ServerKey};
+
+/// Checks whether the content reads the same forwards and backwards.
+///
+/// Results in an encrypted boolean. As the content length is public, the
+/// pairing of each character of the first half with its mirror in the second
+/// half is done in the clear, only the comparisons of the pairs are
+/// homomorphic. They are evaluated in parallel and then AND-reduced. For odd
+/// lengths the middle character is not compared, and empty or single character
+/// content results in an encrypted true.
+#[allow(dead_code)]
+pub fn is_palindrome(sk: &ServerKey, content: &StringCiphertext) -> RadixCiphertextBig {
+    let half = content.len() / 2;
+    let pairs_eq: Vec<RadixCiphertextBig> = (0..half)
+        .into_par_iter()
+        .map(|i| sk.eq_parallelized(&content[i], &content[content.len() - 1 - i]))
+        .collect();
+
+    sk.and_reduce(&pairs_eq)
+        .unwrap_or_else(|| sk.create_trivial_radix(1u64, char_num_blocks(content)))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::tests::assert_decrypted_result;
+    use crate::palindrome::is_palindrome;
+    use test_case::test_case;
+
+    #[test_case("abba", 1 ; "even length")]
+    #[test_case("racecar", 1 ; "odd length")]
+    #[test_case("abca", 0 ; "even length, not a palindrome")]
+    #[test_case("abcab", 0 ; "odd length, not a palindrome")]
+    #[test_case("ab", 0 ; "two characters")]
+    #[test_case("a", 1 ; "single character")]
+    #[test_case("", 1 ; "empty")]
+    fn test_is_palindrome(content: &str, exp: u64) {
+        assert_decrypted_result(content, exp, is_palindrome);
+    }
+}
@@ -38,10 +38,14 @@ pub(crate) enum RegExpr {
     Seq {
         re_xs: Vec<RegExpr>,
     },
+    Group {
+        group_re: Box<RegExpr>,
+        index: usize, // capture group number, in order of the opening parentheses
+    },
 }
 
 impl RegExpr {
-    fn case_insensitive(self) -> Self {
+    pub(crate) fn case_insensitive(self) -> Self {
         match self {
             Self::Char { c } => Self::Range {
                 cs: case_insensitive(c),
@@ -68,11 +72,279 @@ impl RegExpr {
             Self::Seq { re_xs } => Self::Seq {
                 re_xs: re_xs.into_iter().map(|re| re.case_insensitive()).collect(),
             },
+            Self::Group { group_re, index } => Self::Group {
+                group_re: Box::new(group_re.case_insensitive()),
+                index,
+            },
+            _ => self,
+        }
+    }
+}
+
+impl RegExpr {
+    /// Turns each `.` into a negated newline, so that it matches any character
+    /// but `\n`.
+    pub(crate) fn dot_excludes_newline(self) -> Self {
+        match self {
+            Self::AnyChar => Self::Not {
+                not_re: Box::new(Self::Char { c: b'\n' }),
+            },
+            Self::Not { not_re } => Self::Not {
+                not_re: Box::new(not_re.dot_excludes_newline()),
+            },
+            Self::Either { l_re, r_re } => Self::Either {
+                l_re: Box::new(l_re.dot_excludes_newline()),
+                r_re: Box::new(r_re.dot_excludes_newline()),
+            },
+            Self::Optional { opt_re } => Self::Optional {
+                opt_re: Box::new(opt_re.dot_excludes_newline()),
+            },
+            Self::Repeated {
+                repeat_re,
+                at_least,
+                at_most,
+            } => Self::Repeated {
+                repeat_re: Box::new(repeat_re.dot_excludes_newline()),
+                at_least,
+                at_most,
+            },
+            Self::Seq { re_xs } => Self::Seq {
+                re_xs: re_xs
+                    .into_iter()
+                    .map(|re| re.dot_excludes_newline())
+                    .collect(),
+            },
+            Self::Group { group_re, index } => Self::Group {
+                group_re: Box::new(group_re.dot_excludes_newline()),
+                index,
+            },
+            _ => self,
+        }
+    }
+}
+
+impl RegExpr {
+    /// Limits all unbounded repetitions to at most `max_repeat` repetitions.
+    pub(crate) fn max_repeat(self, max_repeat: usize) -> Self {
+        match self {
+            Self::Not { not_re } => Self::Not {
+                not_re: Box::new(not_re.max_repeat(max_repeat)),
+            },
+            Self::Either { l_re, r_re } => Self::Either {
+                l_re: Box::new(l_re.max_repeat(max_repeat)),
+                r_re: Box::new(r_re.max_repeat(max_repeat)),
+            },
+            Self::Optional { opt_re } => Self::Optional {
+                opt_re: Box::new(opt_re.max_repeat(max_repeat)),
+            },
+            Self::Repeated {
+                repeat_re,
+                at_least,
+                at_most,
+            } => Self::Repeated {
+                repeat_re: Box::new(repeat_re.max_repeat(max_repeat)),
+                at_least,
+                at_most: Some(at_most.unwrap_or(max_repeat)),
+            },
+            Self::Seq { re_xs } => Self::Seq {
+                re_xs: re_xs
+                    .into_iter()
+                    .map(|re| re.max_repeat(max_repeat))
+                    .collect(),
+            },
+            Self::Group { group_re, index } => Self::Group {
+                group_re: Box::new(group_re.max_repeat(max_repeat)),
+                index,
+            },
             _ => self,
         }
     }
 }
 
+impl RegExpr {
+    /// Numbers the capture groups in order of their opening parentheses,
+    /// starting at `next_index`.
+    fn number_groups(self, next_index: &mut usize) -> Self {
+        match self {
+            Self::Not { not_re } => Self::Not {
+                not_re: Box::new(not_re.number_groups(next_index)),
+            },
+            Self::Either { l_re, r_re } => {
+                let l_re = Box::new(l_re.number_groups(next_index));
+                let r_re = Box::new(r_re.number_groups(next_index));
+                Self::Either { l_re, r_re }
+            }
+            Self::Optional { opt_re } => Self::Optional {
+                opt_re: Box::new(opt_re.number_groups(next_index)),
+            },
+            Self::Repeated {
+                repeat_re,
+                at_least,
+                at_most,
+            } => Self::Repeated {
+                repeat_re: Box::new(repeat_re.number_groups(next_index)),
+                at_least,
+                at_most,
+            },
+            Self::Seq { re_xs } => Self::Seq {
+                re_xs: re_xs
+                    .into_iter()
+                    .map(|re| re.number_groups(next_index))
+                    .collect(),
+            },
+            Self::Group { group_re, .. } => {
+                let index = *next_index;
+                *next_index += 1;
+                Self::Group {
+                    group_re: Box::new(group_re.number_groups(next_index)),
+                    index,
+                }
+            }
+            _ => self,
+        }
+    }
+
+    /// Minimum number of content characters consumed by any match of the
+    /// regex.
+    pub(crate) fn min_len(&self) -> usize {
+        match self {
+            Self::Sof | Self::Eof => 0,
+            Self::Char { .. } | Self::AnyChar | Self::Between { .. } | Self::Range { .. } => 1,
+            Self::Not { not_re } => not_re.min_len(),
+            Self::Either { l_re, r_re } => l_re.min_len().min(r_re.min_len()),
+            Self::Optional { .. } => 0,
+            Self::Repeated {
+                repeat_re,
+                at_least,
+                ..
+            } => at_least.unwrap_or(0) * repeat_re.min_len(),
+            Self::Seq { re_xs } => re_xs.iter().map(|re| re.min_len()).sum(),
+            Self::Group { group_re, .. } => group_re.min_len(),
+        }
+    }
+
+    /// Maximum number of content characters consumed by any match of the
+    /// regex, or `None` if matches can be arbitrarily long.
+    pub(crate) fn max_len(&self) -> Option<usize> {
+        match self {
+            Self::Sof | Self::Eof => Some(0),
+            Self::Char { .. } | Self::AnyChar | Self::Between { .. } | Self::Range { .. } => {
+                Some(1)
+            }
+            Self::Not { not_re } => not_re.max_len(),
+            Self::Either { l_re, r_re } => Some(l_re.max_len()?.max(r_re.max_len()?)),
+            Self::Optional { opt_re } => opt_re.max_len(),
+            Self::Repeated {
+                repeat_re, at_most, ..
+            } => match repeat_re.max_len()? {
+                0 => Some(0),
+                len => Some((*at_most)? * len),
+            },
+            Self::Seq { re_xs } => re_xs.iter().map(|re| re.max_len()).sum(),
+            Self::Group { group_re, .. } => group_re.max_len(),
+        }
+    }
+
+    /// Whether the regex contains a `^` or a `$` anchor.
+    pub(crate) fn has_anchor(&self) -> bool {
+        match self {
+            Self::Sof | Self::Eof => true,
+            Self::Char { .. } | Self::AnyChar | Self::Between { .. } | Self::Range { .. } => false,
+            Self::Not { not_re } => not_re.has_anchor(),
+            Self::Either { l_re, r_re } => l_re.has_anchor() || r_re.has_anchor(),
+            Self::Optional { opt_re } => opt_re.has_anchor(),
+            Self::Repeated { repeat_re, .. } => repeat_re.has_anchor(),
+            Self::Seq { re_xs } => re_xs.iter().any(|re| re.has_anchor()),
+            Self::Group { group_re, .. } => group_re.has_anchor(),
+        }
+    }
+
+    /// Number of content characters consumed by every match of the regex, or
+    /// `None` if matches of different lengths are possible.
+    pub(crate) fn fixed_len(&self) -> Option<usize> {
+        match self {
+            Self::Sof | Self::Eof => Some(0),
+            Self::Char { .. } | Self::AnyChar | Self::Between { .. } | Self::Range { .. } => {
+                Some(1)
+            }
+            Self::Not { not_re } => not_re.fixed_len(),
+            Self::Either { l_re, r_re } => {
+                let len = l_re.fixed_len()?;
+                (r_re.fixed_len()? == len).then_some(len)
+            }
+            Self::Optional { .. } => None,
+            Self::Repeated {
+                repeat_re,
+                at_least,
+                at_most,
+            } => match (at_least, at_most) {
+                (Some(at_least), Some(at_most)) if at_least == at_most => {
+                    Some(at_least * repeat_re.fixed_len()?)
+                }
+                _ => None,
+            },
+            Self::Seq { re_xs } => re_xs.iter().map(|re| re.fixed_len()).sum(),
+            Self::Group { group_re, .. } => group_re.fixed_len(),
+        }
+    }
+
+    /// Bounds of the first repetition whose least limit is above its most
+    /// limit, if any.
+    fn invalid_repeat(&self) -> Option<(usize, usize)> {
+        match self {
+            Self::Not { not_re } => not_re.invalid_repeat(),
+            Self::Either { l_re, r_re } => l_re.invalid_repeat().or_else(|| r_re.invalid_repeat()),
+            Self::Optional { opt_re } => opt_re.invalid_repeat(),
+            Self::Repeated {
+                repeat_re,
+                at_least,
+                at_most,
+            } => match (at_least, at_most) {
+                (Some(at_least), Some(at_most)) if at_least > at_most => {
+                    Some((*at_least, *at_most))
+                }
+                _ => repeat_re.invalid_repeat(),
+            },
+            Self::Seq { re_xs } => re_xs.iter().find_map(|re| re.invalid_repeat()),
+            Self::Group { group_re, .. } => group_re.invalid_repeat(),
+            _ => None,
+        }
+    }
+
+    /// Whether one of the alternatives of an `Either` is empty, as in `a|`,
+    /// `|a` or `(a|)`.
+    fn has_empty_alternative(&self) -> bool {
+        let is_empty = |re: &RegExpr| matches!(re, Self::Seq { re_xs } if re_xs.is_empty());
+        match self {
+            Self::Not { not_re } => not_re.has_empty_alternative(),
+            Self::Either { l_re, r_re } => {
+                is_empty(l_re)
+                    || is_empty(r_re)
+                    || l_re.has_empty_alternative()
+                    || r_re.has_empty_alternative()
+            }
+            Self::Optional { opt_re } => opt_re.has_empty_alternative(),
+            Self::Repeated { repeat_re, .. } => repeat_re.has_empty_alternative(),
+            Self::Seq { re_xs } => re_xs.iter().any(|re| re.has_empty_alternative()),
+            Self::Group { group_re, .. } => group_re.has_empty_alternative(),
+            _ => false,
+        }
+    }
+
+    /// Number of capture groups in the regex.
+    pub(crate) fn groups_count(&self) -> usize {
+        match self {
+            Self::Not { not_re } => not_re.groups_count(),
+            Self::Either { l_re, r_re } => l_re.groups_count() + r_re.groups_count(),
+            Self::Optional { opt_re } => opt_re.groups_count(),
+            Self::Repeated { repeat_re, .. } => repeat_re.groups_count(),
+            Self::Seq { re_xs } => re_xs.iter().map(|re| re.groups_count()).sum(),
+            Self::Group { group_re, .. } => 1 + group_re.groups_count(),
+            _ => 0,
+        }
+    }
+}
+
 fn case_insensitive(x: u8) -> Vec<u8> {
     let c = u8_to_char(x);
     if c.is_ascii_lowercase() {
@@ -143,11 +415,22 @@ impl fmt::Debug for RegExpr {
                 write!(f, ">")?;
                 Ok(())
             }
+            Self::Group { group_re, index } => {
+                write!(f, "#{}(", index)?;
+                group_re.fmt(f)?;
+                write!(f, ")")
+            }
         }
     }
 }
 
 pub(crate) fn parse(pattern: &str) -> Result<RegExpr> {
+    parse_with_dot_newline(pattern, false)
+}
+
+/// Same as `parse`, but with `dot_matches_newline` set `.` also matches `\n`,
+/// as it does for a pattern with the `s` flag (`/a.b/s`).
+pub(crate) fn parse_with_dot_newline(pattern: &str, dot_matches_newline: bool) -> Result<RegExpr> {
     let (parsed, unparsed) = (
         between(
             byte(b'/'),
@@ -168,15 +451,21 @@ pub(crate) fn parse(pattern: &str) -> Result<RegExpr> {
             }
             RegExpr::Seq { re_xs }
         }),
-        optional(byte(b'i')),
+        many::<Vec<u8>, _, _>(one_of(b"is".iter().copied())),
     )
-        .map(|(re, case_insensitive)| {
-            if case_insensitive.is_some() {
+        .map(|(re, flags)| {
+            let re = if flags.contains(&b'i') {
                 re.case_insensitive()
             } else {
                 re
+            };
+            if dot_matches_newline || flags.contains(&b's') {
+                re
+            } else {
+                re.dot_excludes_newline()
             }
         })
+        .map(|re| re.number_groups(&mut 0))
         .parse(pattern.as_bytes())?;
     if !unparsed.is_empty() {
         return Err(anyhow!(
@@ -184,6 +473,18 @@ pub(crate) fn parse(pattern: &str) -> Result<RegExpr> {
             std::str::from_utf8(unparsed).unwrap()
         ));
     }
+    if let Some((at_least, at_most)) = parsed.invalid_repeat() {
+        return Err(anyhow!(
+            "invalid repetition {{{},{}}}, the least limit is above the most limit",
+            at_least,
+            at_most
+        ));
+    }
+    if parsed.has_empty_alternative() {
+        return Err(anyhow!(
+            "empty alternative in `|`, use an optional `(...)?` to match nothing"
+        ));
+    }
 
     Ok(parsed)
 }
@@ -200,6 +501,7 @@ pub(crate) fn parse(pattern: &str) -> Result<RegExpr> {
 //  <base> ::= <char>
 //          |  '\' <char>
 //          |  '(' <regex> ')'
+//          |  '(' '?:' <regex> ')'
 
 parser! {
     fn regex[Input]()(Input) -> RegExpr
@@ -264,17 +566,43 @@ where
 {
     choice((
         byte(b'.').map(|_| RegExpr::AnyChar),
-        attempt(byte(b'\\').with(parser::token::any())).map(|c| RegExpr::Char { c }),
+        attempt(shorthand_class()).map(class_regex),
+        escaped().map(|c| RegExpr::Char { c }),
         choice((
             byte::alpha_num(),
             parser::token::one_of(NON_ESCAPABLE_SYMBOLS),
         ))
         .map(|c| RegExpr::Char { c }),
         between(byte(b'['), byte(b']'), range()),
-        between(byte(b'('), byte(b')'), regex()),
+        // Non-capturing groups only group their regex, so they don't take a
+        // group number and are parsed to the regex itself
+        attempt(between(byte::bytes(b"(?:"), byte(b')'), regex())),
+        between(byte(b'('), byte(b')'), regex()).map(|re| RegExpr::Group {
+            group_re: Box::new(re),
+            index: 0,
+        }),
     ))
 }
 
+// Escape sequences: `\n`, `\t`, `\r` and `\0` for the usual control characters, `\xHH` for
+// any byte given as two hex digits, and otherwise the escaped character itself
+fn escaped<Input>() -> impl Parser<Input, Output = u8>
+where
+    Input: Stream<Token = u8>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    byte(b'\\').with(choice((
+        byte(b'x')
+            .with((byte::hex_digit(), byte::hex_digit()))
+            .map(|(high, low)| parse_hex_digits(&[high, low])),
+        byte(b'n').map(|_| b'\n'),
+        byte(b't').map(|_| b'\t'),
+        byte(b'r').map(|_| b'\r'),
+        byte(b'0').map(|_| b'\0'),
+        parser::token::any(),
+    )))
+}
+
 parser! {
     fn range[Input]()(Input) -> RegExpr
         where [Input: Stream<Token = u8>]
@@ -283,23 +611,139 @@ parser! {
         }
 }
 
+// Contents of a bracketed character class, negated by a leading `^`. A `]`
+// right after the opening bracket (or the `^`) is a literal one, as a class
+// can't be empty
 fn range_<Input>() -> impl Parser<Input, Output = RegExpr>
+where
+    Input: Stream<Token = u8>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    (
+        optional(byte(b'^')),
+        choice((
+            byte(b']')
+                .with(many(class_items()))
+                .map(|mut items: Vec<Vec<(u8, u8)>>| {
+                    items.insert(0, vec![(b']', b']')]);
+                    items
+                }),
+            many1(class_items()),
+        )),
+    )
+        .map(|(negated, items)| {
+            let re = class_regex(items.into_iter().flatten().collect());
+            if negated.is_some() {
+                RegExpr::Not {
+                    not_re: Box::new(re),
+                }
+            } else {
+                re
+            }
+        })
+}
+
+// A shorthand class, or a single item of a class
+fn class_items<Input>() -> impl Parser<Input, Output = Vec<(u8, u8)>>
 where
     Input: Stream<Token = u8>,
     Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
 {
     choice((
-        byte(b'^').with(range()).map(|re| RegExpr::Not {
-            not_re: Box::new(re),
-        }),
-        attempt(
-            (byte::alpha_num(), byte(b'-'), byte::alpha_num())
-                .map(|(from, _, to)| RegExpr::Between { from, to }),
-        ),
-        many1(byte::alpha_num()).map(|cs| RegExpr::Range { cs }),
+        attempt(shorthand_class()),
+        class_item().map(|item| vec![item]),
+    ))
+}
+
+// A character or a range of characters of a class, as an inclusive `(from,
+// to)` pair
+fn class_item<Input>() -> impl Parser<Input, Output = (u8, u8)>
+where
+    Input: Stream<Token = u8>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    choice((
+        attempt((class_char(), byte(b'-'), class_char())).map(|(from, _, to)| (from, to)),
+        class_char().map(|c| (c, c)),
     ))
 }
 
+fn class_char<Input>() -> impl Parser<Input, Output = u8>
+where
+    Input: Stream<Token = u8>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    choice((
+        escaped(),
+        parser::token::satisfy(|c: u8| c != b']' && c != b'\\'),
+    ))
+}
+
+const DIGIT_CLASS: [(u8, u8); 1] = [(b'0', b'9')];
+const WORD_CLASS: [(u8, u8); 4] = [(b'0', b'9'), (b'A', b'Z'), (b'_', b'_'), (b'a', b'z')];
+// The same bytes as `u8::is_ascii_whitespace`: `\t`, `\n`, form feed, `\r`
+// and space
+const SPACE_CLASS: [(u8, u8); 3] = [(b'\t', b'\n'), (b'\x0C', b'\r'), (b' ', b' ')];
+
+// `\d`, `\w` and `\s`, and their negations `\D`, `\W` and `\S`, as the items
+// of a class. The negations are given as the complement of the items rather
+// than with a `Not`, so that they can also be part of a bracketed class
+fn shorthand_class<Input>() -> impl Parser<Input, Output = Vec<(u8, u8)>>
+where
+    Input: Stream<Token = u8>,
+    Input::Error: ParseError<Input::Token, Input::Range, Input::Position>,
+{
+    byte(b'\\').with(choice((
+        byte(b'd').map(|_| DIGIT_CLASS.to_vec()),
+        byte(b'w').map(|_| WORD_CLASS.to_vec()),
+        byte(b's').map(|_| SPACE_CLASS.to_vec()),
+        byte(b'D').map(|_| complement_class(&DIGIT_CLASS)),
+        byte(b'W').map(|_| complement_class(&WORD_CLASS)),
+        byte(b'S').map(|_| complement_class(&SPACE_CLASS)),
+    )))
+}
+
+// Items covering all the bytes not covered by `items`, which must be sorted
+// and not overlapping
+fn complement_class(items: &[(u8, u8)]) -> Vec<(u8, u8)> {
+    let mut res = vec![];
+    let mut next: u16 = 0;
+    for (from, to) in items {
+        if next < *from as u16 {
+            res.push((next as u8, from - 1));
+        }
+        next = *to as u16 + 1;
+    }
+    if next <= u8::MAX as u16 {
+        res.push((next as u8, u8::MAX));
+    }
+    res
+}
+
+// Single characters are gathered in a `Range`, and each range of characters is
+// a `Between`, all of them joined by `Either`. There is at least one item
+fn class_regex(items: Vec<(u8, u8)>) -> RegExpr {
+    let cs: Vec<u8> = items
+        .iter()
+        .filter(|(from, to)| from == to)
+        .map(|(c, _)| *c)
+        .collect();
+    let mut res: Vec<RegExpr> = items
+        .into_iter()
+        .filter(|(from, to)| from != to)
+        .map(|(from, to)| RegExpr::Between { from, to })
+        .collect();
+    if !cs.is_empty() {
+        res.insert(0, RegExpr::Range { cs });
+    }
+
+    let first = res.remove(0);
+    res.into_iter().fold(first, |l_re, r_re| RegExpr::Either {
+        l_re: Box::new(l_re),
+        r_re: Box::new(r_re),
+    })
+}
+
 fn repeated<Input>() -> impl Parser<Input, Output = RegExpr>
 where
     Input: Stream<Token = u8>,
@@ -357,6 +801,10 @@ fn parse_digits(digits: &[u8]) -> usize {
     std::str::from_utf8(digits).unwrap().parse().unwrap()
 }
 
+fn parse_hex_digits(digits: &[u8]) -> u8 {
+    u8::from_str_radix(std::str::from_utf8(digits).unwrap(), 16).unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use crate::parser::{parse, RegExpr};
@@ -378,7 +826,17 @@ mod tests {
     #[test_case("/'/", RegExpr::Char { c: b'\'' }; "not necessary to escape single quote")]
     #[test_case("/\"/", RegExpr::Char { c: b'\"' }; "not necessary to escape double quote")]
     #[test_case("/\\h/", RegExpr::Char { c: b'h' }; "anything can be escaped")]
-    #[test_case("/./", RegExpr::AnyChar; "any")]
+    #[test_case("/\\n/", RegExpr::Char { c: b'\n' }; "newline escape")]
+    #[test_case("/\\t/", RegExpr::Char { c: b'\t' }; "tab escape")]
+    #[test_case("/\\r/", RegExpr::Char { c: b'\r' }; "carriage return escape")]
+    #[test_case("/\\0/", RegExpr::Char { c: 0 }; "nul escape")]
+    #[test_case("/\\x41/", RegExpr::Char { c: b'A' }; "hex escape")]
+    #[test_case("/\\x89/", RegExpr::Char { c: 0x89 }; "hex escape above ascii")]
+    #[test_case("/\\xfF/", RegExpr::Char { c: 0xff }; "hex escape mixed case")]
+    #[test_case("/./", RegExpr::Not { not_re: Box::new(RegExpr::Char { c: b'\n' }) }; "any but newline")]
+    #[test_case("/./s", RegExpr::AnyChar; "any")]
+    #[test_case("/./is", RegExpr::AnyChar; "any with both flags")]
+    #[test_case("/./si", RegExpr::AnyChar; "any with both flags reversed")]
     #[test_case("/abc/",
         RegExpr::Seq {re_xs: vec![
             RegExpr::Char { c: b'a' },
@@ -503,7 +961,7 @@ mod tests {
             RegExpr::Eof,
         ]};
         "<sof>ab<between 2 and 4>c<eof>")]
-    #[test_case("/^.$/",
+    #[test_case("/^.$/s",
         RegExpr::Seq {re_xs: vec![
             RegExpr::Sof,
             RegExpr::AnyChar,
@@ -598,9 +1056,12 @@ mod tests {
         "repeat bounded at least and at most (w/ {x,y}")]
     #[test_case("/(a|b)*/",
         RegExpr::Repeated {
-            repeat_re: Box::new(RegExpr::Either {
-                l_re: Box::new(RegExpr::Char { c: b'a' }),
-                r_re: Box::new(RegExpr::Char { c: b'b' }),
+            repeat_re: Box::new(RegExpr::Group {
+                group_re: Box::new(RegExpr::Either {
+                    l_re: Box::new(RegExpr::Char { c: b'a' }),
+                    r_re: Box::new(RegExpr::Char { c: b'b' }),
+                }),
+                index: 0,
             }),
             at_least: None,
             at_most: None,
@@ -608,14 +1069,75 @@ mod tests {
         "repeat complex unbounded")]
     #[test_case("/(a|b){3,7}/",
         RegExpr::Repeated {
-            repeat_re: Box::new(RegExpr::Either {
-                l_re: Box::new(RegExpr::Char { c: b'a' }),
-                r_re: Box::new(RegExpr::Char { c: b'b' }),
+            repeat_re: Box::new(RegExpr::Group {
+                group_re: Box::new(RegExpr::Either {
+                    l_re: Box::new(RegExpr::Char { c: b'a' }),
+                    r_re: Box::new(RegExpr::Char { c: b'b' }),
+                }),
+                index: 0,
             }),
             at_least: Some(3),
             at_most: Some(7),
         };
         "repeat complex bounded")]
+    #[test_case("/(a(b))(c)/",
+        RegExpr::Seq { re_xs: vec![
+            RegExpr::Group {
+                group_re: Box::new(RegExpr::Seq { re_xs: vec![
+                    RegExpr::Char { c: b'a' },
+                    RegExpr::Group {
+                        group_re: Box::new(RegExpr::Char { c: b'b' }),
+                        index: 1,
+                    },
+                ]}),
+                index: 0,
+            },
+            RegExpr::Group {
+                group_re: Box::new(RegExpr::Char { c: b'c' }),
+                index: 2,
+            },
+        ]};
+        "groups numbered by opening parenthesis")]
+    #[test_case("/cat|dog|bird/",
+        RegExpr::Either {
+            l_re: Box::new(RegExpr::Seq { re_xs: vec![
+                RegExpr::Char { c: b'c' },
+                RegExpr::Char { c: b'a' },
+                RegExpr::Char { c: b't' },
+            ]}),
+            r_re: Box::new(RegExpr::Either {
+                l_re: Box::new(RegExpr::Seq { re_xs: vec![
+                    RegExpr::Char { c: b'd' },
+                    RegExpr::Char { c: b'o' },
+                    RegExpr::Char { c: b'g' },
+                ]}),
+                r_re: Box::new(RegExpr::Seq { re_xs: vec![
+                    RegExpr::Char { c: b'b' },
+                    RegExpr::Char { c: b'i' },
+                    RegExpr::Char { c: b'r' },
+                    RegExpr::Char { c: b'd' },
+                ]}),
+            }),
+        };
+        "several alternatives")]
+    #[test_case("/(ab|cd)e/",
+        RegExpr::Seq { re_xs: vec![
+            RegExpr::Group {
+                group_re: Box::new(RegExpr::Either {
+                    l_re: Box::new(RegExpr::Seq { re_xs: vec![
+                        RegExpr::Char { c: b'a' },
+                        RegExpr::Char { c: b'b' },
+                    ]}),
+                    r_re: Box::new(RegExpr::Seq { re_xs: vec![
+                        RegExpr::Char { c: b'c' },
+                        RegExpr::Char { c: b'd' },
+                    ]}),
+                }),
+                index: 0,
+            },
+            RegExpr::Char { c: b'e' },
+        ]};
+        "alternatives in group")]
     #[test_case("/^ab|cd/",
         RegExpr::Seq { re_xs: vec![
             RegExpr::Sof,
@@ -692,10 +1214,118 @@ mod tests {
     #[test_case("/[79]/",
         RegExpr::Range { cs: vec![b'7', b'9'] };
         "able to match a number range (part 2)")]
+    #[test_case("/[a-z0-9_]/",
+        RegExpr::Either {
+            l_re: Box::new(RegExpr::Either {
+                l_re: Box::new(RegExpr::Range { cs: vec![b'_'] }),
+                r_re: Box::new(RegExpr::Between { from: b'a', to: b'z' }),
+            }),
+            r_re: Box::new(RegExpr::Between { from: b'0', to: b'9' }),
+        };
+        "class with several ranges and characters")]
+    #[test_case("/[]a]/",
+        RegExpr::Range { cs: vec![b']', b'a'] };
+        "leading bracket in class is literal")]
+    #[test_case("/[^]]/",
+        RegExpr::Not { not_re: Box::new(RegExpr::Range { cs: vec![b']'] }) };
+        "leading bracket in negated class is literal")]
+    #[test_case("/[a-]/",
+        RegExpr::Range { cs: vec![b'a', b'-'] };
+        "trailing minus in class is literal")]
+    #[test_case("/[\\x00-\\xff]/",
+        RegExpr::Between { from: 0, to: 255 };
+        "class spanning the full byte")]
+    #[test_case("/[.*]/",
+        RegExpr::Range { cs: vec![b'.', b'*'] };
+        "no special characters in class")]
+    #[test_case("/\\d/",
+        RegExpr::Between { from: b'0', to: b'9' };
+        "digit shorthand")]
+    #[test_case("/\\D/",
+        RegExpr::Either {
+            l_re: Box::new(RegExpr::Between { from: b'\0', to: b'/' }),
+            r_re: Box::new(RegExpr::Between { from: b':', to: b'\xFF' }),
+        };
+        "negated digit shorthand")]
+    #[test_case("/\\w/",
+        RegExpr::Either {
+            l_re: Box::new(RegExpr::Either {
+                l_re: Box::new(RegExpr::Either {
+                    l_re: Box::new(RegExpr::Range { cs: vec![b'_'] }),
+                    r_re: Box::new(RegExpr::Between { from: b'0', to: b'9' }),
+                }),
+                r_re: Box::new(RegExpr::Between { from: b'A', to: b'Z' }),
+            }),
+            r_re: Box::new(RegExpr::Between { from: b'a', to: b'z' }),
+        };
+        "word shorthand")]
+    #[test_case("/\\s/",
+        RegExpr::Either {
+            l_re: Box::new(RegExpr::Either {
+                l_re: Box::new(RegExpr::Range { cs: vec![b' '] }),
+                r_re: Box::new(RegExpr::Between { from: b'\t', to: b'\n' }),
+            }),
+            r_re: Box::new(RegExpr::Between { from: b'\x0C', to: b'\r' }),
+        };
+        "space shorthand")]
+    #[test_case("/[\\d.]/",
+        RegExpr::Either {
+            l_re: Box::new(RegExpr::Range { cs: vec![b'.'] }),
+            r_re: Box::new(RegExpr::Between { from: b'0', to: b'9' }),
+        };
+        "shorthand in class")]
+    #[test_case("/(?:ab)+/",
+        RegExpr::Repeated {
+            repeat_re: Box::new(RegExpr::Seq { re_xs: vec![
+                RegExpr::Char { c: b'a' },
+                RegExpr::Char { c: b'b' },
+            ]}),
+            at_least: Some(1),
+            at_most: None,
+        };
+        "repeated non-capturing group")]
+    #[test_case("/(?:a(b))(c)/",
+        RegExpr::Seq { re_xs: vec![
+            RegExpr::Seq { re_xs: vec![
+                RegExpr::Char { c: b'a' },
+                RegExpr::Group {
+                    group_re: Box::new(RegExpr::Char { c: b'b' }),
+                    index: 0,
+                },
+            ]},
+            RegExpr::Group {
+                group_re: Box::new(RegExpr::Char { c: b'c' }),
+                index: 1,
+            },
+        ]};
+        "non-capturing group not numbered")]
     fn test_parser(pattern: &str, exp: RegExpr) {
         match parse(pattern) {
             Ok(got) => assert_eq!(exp, got),
             Err(e) => panic!("got err: {}", e),
         }
     }
+
+    #[test_case("/\\xGG/" ; "invalid hex digits")]
+    #[test_case("/[]/" ; "empty class")]
+    #[test_case("/a{4,2}/" ; "least limit above the most limit")]
+    #[test_case("/(a{3,1})?b/" ; "nested least limit above the most limit")]
+    #[test_case("/[a/" ; "unterminated class")]
+    #[test_case("/\\x1/" ; "single hex digit")]
+    #[test_case("/\\x/" ; "no hex digits")]
+    #[test_case("/+a/" ; "one or more without atom")]
+    #[test_case("/*/" ; "zero or more without atom")]
+    #[test_case("/?/" ; "optional without atom")]
+    #[test_case("/^+a/" ; "quantifier after start anchor")]
+    #[test_case("/a|+b/" ; "quantifier at start of alternative")]
+    #[test_case("/(?a)/" ; "quantifier at start of group")]
+    #[test_case("/(?=a)/" ; "unsupported group kind")]
+    #[test_case("/(?:a/" ; "unterminated non-capturing group")]
+    #[test_case("/a|/" ; "empty right alternative")]
+    #[test_case("/|a/" ; "empty left alternative")]
+    #[test_case("/(a||b)c/" ; "empty alternative in group")]
+    #[test_case("/ab|$/" ; "empty alternative before end")]
+    fn test_parser_rejects_invalid_pattern(pattern: &str) {
+        assert!(parse(pattern).is_err());
+    }
 }
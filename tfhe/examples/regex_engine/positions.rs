@@ -0,0 +1,179 @@
+use crate::cache::parse_pattern;
+use crate::ciphertext::{number_layout, StringCiphertext};
+use crate::engine::{char_comparisons, or_branches};
+use crate::execution::{lazy_branches, Execution, LazyExecution};
+use anyhow::{anyhow, Result};
+use tfhe::integer::{RadixCiphertextBig, ServerKey};
+
+/// Returns the start positions of the first `k` matches of the pattern.
+///
+/// Results in exactly `k` pairs `(found, position)` of encrypted values, so
+/// that the number of matches is not revealed. The pairs hold the positions at
+/// which a match starts, in increasing order. When there are fewer than `k`
+/// such positions, the remaining pairs have `found` set to false and an
+/// encrypted 0 as position. Positions beyond the `k`-th match are dropped.
+///
+/// The positions are compacted from the per position match mask with a fixed
+/// size selection: an encrypted one-hot count of the matches seen so far
+/// selects the output slot of each position. This takes about `4 * k` boolean
+/// operations per content position on top of the matching itself, so the cost
+/// grows with `k` times the content length.
+///
+/// The positions have as many blocks as the content characters, and an error
+/// is returned if the content is too long for its positions to be represented
+/// on them (256 characters or more with the default keys).
+#[allow(dead_code)]
+pub fn find_positions(
+    sk: &ServerKey,
+    content: &StringCiphertext,
+    pattern: &str,
+    k: usize,
+) -> Result<Vec<(RadixCiphertextBig, RadixCiphertextBig)>> {
+    let (num_blocks, max_position) = number_layout(sk, content, 0);
+    if content.len() as u64 > max_position {
+        return Err(anyhow!(
+            "content too long, at most {} characters are supported",
+            max_position
+        ));
+    }
+
+    let re = parse_pattern(pattern, false)?;
+    let mut exec = Execution::new(sk.clone(), content);
+    exec.prefill_char_comparisons(content, &char_comparisons(&re));
+
+    let ct_zero = || -> RadixCiphertextBig { sk.create_trivial_zero_radix(num_blocks) };
+    let mut res: Vec<(RadixCiphertextBig, RadixCiphertextBig)> =
+        (0..k).map(|_| (ct_zero(), ct_zero())).collect();
+
+    // Encrypted booleans, the j-th one is true when exactly j matches were seen
+    // so far, and all of them are false once k matches were seen
+    let mut ct_seen: Vec<RadixCiphertextBig> = (0..k)
+        .map(|j| sk.create_trivial_radix((j == 0) as u64, num_blocks))
+        .collect();
+
+    for i in 0..content.len() {
+        let branches: Vec<LazyExecution> = lazy_branches(content, &re, i);
+        let match_res = or_branches(&mut exec, &branches);
+        let ct_not_match = exec.ct_not(match_res.clone()).0;
+        let ct_match = match_res.0;
+
+        // Only the slot of the current count is selected, so at most one term
+        // of each sum is non zero
+        for ((found, position), seen) in res.iter_mut().zip(&ct_seen) {
+            let mut ct_selected = sk.bitand_parallelized(&ct_match, seen);
+            let mut ct_position = sk.smart_scalar_mul(&mut ct_selected, i as u64);
+            sk.smart_add_assign(found, &mut ct_selected);
+            sk.smart_add_assign(position, &mut ct_position);
+        }
+
+        // The count moves up by one on a match
+        let mut ct_prev_seen = sk.create_trivial_zero_radix(num_blocks);
+        for seen in ct_seen.iter_mut() {
+            let ct_stays = sk.bitand_parallelized(seen, &ct_not_match);
+            let ct_moves = sk.bitand_parallelized(&ct_prev_seen, &ct_match);
+            ct_prev_seen = std::mem::replace(seen, sk.bitor_parallelized(&ct_stays, &ct_moves));
+        }
+    }
+    for (found, position) in res.iter_mut() {
+        sk.full_propagate(found);
+        sk.full_propagate(position);
+    }
+
+    exec.log_ct_operations();
+    Ok(res)
+}
+
+/// Returns the 1-based position at which the first match of the pattern
+/// starts, or 0 if the pattern does not match.
+///
+/// Results in a single encrypted value, so that only the position of the
+/// first match is revealed to the client. Every content position is evaluated,
+/// including the end of the content where only an empty match can start, as
+/// for `has_match`, so the result is 0 exactly when `has_match` is false.
+///
+/// The first match is selected with a priority encoder over the per position
+/// match results: a running encrypted boolean tells whether a match was found
+/// at an earlier position, and only the position matching while it is still
+/// false adds its index to the result.
+///
+/// The position has as many blocks as the content characters, and an error is
+/// returned if the content is too long for its positions to be represented on
+/// them (255 characters or more with the default keys).
+#[allow(dead_code)]
+pub fn match_position(
+    sk: &ServerKey,
+    content: &StringCiphertext,
+    pattern: &str,
+) -> Result<RadixCiphertextBig> {
+    // The positions are 1-based, so they go up to one past the content length
+    let (num_blocks, max_position) = number_layout(sk, content, 0);
+    if content.len() as u64 >= max_position {
+        return Err(anyhow!(
+            "content too long, at most {} characters are supported",
+            max_position - 1
+        ));
+    }
+
+    let re = parse_pattern(pattern, false)?;
+    let mut exec = Execution::new(sk.clone(), content);
+    exec.prefill_char_comparisons(content, &char_comparisons(&re));
+
+    // Only the first matching position is selected, so at most one term of
+    // the sum is non zero
+    let mut ct_position: RadixCiphertextBig = sk.create_trivial_zero_radix(num_blocks);
+    let mut matched = exec.ct_false();
+    for i in 0..=content.len() {
+        let branches: Vec<LazyExecution> = lazy_branches(content, &re, i);
+        let match_res = or_branches(&mut exec, &branches);
+        let not_matched_before = exec.ct_not(matched.clone());
+        let mut ct_is_first = exec.ct_and(match_res.clone(), not_matched_before).0;
+
+        let mut ct_i = sk.smart_scalar_mul(&mut ct_is_first, (i + 1) as u64);
+        sk.smart_add_assign(&mut ct_position, &mut ct_i);
+
+        matched = exec.ct_or(matched, match_res);
+    }
+    sk.full_propagate(&mut ct_position);
+
+    exec.log_ct_operations();
+    Ok(ct_position)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::tests::{assert_decrypted_result, KEYS};
+    use crate::positions::{find_positions, match_position};
+    use test_case::test_case;
+
+    use crate::ciphertext::{encrypt_str, StringCiphertext};
+
+    #[test_case("abxab", "/ab/", 3, &[(1, 0), (1, 3), (0, 0)] ; "fewer matches than k")]
+    #[test_case("ababab", "/ab/", 2, &[(1, 0), (1, 2)] ; "more matches than k")]
+    #[test_case("abab", "/ab/", 2, &[(1, 0), (1, 2)] ; "exactly k matches")]
+    #[test_case("aaa", "/a+/", 2, &[(1, 0), (1, 1)] ; "overlapping matches")]
+    #[test_case("abc", "/x/", 2, &[(0, 0), (0, 0)] ; "no match")]
+    #[test_case("abc", "/a/", 0, &[] ; "k of 0")]
+    fn test_find_positions(content: &str, pattern: &str, k: usize, exp: &[(u64, u64)]) {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, content).unwrap();
+        let ct_res = find_positions(&KEYS.1, &ct_content, pattern, k).unwrap();
+
+        let got: Vec<(u64, u64)> = ct_res
+            .iter()
+            .map(|(found, position)| (KEYS.0.decrypt(found), KEYS.0.decrypt(position)))
+            .collect();
+        assert_eq!(exp, got.as_slice());
+    }
+
+    #[test_case("xxab", "/ab/", 3 ; "match not at the start")]
+    #[test_case("abab", "/ab/", 1 ; "first of several matches")]
+    #[test_case("abc", "/x/", 0 ; "no match")]
+    #[test_case("abc", "/^b/", 0 ; "anchored no match")]
+    #[test_case("abc", "/c$/", 3 ; "anchored at the end")]
+    #[test_case("abc", "/$/", 4 ; "empty match at the end")]
+    #[test_case("", "/^$/", 1 ; "empty content")]
+    fn test_match_position(content: &str, pattern: &str, exp: u64) {
+        assert_decrypted_result(content, exp, |sk, ct_content| {
+            match_position(sk, ct_content, pattern).unwrap()
+        });
+    }
+}
@@ -0,0 +1,116 @@
+use crate::cache::parse_pattern;
+use crate::ciphertext::StringCiphertext;
+use crate::engine::{build_branches, char_comparisons};
+use crate::execution::{ExecutedResult, Execution};
+use anyhow::Result;
+use rayon::prelude::*;
+use tfhe::integer::{RadixCiphertextBig, ServerKey};
+
+/// Marks the content characters that are part of a match of the pattern.
+///
+/// Results in one encrypted boolean per content character, true if the
+/// character lies within any match. Every way the pattern can match is taken
+/// into account, not only the leftmost or longest one: where matches overlap,
+/// or where a match is contained in another, the characters of all of them are
+/// marked, i.e. the mask is the union of all the matched regions. Empty matches
+/// don't mark any character.
+///
+/// The extent of each possible match is known in the clear from the regex
+/// structure, only whether it actually matches is encrypted. Each match flag is
+/// therefore computed once and ORed into the mask of every character within
+/// its extent.
+pub fn matched_region_mask(
+    sk: &ServerKey,
+    content: &StringCiphertext,
+    pattern: &str,
+) -> Result<Vec<RadixCiphertextBig>> {
+    let re = parse_pattern(pattern, false)?;
+
+    let mut exec = Execution::new(sk.clone(), content);
+    exec.prefill_char_comparisons(content, &char_comparisons(&re));
+
+    let mut mask: Vec<Vec<ExecutedResult>> = vec![vec![]; content.len()];
+    for start in 0..content.len() {
+        for (branch, end) in build_branches(content, &re, start) {
+            if end == start {
+                continue;
+            }
+            let branch_res = branch(&mut exec);
+            for flags in mask[start..end].iter_mut() {
+                flags.push(branch_res.clone());
+            }
+        }
+    }
+
+    let res = mask
+        .into_iter()
+        .map(|flags| exec.ct_or_all(flags).0)
+        .collect();
+    exec.log_ct_operations();
+    Ok(res)
+}
+
+/// Keeps the content characters that are part of a match of the pattern, and
+/// sets all the others to 0.
+///
+/// Results in an encrypted string of the same length as the content. The
+/// characters kept are those marked by `matched_region_mask`, so the union of
+/// all the matched regions. Each character is ANDed with its mask bit spread
+/// over the 8 bits of a character, which acts as a selection between the
+/// character and 0, the positions being handled in parallel.
+#[allow(dead_code)]
+pub fn keep_matches(
+    sk: &ServerKey,
+    content: &StringCiphertext,
+    pattern: &str,
+) -> Result<StringCiphertext> {
+    let mask = matched_region_mask(sk, content, pattern)?;
+
+    let res: Vec<RadixCiphertextBig> = content
+        .par_iter()
+        .zip(mask.par_iter())
+        .map(|(ct_char, ct_in_match)| {
+            let ct_char_mask = sk.scalar_mul_parallelized(ct_in_match, 0xFF);
+            sk.bitand_parallelized(ct_char, &ct_char_mask)
+        })
+        .collect();
+    Ok(res.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::tests::KEYS;
+    use crate::region::{keep_matches, matched_region_mask};
+    use test_case::test_case;
+
+    use crate::ciphertext::{encrypt_str, StringCiphertext};
+
+    #[test_case("xabx", "/ab/", &[0, 1, 1, 0] ; "single match")]
+    #[test_case("aaab", "/aa/", &[1, 1, 1, 0] ; "overlapping matches")]
+    #[test_case("abcbc", "/abc|cbc/", &[1, 1, 1, 1, 1] ; "overlapping alternatives")]
+    #[test_case("xaab", "/a+b/", &[0, 1, 1, 1] ; "nested matches")]
+    #[test_case("abxab", "/^ab/", &[1, 1, 0, 0, 0] ; "anchored")]
+    #[test_case("xyz", "/a?/", &[0, 0, 0] ; "only empty matches")]
+    fn test_matched_region_mask(content: &str, pattern: &str, exp: &[u64]) {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, content).unwrap();
+        let ct_res = matched_region_mask(&KEYS.1, &ct_content, pattern).unwrap();
+
+        let got: Vec<u64> = ct_res.iter().map(|ct| KEYS.0.decrypt(ct)).collect();
+        assert_eq!(exp, got.as_slice());
+    }
+
+    #[test_case("ab12cd345", "/[0-9]+/", &[0, 0, b'1', b'2', 0, 0, b'3', b'4', b'5'] ; "digits")]
+    #[test_case("xabx", "/ab/", &[0, b'a', b'b', 0] ; "single match")]
+    #[test_case("abc", "/x/", &[0, 0, 0] ; "no match")]
+    #[test_case("abc", "/.*/", b"abc" ; "whole content")]
+    fn test_keep_matches(content: &str, pattern: &str, exp: &[u8]) {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, content).unwrap();
+        let ct_res = keep_matches(&KEYS.1, &ct_content, pattern).unwrap();
+
+        let got: Vec<u8> = ct_res
+            .iter()
+            .map(|ct| KEYS.0.decrypt::<u64, _>(ct) as u8)
+            .collect();
+        assert_eq!(exp, got.as_slice());
+    }
+}
@@ -0,0 +1,298 @@
+use crate::cache::parse_pattern;
+use crate::ciphertext::{char_num_blocks, StringCiphertext};
+use crate::engine::{build_branches, char_comparisons, or_branches};
+use crate::execution::{lazy_branches, ExecutedResult, Execution, LazyExecution};
+use anyhow::{anyhow, Result};
+use rayon::prelude::*;
+use std::collections::BTreeMap;
+use tfhe::integer::{RadixCiphertextBig, ServerKey};
+
+/// Replaces the matches of the pattern with the replacement, in a result of
+/// the same length as the content together with a mask of the positions to
+/// keep.
+///
+/// As the length of an encrypted string is public, a replacement of a
+/// different length than the match can't change the length of the result.
+/// Instead, each position of the result comes with an encrypted boolean of the
+/// returned mask, and the replaced content is obtained by removing the
+/// positions whose mask is false after decryption.
+///
+/// The matches replaced are those `replace_all` of a regex library would
+/// replace: the content is scanned from left to right, taking at each position
+/// the longest match starting there, and resuming the scan after its end.
+/// Empty matches are not replaced, as there is no position to hold the
+/// replacement. For a match of `len` characters and a replacement of
+/// `rep_len` characters:
+/// - the first `min(len, rep_len)` positions of the match hold the first
+///   characters of the replacement, and their mask is true;
+/// - if `rep_len < len`, the remaining positions of the match are set to 0 and
+///   their mask is false, so the match shrinks to the replacement;
+/// - if `rep_len > len`, the replacement is truncated to `len` characters, as
+///   there is no position for the others.
+///
+/// The positions outside of the matches are left as is, and their mask is
+/// true.
+#[allow(dead_code)]
+pub fn replace_all_masked(
+    sk: &ServerKey,
+    content: &StringCiphertext,
+    pattern: &str,
+    replacement: &str,
+) -> Result<(StringCiphertext, Vec<RadixCiphertextBig>)> {
+    let re = parse_pattern(pattern, false)?;
+    let replacement = replacement.as_bytes();
+
+    let mut exec = Execution::new(sk.clone(), content);
+    exec.prefill_char_comparisons(content, &char_comparisons(&re));
+
+    // For each position, the selection flags of the matches covering it, and
+    // of those where it is past the end of the replacement. As the selected
+    // matches don't overlap, at most one flag of each position is true, and at
+    // most one term of each sum in `replaced` is non zero
+    let mut covered: Vec<Vec<ExecutedResult>> = vec![vec![]; content.len()];
+    let mut dropped: Vec<Vec<ExecutedResult>> = vec![vec![]; content.len()];
+    let mut replaced: Vec<RadixCiphertextBig> = (0..content.len())
+        .map(|_| sk.create_trivial_zero_radix(char_num_blocks(content)))
+        .collect();
+
+    for i in 0..content.len() {
+        let mut branches_by_end: BTreeMap<usize, Vec<LazyExecution>> = BTreeMap::new();
+        for (branch, end) in build_branches(content, &re, i) {
+            if end > i {
+                branches_by_end.entry(end).or_default().push(branch);
+            }
+        }
+
+        // All the selected matches covering i start before it, so its
+        // flags are complete at this point
+        let is_covered = exec.ct_or_all(covered[i].clone());
+        let is_free = exec.ct_not(is_covered);
+        let mut matched_longer = exec.ct_false();
+        for (end, branches) in branches_by_end.into_iter().rev() {
+            let end_res = or_branches(&mut exec, &branches);
+            let not_matched_longer = exec.ct_not(matched_longer.clone());
+            let is_longest = exec.ct_and(end_res.clone(), not_matched_longer);
+            let is_selected = exec.ct_and(is_longest, is_free.clone());
+
+            for pos in i..end {
+                covered[pos].push(is_selected.clone());
+                match replacement.get(pos - i) {
+                    Some(c) => {
+                        let mut ct_selected = is_selected.0.clone();
+                        let mut ct_c = sk.smart_scalar_mul(&mut ct_selected, *c as u64);
+                        sk.smart_add_assign(&mut replaced[pos], &mut ct_c);
+                    }
+                    None => dropped[pos].push(is_selected.clone()),
+                }
+            }
+
+            matched_longer = exec.ct_or(matched_longer, end_res);
+        }
+    }
+
+    let mut not_covered = Vec::with_capacity(content.len());
+    let mut mask = Vec::with_capacity(content.len());
+    for (pos_covered, pos_dropped) in covered.into_iter().zip(dropped) {
+        let is_covered = exec.ct_or_all(pos_covered);
+        not_covered.push(exec.ct_not(is_covered).0);
+        let is_dropped = exec.ct_or_all(pos_dropped);
+        mask.push(exec.ct_not(is_dropped).0);
+    }
+    exec.log_ct_operations();
+
+    // The content characters are kept outside of the matches, and the
+    // replacement characters are added within them
+    let res: Vec<RadixCiphertextBig> = content
+        .par_iter()
+        .zip(not_covered.par_iter())
+        .zip(replaced.par_iter_mut())
+        .map(|((ct_char, ct_not_covered), ct_replaced)| {
+            let ct_char_mask = sk.scalar_mul_parallelized(ct_not_covered, 0xFF);
+            let ct_kept = sk.bitand_parallelized(ct_char, &ct_char_mask);
+            sk.full_propagate_parallelized(ct_replaced);
+            sk.add_parallelized(&ct_kept, ct_replaced)
+        })
+        .collect();
+    Ok((res.into_iter().collect(), mask))
+}
+
+/// Replaces the matches of a fixed length pattern with a replacement of the
+/// same length, in a new encrypted string of the same length as the content.
+///
+/// As the length of an encrypted string is public, and as this function
+/// doesn't return a mask as `replace_all_masked` does, the result can only
+/// have the length of the content if each match is replaced by as many
+/// characters. So the pattern must be of fixed length, i.e. all of its matches
+/// have the same non zero length, which rules out `?`, `*`, `+`, repetitions
+/// with different limits and alternatives of different lengths, and the
+/// replacement must be of that length. An error is returned otherwise.
+///
+/// The matches replaced are found from left to right, the scan resuming after
+/// the end of each match, as with `replace_all_masked`. Each position of the
+/// result is then selected homomorphically, with
+/// `ServerKey::select_parallelized`, between the content character and the
+/// character of the replacement at the same offset of each match that may
+/// cover it. The selected matches don't overlap, so at most one of them
+/// covers each position.
+#[allow(dead_code)]
+pub fn replace_fixed_len(
+    sk: &ServerKey,
+    content: &StringCiphertext,
+    pattern: &str,
+    replacement: &str,
+) -> Result<StringCiphertext> {
+    if !replacement.is_ascii() {
+        return Err(anyhow!("replacement contains non-ascii characters"));
+    }
+    let ct_replacement: StringCiphertext = replacement
+        .bytes()
+        .map(|c| sk.create_trivial_radix(c as u64, char_num_blocks(content)))
+        .collect();
+    replace_fixed_len_encrypted(sk, content, pattern, &ct_replacement)
+}
+
+/// Same as `replace_fixed_len`, with an encrypted replacement, whose
+/// characters must have as many blocks as the content ones.
+pub fn replace_fixed_len_encrypted(
+    sk: &ServerKey,
+    content: &StringCiphertext,
+    pattern: &str,
+    replacement: &StringCiphertext,
+) -> Result<StringCiphertext> {
+    let re = parse_pattern(pattern, false)?;
+    let len = match re.fixed_len() {
+        Some(len) if len > 0 => len,
+        _ => {
+            return Err(anyhow!(
+                "pattern must have a fixed non zero length to be replaced in place"
+            ))
+        }
+    };
+    if replacement.len() != len {
+        return Err(anyhow!(
+            "replacement has {} characters, while the matches of the pattern have {}",
+            replacement.len(),
+            len
+        ));
+    }
+    if content.len() < len {
+        return Ok(content.iter().cloned().collect());
+    }
+
+    let mut exec = Execution::new(sk.clone(), content);
+    exec.prefill_char_comparisons(content, &char_comparisons(&re));
+
+    // A match is selected if it doesn't overlap a selected match starting
+    // before it, which can only start less than `len` positions before
+    let mut selected: Vec<ExecutedResult> = Vec::with_capacity(content.len() - len + 1);
+    for i in 0..=content.len() - len {
+        let branches: Vec<LazyExecution> = lazy_branches(content, &re, i);
+        let match_res = or_branches(&mut exec, &branches);
+        let is_covered = exec.ct_or_all(selected[i.saturating_sub(len - 1)..i].to_vec());
+        let is_free = exec.ct_not(is_covered);
+        selected.push(exec.ct_and(match_res, is_free));
+    }
+    exec.log_ct_operations();
+
+    let res: Vec<RadixCiphertextBig> = content
+        .par_iter()
+        .enumerate()
+        .map(|(pos, ct_char)| {
+            let first_start = (pos + 1).saturating_sub(len);
+            let last_start = pos.min(selected.len() - 1);
+            (first_start..=last_start).fold(ct_char.clone(), |ct_res, start| {
+                let options = [ct_res, replacement[pos - start].clone()];
+                sk.select_parallelized(&options, &selected[start].0)
+                    .unwrap()
+            })
+        })
+        .collect();
+    Ok(res.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::tests::KEYS;
+    use crate::replace::{replace_all_masked, replace_fixed_len, replace_fixed_len_encrypted};
+    use test_case::test_case;
+
+    use crate::ciphertext::{encrypt_str, StringCiphertext};
+
+    // Cleartext reference for a literal pattern, with the replacement
+    // truncated to the length of the matches
+    fn replace_all_reference(content: &str, literal: &str, replacement: &str) -> String {
+        let truncated = &replacement[..replacement.len().min(literal.len())];
+        content.replace(literal, truncated)
+    }
+
+    fn replace_all_masked_unmasked(content: &str, pattern: &str, replacement: &str) -> String {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, content).unwrap();
+        let (ct_res, ct_mask) =
+            replace_all_masked(&KEYS.1, &ct_content, pattern, replacement).unwrap();
+        assert_eq!(ct_content.len(), ct_res.len());
+        assert_eq!(ct_content.len(), ct_mask.len());
+
+        ct_res
+            .iter()
+            .zip(ct_mask.iter())
+            .filter(|(_, ct_keep)| KEYS.0.decrypt_bool(ct_keep).unwrap())
+            .map(|(ct, _)| KEYS.0.decrypt::<u64, _>(ct) as u8 as char)
+            .collect()
+    }
+
+    fn decrypt(ct_res: &StringCiphertext) -> String {
+        ct_res
+            .iter()
+            .map(|ct| KEYS.0.decrypt::<u64, _>(ct) as u8 as char)
+            .collect()
+    }
+
+    #[test_case("xabcxabc", "abc", "Z" ; "shrinking")]
+    #[test_case("xabcxabc", "abc", "UVW" ; "same length")]
+    #[test_case("xabx", "ab", "WXYZ" ; "growing")]
+    #[test_case("aaaa", "aa", "b" ; "non overlapping matches")]
+    #[test_case("xyz", "ab", "c" ; "no match")]
+    fn test_replace_all_masked(content: &str, literal: &str, replacement: &str) {
+        let pattern = format!("/{literal}/");
+        assert_eq!(
+            replace_all_reference(content, literal, replacement),
+            replace_all_masked_unmasked(content, &pattern, replacement)
+        );
+    }
+
+    #[test_case("xaaabyab", "/a+b/", "Z", "xZyZ" ; "longest match")]
+    #[test_case("ab1cd23", "/[0-9]+/", "##", "ab#cd##" ; "truncated and shrunk")]
+    #[test_case("abc", "/x*/", "Z", "abc" ; "only empty matches")]
+    fn test_replace_all_masked_regex(content: &str, pattern: &str, replacement: &str, exp: &str) {
+        assert_eq!(
+            exp,
+            replace_all_masked_unmasked(content, pattern, replacement)
+        );
+    }
+
+    #[test_case("xabcxabc", "/abc/", "UVW", "xUVWxUVW" ; "literal")]
+    #[test_case("aaaaa", "/aa/", "bc", "bcbca" ; "non overlapping matches")]
+    #[test_case("a1b22", "/[0-9]/", "#", "a#b##" ; "class")]
+    #[test_case("abab", "/^ab/", "cd", "cdab" ; "anchored")]
+    #[test_case("ab", "/abc/", "xyz", "ab" ; "content shorter than the pattern")]
+    fn test_replace_fixed_len(content: &str, pattern: &str, replacement: &str, exp: &str) {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, content).unwrap();
+        let ct_res = replace_fixed_len(&KEYS.1, &ct_content, pattern, replacement).unwrap();
+        assert_eq!(exp, decrypt(&ct_res));
+
+        let ct_replacement = encrypt_str(&KEYS.0, replacement).unwrap();
+        let ct_res =
+            replace_fixed_len_encrypted(&KEYS.1, &ct_content, pattern, &ct_replacement).unwrap();
+        assert_eq!(exp, decrypt(&ct_res));
+    }
+
+    #[test_case("/ab?/", "x" ; "optional")]
+    #[test_case("/a+/", "x" ; "unbounded repetition")]
+    #[test_case("/ab|c/", "x" ; "alternatives of different lengths")]
+    #[test_case("/^$/", "" ; "empty matches")]
+    #[test_case("/abc/", "xy" ; "replacement of another length")]
+    fn test_replace_fixed_len_rejected(pattern: &str, replacement: &str) {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, "abc").unwrap();
+        assert!(replace_fixed_len(&KEYS.1, &ct_content, pattern, replacement).is_err());
+    }
+}
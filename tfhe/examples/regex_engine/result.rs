@@ -0,0 +1,71 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use tfhe::integer::{RadixCiphertextBig, RadixClientKey};
+
+/// Encrypted extent of a match, as returned by `find`.
+///
+/// All of its ciphertexts are serializable, so that a server can send back the
+/// whole result as a single blob for the client to deserialize and decrypt
+/// with its client key. A replaced or otherwise transformed content, being a
+/// `StringCiphertext`, can be sent back the same way.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct MatchResult {
+    /// Encrypted boolean telling whether the pattern matched
+    pub found: RadixCiphertextBig,
+    /// Encrypted content position of the first matched character
+    pub start: RadixCiphertextBig,
+    /// Encrypted number of matched characters
+    pub len: RadixCiphertextBig,
+}
+
+impl MatchResult {
+    /// Decrypts the result into the position and length of the match, or
+    /// `None` if the pattern did not match.
+    #[allow(dead_code)]
+    pub fn decrypt(&self, client_key: &RadixClientKey) -> Result<Option<(usize, usize)>> {
+        if !client_key.decrypt_bool(&self.found)? {
+            return Ok(None);
+        }
+        let start: u64 = client_key.decrypt(&self.start);
+        let len: u64 = client_key.decrypt(&self.len);
+        Ok(Some((start as usize, len as usize)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::captures::find;
+    use crate::engine::tests::KEYS;
+    use crate::result::MatchResult;
+    use test_case::test_case;
+
+    use crate::ciphertext::{decrypt_str_streaming, encrypt_str, StringCiphertext};
+
+    #[test_case("xabcd", "/bc/", Some((2, 2)) ; "match")]
+    #[test_case("xabcd", "/ba/", None ; "no match")]
+    fn test_match_result_serialization(content: &str, pattern: &str, exp: Option<(usize, usize)>) {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, content).unwrap();
+        let ct_res = find(&KEYS.1, &ct_content, pattern).unwrap();
+
+        let serialized = bincode::serialize(&ct_res).unwrap();
+        let deserialized: MatchResult = bincode::deserialize(&serialized).unwrap();
+
+        assert_eq!(exp, deserialized.decrypt(&KEYS.0).unwrap());
+    }
+
+    #[test]
+    fn test_string_ciphertext_serialization() {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, "abc").unwrap();
+
+        let serialized = bincode::serialize(&ct_content).unwrap();
+        let deserialized: StringCiphertext = bincode::deserialize(&serialized).unwrap();
+
+        let mut got = vec![];
+        decrypt_str_streaming(&KEYS.0, &deserialized, |c| {
+            got.push(c);
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(b"abc", got.as_slice());
+    }
+}
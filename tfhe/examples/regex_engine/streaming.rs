@@ -0,0 +1,161 @@
+use crate::ciphertext::StringCiphertext;
+use crate::compiled::CompiledRegex;
+use crate::engine::has_match_compiled;
+use anyhow::{anyhow, Result};
+use tfhe::integer::{RadixCiphertextBig, ServerKey};
+
+/// Matches a compiled pattern against content that arrives a chunk at a time.
+///
+/// Each chunk is matched together with the last characters of the content
+/// pushed before it, so that matches straddling a chunk boundary are found
+/// too. The number of characters carried over is the length of the longest
+/// match minus one, which is enough for any match to lie entirely within a
+/// chunk and the characters carried over before it.
+pub struct StreamingMatcher {
+    compiled: CompiledRegex,
+    // Number of characters carried over from a chunk to the next one
+    overlap: usize,
+    // The last `overlap` characters of the content pushed so far, or all of
+    // them if there are fewer
+    tail: StringCiphertext,
+    found: Option<RadixCiphertextBig>,
+}
+
+#[allow(dead_code)]
+impl StreamingMatcher {
+    /// Results in an error if the pattern is anchored or if its matches can be
+    /// arbitrarily long.
+    ///
+    /// Each chunk is matched on its own, so `^` and `$` would anchor at the
+    /// start and the end of every chunk rather than of the whole content, and
+    /// no finite overlap would find all the matches straddling a chunk
+    /// boundary. Compiling the pattern with `MatchOptions::max_repeat` bounds
+    /// the length of its matches.
+    pub fn new(compiled: CompiledRegex) -> Result<Self> {
+        if compiled.re.has_anchor() {
+            return Err(anyhow!(
+                "anchored patterns are not supported when streaming the content"
+            ));
+        }
+        let Some(max_len) = compiled.re.max_len() else {
+            return Err(anyhow!(
+                "the matches of the pattern can be arbitrarily long, \
+                bound them with MatchOptions::max_repeat to stream the content"
+            ));
+        };
+        Ok(Self {
+            compiled,
+            overlap: max_len.saturating_sub(1),
+            tail: StringCiphertext::empty(),
+            found: None,
+        })
+    }
+
+    /// Number of characters carried over from a chunk to the next one.
+    pub fn overlap(&self) -> usize {
+        self.overlap
+    }
+
+    /// Matches the pattern in the chunk, preceded by the characters carried
+    /// over from the previous chunks, and ORs the result into the running
+    /// result.
+    ///
+    /// Results in the updated running result, see `found`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the characters of the chunk don't have the same number of
+    /// blocks as the ones of the previous chunks.
+    pub fn push_chunk(&mut self, sk: &ServerKey, chunk: &StringCiphertext) -> &RadixCiphertextBig {
+        let mut window = std::mem::take(&mut self.tail);
+        window.extend(chunk.iter().cloned());
+
+        let res = has_match_compiled(sk, &window, &self.compiled);
+        let found = match self.found.take() {
+            Some(found) => sk.bitor_parallelized(&found, &res),
+            None => res,
+        };
+
+        self.tail = window[window.len().saturating_sub(self.overlap)..]
+            .iter()
+            .cloned()
+            .collect();
+        self.found.insert(found)
+    }
+
+    /// Encrypted boolean telling whether a match was found in the content
+    /// pushed so far, or `None` if no chunk was pushed yet.
+    pub fn found(&self) -> Option<&RadixCiphertextBig> {
+        self.found.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::compiled::CompiledRegex;
+    use crate::engine::tests::KEYS;
+    use crate::engine::MatchOptions;
+    use crate::streaming::StreamingMatcher;
+    use test_case::test_case;
+
+    use crate::ciphertext::{encrypt_str, StringCiphertext};
+
+    #[test_case(&["xxab", "cdxx"], "/abcd/", &[0, 1] ; "match straddling two chunks")]
+    #[test_case(&["xa", "b", "cd"], "/abcd/", &[0, 0, 1] ; "match straddling three chunks")]
+    #[test_case(&["abcd", "xxxx"], "/abcd/", &[1, 1] ; "running result stays true")]
+    #[test_case(&["xxab", "xdxx"], "/abcd/", &[0, 0] ; "no match")]
+    #[test_case(&["xa", "bbc"], "/ab?b?c/", &[0, 1] ; "variable length match")]
+    #[test_case(&["", "ab"], "/ab/", &[0, 1] ; "empty chunk")]
+    fn test_streaming_matcher(chunks: &[&str], pattern: &str, exp: &[u64]) {
+        let compiled = CompiledRegex::compile(pattern).unwrap();
+        let mut matcher = StreamingMatcher::new(compiled).unwrap();
+        assert!(matcher.found().is_none());
+
+        let mut got: Vec<u64> = vec![];
+        for chunk in chunks {
+            let ct_chunk: StringCiphertext = encrypt_str(&KEYS.0, chunk).unwrap();
+            got.push(KEYS.0.decrypt(matcher.push_chunk(&KEYS.1, &ct_chunk)));
+        }
+        assert_eq!(exp, got.as_slice());
+    }
+
+    #[test_case("/abcd/", None, 3 ; "fixed length")]
+    #[test_case("/ab?c/", None, 2 ; "bounded length")]
+    #[test_case("/ab*c/", Some(3), 4 ; "unbounded length capped")]
+    fn test_streaming_matcher_overlap(pattern: &str, max_repeat: Option<usize>, exp: usize) {
+        let options = MatchOptions {
+            max_repeat,
+            ..MatchOptions::default()
+        };
+        let compiled = CompiledRegex::compile_with_options(pattern, options).unwrap();
+        assert_eq!(exp, StreamingMatcher::new(compiled).unwrap().overlap());
+    }
+
+    #[test_case("/ab*c/" ; "unbounded length")]
+    #[test_case("/^ab/" ; "start anchor")]
+    #[test_case("/ab$/" ; "end anchor")]
+    #[test_case("/x|^ab/" ; "anchor in a branch")]
+    fn test_streaming_matcher_rejects(pattern: &str) {
+        let compiled = CompiledRegex::compile(pattern).unwrap();
+        assert!(StreamingMatcher::new(compiled).is_err());
+    }
+
+    #[test]
+    fn test_streaming_matcher_unbounded_straddling() {
+        // With `max_repeat`, a match of an unbounded pattern straddling the
+        // chunk boundary is found as long as it fits in the overlap
+        let options = MatchOptions {
+            max_repeat: Some(3),
+            ..MatchOptions::default()
+        };
+        let compiled = CompiledRegex::compile_with_options("/ab*c/", options).unwrap();
+        let mut matcher = StreamingMatcher::new(compiled).unwrap();
+
+        let mut got: Vec<u64> = vec![];
+        for chunk in ["xxab", "bcxx"] {
+            let ct_chunk: StringCiphertext = encrypt_str(&KEYS.0, chunk).unwrap();
+            got.push(KEYS.0.decrypt(matcher.push_chunk(&KEYS.1, &ct_chunk)));
+        }
+        assert_eq!(vec![0, 1], got);
+    }
+}
@@ -0,0 +1,129 @@
+use crate::ciphertext::StringCiphertext;
+use crate::engine::or_branches;
+use crate::execution::{lazy_branches, Execution, LazyExecution};
+use crate::keyword::starts_with_any;
+use crate::parser::RegExpr;
+use rayon::prelude::*;
+use tfhe::integer::{RadixCiphertextBig, ServerKey};
+
+/// Character replacing the trimmed characters in the results of `trim_prefix`
+/// and `trim_suffix`.
+pub const TRIMMED: u8 = 0;
+
+/// Removes the prefix from the start of the content, if the content starts
+/// with it.
+///
+/// As the length of an encrypted string is public, the result has the same
+/// length as the content, with the characters of the prefix replaced by
+/// `TRIMMED` when the content starts with it, so that the trimmed string is
+/// obtained by removing the leading `TRIMMED` characters after decryption.
+/// Otherwise the content is left as is. Nothing is trimmed if the prefix is
+/// empty or longer than the content.
+#[allow(dead_code)]
+pub fn trim_prefix(sk: &ServerKey, content: &StringCiphertext, prefix: &str) -> StringCiphertext {
+    if prefix.is_empty() || prefix.len() > content.len() {
+        return content.iter().cloned().collect();
+    }
+
+    let is_trimmed = starts_with_any(sk, content, &[prefix]);
+    trim_positions(sk, content, &is_trimmed, 0, prefix.as_bytes())
+}
+
+/// Removes the suffix from the end of the content, if the content ends with
+/// it.
+///
+/// The counterpart of `trim_prefix`: the result has the same length as the
+/// content, with the characters of the suffix replaced by `TRIMMED` when the
+/// content ends with it, to be removed after decryption. Nothing is trimmed
+/// if the suffix is empty or longer than the content.
+#[allow(dead_code)]
+pub fn trim_suffix(sk: &ServerKey, content: &StringCiphertext, suffix: &str) -> StringCiphertext {
+    if suffix.is_empty() || suffix.len() > content.len() {
+        return content.iter().cloned().collect();
+    }
+
+    let start = content.len() - suffix.len();
+    let mut re_xs: Vec<RegExpr> = suffix.bytes().map(|c| RegExpr::Char { c }).collect();
+    re_xs.push(RegExpr::Eof);
+    let re = RegExpr::Seq { re_xs };
+
+    let mut exec = Execution::new(sk.clone(), content);
+    let branches: Vec<LazyExecution> = lazy_branches(content, &re, start);
+    let is_trimmed = or_branches(&mut exec, &branches).0;
+    exec.log_ct_operations();
+
+    trim_positions(sk, content, &is_trimmed, start, suffix.as_bytes())
+}
+
+// Replaces the characters of `trimmed`, found at `start` in the content, by
+// `TRIMMED` if `is_trimmed` is true. As these characters are then known to
+// equal `trimmed`, the replacement is done by subtracting the encrypted
+// boolean times the difference between each of them and `TRIMMED`, rather
+// than selecting between the character and `TRIMMED`
+fn trim_positions(
+    sk: &ServerKey,
+    content: &[RadixCiphertextBig],
+    is_trimmed: &RadixCiphertextBig,
+    start: usize,
+    trimmed: &[u8],
+) -> StringCiphertext {
+    let res: Vec<RadixCiphertextBig> = content
+        .par_iter()
+        .enumerate()
+        .map(|(i, ct_char)| {
+            if i < start || i >= start + trimmed.len() {
+                return ct_char.clone();
+            }
+            let ct_diff =
+                sk.scalar_mul_parallelized(is_trimmed, (trimmed[i - start] - TRIMMED) as u64);
+            sk.sub_parallelized(ct_char, &ct_diff)
+        })
+        .collect();
+    res.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::tests::KEYS;
+    use crate::trim::{trim_prefix, trim_suffix, TRIMMED};
+    use test_case::test_case;
+
+    use crate::ciphertext::{encrypt_str, StringCiphertext};
+
+    fn decrypt_trimmed(ct_res: &StringCiphertext) -> String {
+        ct_res
+            .iter()
+            .map(|ct| KEYS.0.decrypt::<u64, _>(ct) as u8)
+            .filter(|c| *c != TRIMMED)
+            .map(|c| c as char)
+            .collect()
+    }
+
+    #[test_case("<b>text", "<b>" ; "present")]
+    #[test_case("text<b>", "<b>" ; "absent")]
+    #[test_case("<b>", "<b>" ; "whole content")]
+    #[test_case("<b", "<b>" ; "longer than the content")]
+    #[test_case("text", "" ; "empty prefix")]
+    fn test_trim_prefix(content: &str, prefix: &str) {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, content).unwrap();
+        let ct_res = trim_prefix(&KEYS.1, &ct_content, prefix);
+        assert_eq!(ct_content.len(), ct_res.len());
+
+        let exp = content.strip_prefix(prefix).unwrap_or(content);
+        assert_eq!(exp, decrypt_trimmed(&ct_res));
+    }
+
+    #[test_case("text</b>", "</b>" ; "present")]
+    #[test_case("</b>text", "</b>" ; "absent")]
+    #[test_case("</b>", "</b>" ; "whole content")]
+    #[test_case("/b>", "</b>" ; "longer than the content")]
+    #[test_case("text", "" ; "empty suffix")]
+    fn test_trim_suffix(content: &str, suffix: &str) {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, content).unwrap();
+        let ct_res = trim_suffix(&KEYS.1, &ct_content, suffix);
+        assert_eq!(ct_content.len(), ct_res.len());
+
+        let exp = content.strip_suffix(suffix).unwrap_or(content);
+        assert_eq!(exp, decrypt_trimmed(&ct_res));
+    }
+}
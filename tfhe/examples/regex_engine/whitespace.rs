@@ -0,0 +1,85 @@
+use crate::ciphertext::{char_num_blocks, StringCiphertext};
+use rayon::prelude::*;
+use tfhe::integer::{RadixCiphertextBig, ServerKey};
+
+/// Character replacing the collapsed spaces in the result of
+/// `collapse_spaces`.
+pub const COLLAPSED_SPACE: u8 = 0;
+
+/// Collapses the runs of spaces of the content into single spaces.
+///
+/// As the length of an encrypted string is public, the result can't be
+/// shorter than the content. It has the same length instead, with every space
+/// immediately following another space replaced by `COLLAPSED_SPACE`, so that
+/// the single-spaced text is obtained by removing the `COLLAPSED_SPACE`
+/// characters after decryption. The first space of each run is kept, and the
+/// other characters are left as is.
+///
+/// Each character and its predecessor are compared to the space in parallel,
+/// and the character is replaced when both are spaces. As only a space is ever
+/// replaced, the replacement is done by subtracting the encrypted boolean
+/// times the difference between the space and `COLLAPSED_SPACE`.
+#[allow(dead_code)]
+pub fn collapse_spaces(sk: &ServerKey, content: &StringCiphertext) -> StringCiphertext {
+    let ct_space = sk.create_trivial_radix(b' ' as u64, char_num_blocks(content));
+    let is_space: Vec<RadixCiphertextBig> = content
+        .par_iter()
+        .map(|ct_char| sk.eq_parallelized(ct_char, &ct_space))
+        .collect();
+
+    let res: Vec<RadixCiphertextBig> = (0..content.len())
+        .into_par_iter()
+        .map(|i| {
+            if i == 0 {
+                return content[0].clone();
+            }
+            let is_collapsed = sk.bitand_parallelized(&is_space[i - 1], &is_space[i]);
+            let ct_diff =
+                sk.scalar_mul_parallelized(&is_collapsed, (b' ' - COLLAPSED_SPACE) as u64);
+            sk.sub_parallelized(&content[i], &ct_diff)
+        })
+        .collect();
+    res.into_iter().collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::tests::KEYS;
+    use crate::whitespace::{collapse_spaces, COLLAPSED_SPACE};
+    use test_case::test_case;
+
+    use crate::ciphertext::{encrypt_str, StringCiphertext};
+
+    // Cleartext reference, keeping the first space of each run
+    fn collapse_spaces_reference(content: &str) -> String {
+        let mut res = String::new();
+        for c in content.chars() {
+            if !(c == ' ' && res.ends_with(' ')) {
+                res.push(c);
+            }
+        }
+        res
+    }
+
+    #[test_case("a  b" ; "double space")]
+    #[test_case("a    b c" ; "long run")]
+    #[test_case("  ab  " ; "leading and trailing runs")]
+    #[test_case("abc" ; "no space")]
+    #[test_case(" " ; "single space")]
+    #[test_case("" ; "empty")]
+    fn test_collapse_spaces(content: &str) {
+        let ct_content: StringCiphertext = encrypt_str(&KEYS.0, content).unwrap();
+        let ct_res = collapse_spaces(&KEYS.1, &ct_content);
+        assert_eq!(ct_content.len(), ct_res.len());
+
+        let got: Vec<u8> = ct_res
+            .iter()
+            .map(|ct| KEYS.0.decrypt::<u64, _>(ct) as u8)
+            .filter(|c| *c != COLLAPSED_SPACE)
+            .collect();
+        assert_eq!(
+            collapse_spaces_reference(content).as_bytes(),
+            got.as_slice()
+        );
+    }
+}
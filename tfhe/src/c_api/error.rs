@@ -0,0 +1,42 @@
+//! Module providing the retrieval of the error message of a failed `C` FFI call.
+
+use std::cell::RefCell;
+use std::os::raw::{c_char, c_int};
+
+thread_local! {
+    static LAST_ERROR_MESSAGE: RefCell<Option<String>> = RefCell::new(None);
+}
+
+pub(in crate::c_api) fn set_last_error_message(message: String) {
+    LAST_ERROR_MESSAGE.with(|last_error_message| *last_error_message.borrow_mut() = Some(message));
+}
+
+pub(in crate::c_api) fn clear_last_error_message() {
+    LAST_ERROR_MESSAGE.with(|last_error_message| *last_error_message.borrow_mut() = None);
+}
+
+/// Copy the error message of the last call that failed on the current thread into `buf`.
+///
+/// The message is written as a `NUL` terminated string, truncated to fit in the `len` bytes of
+/// `buf`. As the message is cleared by each successful call, an empty string is written if the
+/// last call made on the current thread succeeded.
+///
+/// Returns a non-zero value if `buf` is `NULL` or `len` is `0`, in which case nothing is written.
+#[no_mangle]
+pub unsafe extern "C" fn tfhe_last_error_message(buf: *mut c_char, len: usize) -> c_int {
+    if buf.is_null() || len == 0 {
+        return 1;
+    }
+
+    LAST_ERROR_MESSAGE.with(|last_error_message| {
+        let last_error_message = last_error_message.borrow();
+        let message = last_error_message.as_deref().unwrap_or_default().as_bytes();
+
+        // Keep room for the NUL terminator
+        let copied_len = message.len().min(len - 1);
+        std::ptr::copy_nonoverlapping(message.as_ptr(), buf.cast::<u8>(), copied_len);
+        *buf.add(copied_len) = 0;
+    });
+
+    0
+}
@@ -0,0 +1,20 @@
+use crate::integer;
+
+pub(in crate::c_api) enum IntegerRadixCiphertextInner {
+    Big(integer::RadixCiphertextBig),
+    Small(integer::RadixCiphertextSmall),
+}
+
+impl From<integer::RadixCiphertextBig> for IntegerRadixCiphertextInner {
+    fn from(value: integer::RadixCiphertextBig) -> Self {
+        IntegerRadixCiphertextInner::Big(value)
+    }
+}
+
+impl From<integer::RadixCiphertextSmall> for IntegerRadixCiphertextInner {
+    fn from(value: integer::RadixCiphertextSmall) -> Self {
+        IntegerRadixCiphertextInner::Small(value)
+    }
+}
+
+pub struct IntegerRadixCiphertext(pub(in crate::c_api) IntegerRadixCiphertextInner);
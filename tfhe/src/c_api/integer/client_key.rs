@@ -0,0 +1,74 @@
+use crate::c_api::utils::*;
+use std::os::raw::c_int;
+
+use crate::integer;
+
+use super::{IntegerRadixCiphertext, IntegerRadixCiphertextInner};
+
+pub struct IntegerRadixClientKey(pub(in crate::c_api) integer::RadixClientKey);
+
+#[no_mangle]
+pub unsafe extern "C" fn integer_radix_client_key_encrypt(
+    client_key: *const IntegerRadixClientKey,
+    value_to_encrypt: u64,
+    result: *mut *mut IntegerRadixCiphertext,
+) -> c_int {
+    catch_panic(|| {
+        check_ptr_is_non_null_and_aligned(result).unwrap();
+
+        // First fill the result with a null ptr so that if we fail and the return code is not
+        // checked, then any access to the result pointer will segfault (mimics malloc on failure)
+        *result = std::ptr::null_mut();
+
+        let client_key = get_ref_checked(client_key).unwrap();
+
+        let heap_allocated_ciphertext = Box::new(IntegerRadixCiphertext(
+            client_key.0.encrypt(value_to_encrypt).into(),
+        ));
+
+        *result = Box::into_raw(heap_allocated_ciphertext);
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn integer_radix_client_key_encrypt_small(
+    client_key: *const IntegerRadixClientKey,
+    value_to_encrypt: u64,
+    result: *mut *mut IntegerRadixCiphertext,
+) -> c_int {
+    catch_panic(|| {
+        check_ptr_is_non_null_and_aligned(result).unwrap();
+
+        // First fill the result with a null ptr so that if we fail and the return code is not
+        // checked, then any access to the result pointer will segfault (mimics malloc on failure)
+        *result = std::ptr::null_mut();
+
+        let client_key = get_ref_checked(client_key).unwrap();
+
+        let heap_allocated_ciphertext = Box::new(IntegerRadixCiphertext(
+            client_key.0.encrypt_small(value_to_encrypt).into(),
+        ));
+
+        *result = Box::into_raw(heap_allocated_ciphertext);
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn integer_radix_client_key_decrypt(
+    client_key: *const IntegerRadixClientKey,
+    ciphertext_to_decrypt: *const IntegerRadixCiphertext,
+    result: *mut u64,
+) -> c_int {
+    catch_panic(|| {
+        check_ptr_is_non_null_and_aligned(result).unwrap();
+
+        let client_key = get_ref_checked(client_key).unwrap();
+        let ciphertext_to_decrypt = get_ref_checked(ciphertext_to_decrypt).unwrap();
+        let inner = &ciphertext_to_decrypt.0;
+
+        *result = match inner {
+            IntegerRadixCiphertextInner::Big(inner_ct) => client_key.0.decrypt(inner_ct),
+            IntegerRadixCiphertextInner::Small(inner_ct) => client_key.0.decrypt(inner_ct),
+        };
+    })
+}
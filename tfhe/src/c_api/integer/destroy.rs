@@ -0,0 +1,35 @@
+use crate::c_api::utils::*;
+use std::os::raw::c_int;
+
+use super::{IntegerRadixCiphertext, IntegerRadixClientKey, IntegerServerKey};
+
+#[no_mangle]
+pub unsafe extern "C" fn destroy_integer_radix_client_key(
+    client_key: *mut IntegerRadixClientKey,
+) -> c_int {
+    catch_panic(|| {
+        check_ptr_is_non_null_and_aligned(client_key).unwrap();
+
+        drop(Box::from_raw(client_key));
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn destroy_integer_server_key(server_key: *mut IntegerServerKey) -> c_int {
+    catch_panic(|| {
+        check_ptr_is_non_null_and_aligned(server_key).unwrap();
+
+        drop(Box::from_raw(server_key));
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn destroy_integer_radix_ciphertext(
+    radix_ciphertext: *mut IntegerRadixCiphertext,
+) -> c_int {
+    catch_panic(|| {
+        check_ptr_is_non_null_and_aligned(radix_ciphertext).unwrap();
+
+        drop(Box::from_raw(radix_ciphertext));
+    })
+}
@@ -0,0 +1,42 @@
+pub mod ciphertext;
+pub mod client_key;
+pub mod destroy;
+pub mod server_key;
+
+use crate::c_api::utils::*;
+use std::os::raw::c_int;
+
+use crate::integer;
+
+pub use ciphertext::IntegerRadixCiphertext;
+pub(in crate::c_api) use ciphertext::IntegerRadixCiphertextInner;
+pub use client_key::IntegerRadixClientKey;
+pub use server_key::IntegerServerKey;
+
+#[no_mangle]
+pub unsafe extern "C" fn integer_gen_keys_radix(
+    shortint_parameters: *const crate::c_api::shortint::parameters::ShortintParameters,
+    num_blocks: usize,
+    result_client_key: *mut *mut IntegerRadixClientKey,
+    result_server_key: *mut *mut IntegerServerKey,
+) -> c_int {
+    catch_panic(|| {
+        check_ptr_is_non_null_and_aligned(result_client_key).unwrap();
+        check_ptr_is_non_null_and_aligned(result_server_key).unwrap();
+
+        // First fill the result with a null ptr so that if we fail and the return code is not
+        // checked, then any access to the result pointer will segfault (mimics malloc on failure)
+        *result_client_key = std::ptr::null_mut();
+        *result_server_key = std::ptr::null_mut();
+
+        let shortint_parameters = get_ref_checked(shortint_parameters).unwrap();
+
+        let (client_key, server_key) = integer::gen_keys_radix(shortint_parameters.0, num_blocks);
+
+        let heap_allocated_client_key = Box::new(IntegerRadixClientKey(client_key));
+        let heap_allocated_server_key = Box::new(IntegerServerKey(server_key));
+
+        *result_client_key = Box::into_raw(heap_allocated_client_key);
+        *result_server_key = Box::into_raw(heap_allocated_server_key);
+    })
+}
@@ -0,0 +1,102 @@
+use crate::c_api::utils::*;
+use std::os::raw::c_int;
+
+use super::super::{IntegerRadixCiphertext, IntegerRadixCiphertextInner};
+use super::{
+    dispatch_binary_assign_radix_server_key_call, dispatch_binary_radix_server_key_call,
+    IntegerServerKey,
+};
+
+#[no_mangle]
+pub unsafe extern "C" fn integer_server_key_smart_add_parallelized(
+    server_key: *const IntegerServerKey,
+    ct_left: *mut IntegerRadixCiphertext,
+    ct_right: *mut IntegerRadixCiphertext,
+    result: *mut *mut IntegerRadixCiphertext,
+) -> c_int {
+    catch_panic(|| {
+        check_ptr_is_non_null_and_aligned(result).unwrap();
+
+        let server_key = get_ref_checked(server_key).unwrap();
+        let ct_left = get_mut_checked(ct_left).unwrap();
+        let ct_right = get_mut_checked(ct_right).unwrap();
+
+        let res = dispatch_binary_radix_server_key_call!(
+            server_key,
+            smart_add_parallelized,
+            &mut ct_left,
+            &mut ct_right
+        );
+
+        let heap_allocated_ct_result = Box::new(IntegerRadixCiphertext(res));
+
+        *result = Box::into_raw(heap_allocated_ct_result);
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn integer_server_key_add_parallelized(
+    server_key: *const IntegerServerKey,
+    ct_left: *const IntegerRadixCiphertext,
+    ct_right: *const IntegerRadixCiphertext,
+    result: *mut *mut IntegerRadixCiphertext,
+) -> c_int {
+    catch_panic(|| {
+        check_ptr_is_non_null_and_aligned(result).unwrap();
+
+        let server_key = get_ref_checked(server_key).unwrap();
+        let ct_left = get_ref_checked(ct_left).unwrap();
+        let ct_right = get_ref_checked(ct_right).unwrap();
+
+        let res = dispatch_binary_radix_server_key_call!(
+            server_key,
+            add_parallelized,
+            &ct_left,
+            &ct_right
+        );
+
+        let heap_allocated_ct_result = Box::new(IntegerRadixCiphertext(res));
+
+        *result = Box::into_raw(heap_allocated_ct_result);
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn integer_server_key_smart_add_assign_parallelized(
+    server_key: *const IntegerServerKey,
+    ct_left_and_result: *mut IntegerRadixCiphertext,
+    ct_right: *mut IntegerRadixCiphertext,
+) -> c_int {
+    catch_panic(|| {
+        let server_key = get_ref_checked(server_key).unwrap();
+        let ct_left_and_result = get_mut_checked(ct_left_and_result).unwrap();
+        let ct_right = get_mut_checked(ct_right).unwrap();
+
+        dispatch_binary_assign_radix_server_key_call!(
+            server_key,
+            smart_add_assign_parallelized,
+            &mut ct_left_and_result,
+            &mut ct_right
+        );
+    })
+}
+
+#[no_mangle]
+pub unsafe extern "C" fn integer_server_key_add_assign_parallelized(
+    server_key: *const IntegerServerKey,
+    ct_left_and_result: *mut IntegerRadixCiphertext,
+    ct_right: *const IntegerRadixCiphertext,
+) -> c_int {
+    catch_panic(|| {
+        let server_key = get_ref_checked(server_key).unwrap();
+        let ct_left_and_result = get_mut_checked(ct_left_and_result).unwrap();
+        let ct_right = get_ref_checked(ct_right).unwrap();
+
+        dispatch_binary_assign_radix_server_key_call!(
+            server_key,
+            add_assign_parallelized,
+            &mut ct_left_and_result,
+            &ct_right
+        );
+    })
+}
@@ -0,0 +1,83 @@
+use crate::integer;
+
+pub mod add;
+
+pub struct IntegerServerKey(pub(in crate::c_api) integer::ServerKey);
+
+macro_rules! dispatch_binary_radix_server_key_call {
+    ($server_key:ident, $method:tt, &mut $ct_left:ident, &mut $ct_right:ident) => {
+        match (&mut $ct_left.0, &mut $ct_right.0) {
+            (
+                IntegerRadixCiphertextInner::Big(inner_left),
+                IntegerRadixCiphertextInner::Big(inner_right),
+            ) => IntegerRadixCiphertextInner::Big($server_key.0.$method(inner_left, inner_right)),
+            (
+                IntegerRadixCiphertextInner::Small(inner_left),
+                IntegerRadixCiphertextInner::Small(inner_right),
+            ) => IntegerRadixCiphertextInner::Small($server_key.0.$method(inner_left, inner_right)),
+            _ => Err(
+                "Got mixed Big and Small ciphertexts, this is not supported, \
+            did you mistakenly use a Small ciphertext with a Big ciphertext?",
+            )
+            .unwrap(),
+        }
+    };
+    ($server_key:ident, $method:tt, &$ct_left:ident, &$ct_right:ident) => {
+        match (&$ct_left.0, &$ct_right.0) {
+            (
+                IntegerRadixCiphertextInner::Big(inner_left),
+                IntegerRadixCiphertextInner::Big(inner_right),
+            ) => IntegerRadixCiphertextInner::Big($server_key.0.$method(inner_left, inner_right)),
+            (
+                IntegerRadixCiphertextInner::Small(inner_left),
+                IntegerRadixCiphertextInner::Small(inner_right),
+            ) => IntegerRadixCiphertextInner::Small($server_key.0.$method(inner_left, inner_right)),
+            _ => Err(
+                "Got mixed Big and Small ciphertexts, this is not supported, \
+            did you mistakenly use a Small ciphertext with a Big ciphertext?",
+            )
+            .unwrap(),
+        }
+    };
+}
+
+pub(in crate::c_api) use dispatch_binary_radix_server_key_call;
+
+macro_rules! dispatch_binary_assign_radix_server_key_call {
+    ($server_key:ident, $method:tt, &mut $ct_left_and_result:ident, &mut $ct_right:ident) => {
+        match (&mut $ct_left_and_result.0, &mut $ct_right.0) {
+            (
+                IntegerRadixCiphertextInner::Big(inner_left),
+                IntegerRadixCiphertextInner::Big(inner_right),
+            ) => $server_key.0.$method(inner_left, inner_right),
+            (
+                IntegerRadixCiphertextInner::Small(inner_left),
+                IntegerRadixCiphertextInner::Small(inner_right),
+            ) => $server_key.0.$method(inner_left, inner_right),
+            _ => Err(
+                "Got mixed Big and Small ciphertexts, this is not supported, \
+            did you mistakenly use a Small ciphertext with a Big ciphertext?",
+            )
+            .unwrap(),
+        }
+    };
+    ($server_key:ident, $method:tt, &mut $ct_left_and_result:ident, &$ct_right:ident) => {
+        match (&mut $ct_left_and_result.0, &$ct_right.0) {
+            (
+                IntegerRadixCiphertextInner::Big(inner_left),
+                IntegerRadixCiphertextInner::Big(inner_right),
+            ) => $server_key.0.$method(inner_left, inner_right),
+            (
+                IntegerRadixCiphertextInner::Small(inner_left),
+                IntegerRadixCiphertextInner::Small(inner_right),
+            ) => $server_key.0.$method(inner_left, inner_right),
+            _ => Err(
+                "Got mixed Big and Small ciphertexts, this is not supported, \
+            did you mistakenly use a Small ciphertext with a Big ciphertext?",
+            )
+            .unwrap(),
+        }
+    };
+}
+
+pub(in crate::c_api) use dispatch_binary_assign_radix_server_key_call;
@@ -2,8 +2,11 @@
 #[cfg(feature = "boolean-c-api")]
 pub mod boolean;
 pub mod buffer;
+pub mod error;
 #[cfg(feature = "high-level-c-api")]
 pub mod high_level_api;
+#[cfg(feature = "integer-c-api")]
+pub mod integer;
 #[cfg(feature = "shortint-c-api")]
 pub mod shortint;
 pub(crate) mod utils;
@@ -1,12 +1,31 @@
+use crate::c_api::error::{clear_last_error_message, set_last_error_message};
 use std::os::raw::c_int;
 
+/// Runs the closure, returning a non-zero value if it panicked.
+///
+/// The panic message is kept as the last error message of the current thread, see
+/// [`tfhe_last_error_message`](crate::c_api::error::tfhe_last_error_message), and is cleared
+/// if the closure succeeds.
 pub fn catch_panic<F>(closure: F) -> c_int
 where
     F: FnOnce(),
 {
     match std::panic::catch_unwind(std::panic::AssertUnwindSafe(closure)) {
-        Ok(_) => 0,
-        _ => 1,
+        Ok(_) => {
+            clear_last_error_message();
+            0
+        }
+        Err(payload) => {
+            let message = if let Some(message) = payload.downcast_ref::<&str>() {
+                (*message).to_owned()
+            } else if let Some(message) = payload.downcast_ref::<String>() {
+                message.clone()
+            } else {
+                "unknown error".to_owned()
+            };
+            set_last_error_message(message);
+            1
+        }
     }
 }
 
@@ -1,4 +1,6 @@
 //! This module implements the ciphertext structures.
+use crate::integer::encryption::AsLittleEndianWords;
+use crate::integer::ServerKey;
 use crate::shortint::ciphertext::{BootstrapKeyswitch, KeyswitchBootstrap};
 use crate::shortint::{
     CiphertextBase, CiphertextBig, CiphertextSmall, CompressedCiphertextBig,
@@ -34,6 +36,39 @@ impl<PBSOrder: PBSOrderMarker> RadixCiphertext<PBSOrder> {
     pub fn block_carries_are_empty(&self) -> bool {
         self.blocks.iter().all(|block| block.carry_is_empty())
     }
+
+    /// Create a trivial radix ciphertext, same as [`ServerKey::create_trivial_radix`]
+    ///
+    /// Trivial means that the value is not encrypted: anyone holding the ciphertext can read the
+    /// value from it. Trivial ciphertexts are only meant for values that are public anyway, such
+    /// as constants or the identity element of a reduction.
+    ///
+    /// The value is reduced modulo the modulus of the ciphertext, i.e. the message modulus to
+    /// the power `num_blocks`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::{gen_keys_radix, RadixCiphertextBig};
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// let num_blocks = 4;
+    ///
+    /// // Generate the client key and the server key:
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, num_blocks);
+    ///
+    /// let ctxt = RadixCiphertextBig::trivial(212u64, num_blocks, &sks);
+    ///
+    /// // Decrypt:
+    /// let dec: u64 = cks.decrypt(&ctxt);
+    /// assert_eq!(212, dec);
+    /// ```
+    pub fn trivial<T>(value: T, num_blocks: usize, server_key: &ServerKey) -> Self
+    where
+        T: AsLittleEndianWords,
+    {
+        server_key.create_trivial_radix(value, num_blocks)
+    }
 }
 
 impl From<CompressedRadixCiphertextBig> for RadixCiphertextBig {
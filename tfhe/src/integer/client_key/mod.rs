@@ -22,7 +22,7 @@ use serde::{Deserialize, Serialize};
 pub use utils::radix_decomposition;
 
 pub use crt::CrtClientKey;
-pub use radix::RadixClientKey;
+pub use radix::{DecryptionError, RadixClientKey};
 
 use super::ciphertext::RadixCiphertext;
 use super::CompressedRadixCiphertextSmall;
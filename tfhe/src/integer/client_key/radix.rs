@@ -10,6 +10,26 @@ use crate::shortint::{
 };
 
 use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+/// Error returned when a decrypted value is not the one expected.
+#[derive(Debug, PartialEq, Eq)]
+pub enum DecryptionError {
+    /// The decrypted value is neither 0 nor 1
+    NotABoolean(u64),
+}
+
+impl Display for DecryptionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecryptionError::NotABoolean(value) => {
+                write!(f, "The decrypted value {value} is not a boolean")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecryptionError {}
 
 /// Client key "specialized" for radix decomposition.
 ///
@@ -70,6 +90,113 @@ impl RadixClientKey {
         self.key.decrypt_radix(ciphertext)
     }
 
+    /// Encrypts a signed integer in two's complement over the width of the key.
+    ///
+    /// The width is `num_blocks * log2(message_modulus)` bits, so the representable range is
+    /// `-2^(width - 1)..=2^(width - 1) - 1`, e.g. `-128..=127` for 4 blocks of 2 bits of
+    /// message.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the width is greater than 64 bits or if the value is not representable.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::RadixClientKey;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // 2 * 4 = 8 bits of message
+    /// let num_block = 4;
+    /// let cks = RadixClientKey::new(PARAM_MESSAGE_2_CARRY_2, num_block);
+    ///
+    /// let msg = -100_i64;
+    ///
+    /// let ct = cks.encrypt_signed(msg);
+    ///
+    /// // The ciphertext encrypts the two's complement representation
+    /// let dec: u64 = cks.decrypt(&ct);
+    /// assert_eq!(dec, 156);
+    ///
+    /// let dec = cks.decrypt_signed(&ct);
+    /// assert_eq!(dec, msg);
+    /// ```
+    pub fn encrypt_signed(&self, message: i64) -> RadixCiphertextBig {
+        let num_bits = self.signed_num_bits();
+        let min = i64::MIN >> (64 - num_bits);
+        let max = i64::MAX >> (64 - num_bits);
+        assert!(
+            (min..=max).contains(&message),
+            "{message} is not representable on {num_bits} bits, the range is {min}..={max}"
+        );
+        let mask = u64::MAX >> (64 - num_bits);
+        self.encrypt(message as u64 & mask)
+    }
+
+    /// Decrypts a ciphertext encrypting a signed integer in two's complement over the width of
+    /// the key, as encrypted by [`Self::encrypt_signed`].
+    ///
+    /// The most significant bit of the width is the sign bit, and is extended to the 64 bits of
+    /// the result.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the width is greater than 64 bits.
+    pub fn decrypt_signed<PBSOrder: PBSOrderMarker>(
+        &self,
+        ciphertext: &RadixCiphertext<PBSOrder>,
+    ) -> i64 {
+        let num_bits = self.signed_num_bits();
+        let value: u64 = self.decrypt(ciphertext);
+        ((value << (64 - num_bits)) as i64) >> (64 - num_bits)
+    }
+
+    fn signed_num_bits(&self) -> u32 {
+        let num_bits = self.parameters().message_modulus.0.ilog2() * self.num_blocks as u32;
+        assert!(
+            (1..=64).contains(&num_bits),
+            "signed integers must be between 1 and 64 bits, got {num_bits} bits"
+        );
+        num_bits
+    }
+
+    /// Decrypts a ciphertext encrypting a boolean, such as the result of a comparison.
+    ///
+    /// Returns an error if the decrypted value is neither 0 nor 1.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::integer::DecryptionError;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// let num_blocks = 4;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, num_blocks);
+    ///
+    /// let ct1 = cks.encrypt(14u64);
+    /// let ct2 = cks.encrypt(97u64);
+    ///
+    /// let ct_res = sks.lt_parallelized(&ct1, &ct2);
+    /// assert_eq!(cks.decrypt_bool(&ct_res), Ok(true));
+    ///
+    /// let ct_res = sks.gt_parallelized(&ct1, &ct2);
+    /// assert_eq!(cks.decrypt_bool(&ct_res), Ok(false));
+    ///
+    /// let ct = cks.encrypt(2u64);
+    /// assert_eq!(cks.decrypt_bool(&ct), Err(DecryptionError::NotABoolean(2)));
+    /// ```
+    pub fn decrypt_bool<PBSOrder: PBSOrderMarker>(
+        &self,
+        ciphertext: &RadixCiphertext<PBSOrder>,
+    ) -> Result<bool, DecryptionError> {
+        match self.decrypt::<u64, _>(ciphertext) {
+            0 => Ok(false),
+            1 => Ok(true),
+            value => Err(DecryptionError::NotABoolean(value)),
+        }
+    }
+
     /// Returns the parameters used by the client key.
     pub fn parameters(&self) -> ShortintParameters {
         self.key.parameters()
@@ -66,11 +66,11 @@ pub use ciphertext::{
     CompressedRadixCiphertextBig, CompressedRadixCiphertextSmall, CrtCiphertext, IntegerCiphertext,
     RadixCiphertextBig, RadixCiphertextSmall,
 };
-pub use client_key::{ClientKey, CrtClientKey, RadixClientKey};
+pub use client_key::{ClientKey, CrtClientKey, DecryptionError, RadixClientKey};
 pub use public_key::{
     CompressedPublicKeyBig, CompressedPublicKeySmall, PublicKeyBig, PublicKeySmall,
 };
-pub use server_key::{CheckError, CompressedServerKey, ServerKey};
+pub use server_key::{CheckError, CompressedServerKey, ServerKey, WithServerKey};
 pub use u256::U256;
 
 /// Generate a couple of client and server keys with given parameters
@@ -170,6 +170,68 @@ where
     (RadixClientKey::from((cks, num_blocks)), sks)
 }
 
+/// Generate a client key and a compressed server key with given parameters
+///
+/// The [CompressedServerKey] is much smaller than a [ServerKey], which makes it cheaper to
+/// send to the server. It cannot be used for homomorphic operations as is, the server has to
+/// decompress it first, by converting it into a [ServerKey].
+///
+/// ```rust
+/// use tfhe::integer::{gen_keys_compressed, ServerKey};
+/// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+///
+/// // generate the client key and the compressed server key:
+/// let (cks, compressed_sks) = gen_keys_compressed(PARAM_MESSAGE_2_CARRY_2);
+///
+/// // on the server side, decompress the server key before using it:
+/// let sks = ServerKey::from(compressed_sks);
+/// ```
+pub fn gen_keys_compressed<P>(parameters_set: P) -> (ClientKey, CompressedServerKey)
+where
+    P: TryInto<crate::shortint::parameters::ShortintParameterSet>,
+    <P as TryInto<crate::shortint::parameters::ShortintParameterSet>>::Error: std::fmt::Debug,
+{
+    let cks = ClientKey::new(parameters_set);
+    let compressed_sks = CompressedServerKey::new(&cks);
+
+    (cks, compressed_sks)
+}
+
+/// Generate a client key and a compressed server key with given parameters
+///
+/// Contrary to [gen_keys_compressed], this returns a [RadixClientKey]
+///
+/// ```rust
+/// use tfhe::integer::{gen_keys_radix_compressed, ServerKey};
+/// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+///
+/// // generate the client key and the compressed server key:
+/// let num_blocks = 4;
+/// let (cks, compressed_sks) = gen_keys_radix_compressed(PARAM_MESSAGE_2_CARRY_2, num_blocks);
+///
+/// // on the server side, decompress the server key before using it:
+/// let sks = ServerKey::from(compressed_sks);
+///
+/// let ct1 = cks.encrypt(14u64);
+/// let ct2 = cks.encrypt(97u64);
+/// let ct_res = sks.add_parallelized(&ct1, &ct2);
+///
+/// let res: u64 = cks.decrypt(&ct_res);
+/// assert_eq!(res, 111);
+/// ```
+pub fn gen_keys_radix_compressed<P>(
+    parameters_set: P,
+    num_blocks: usize,
+) -> (RadixClientKey, CompressedServerKey)
+where
+    P: TryInto<crate::shortint::parameters::ShortintParameterSet>,
+    <P as TryInto<crate::shortint::parameters::ShortintParameterSet>>::Error: std::fmt::Debug,
+{
+    let (cks, compressed_sks) = gen_keys_compressed(parameters_set);
+
+    (RadixClientKey::from((cks, num_blocks)), compressed_sks)
+}
+
 /// Generate a couple of client and server keys with given parameters
 ///
 /// Contrary to [gen_keys], this returns a [CrtClientKey]
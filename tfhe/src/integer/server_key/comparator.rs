@@ -1164,9 +1164,13 @@ mod tests {
     ///
     /// This calls the `unchecked_comparator_method` with fresh ciphertexts
     /// and compares that it gives the same results as the `clear_fn`.
+    ///
+    /// When `result_is_boolean` is true, the result is also checked to be a
+    /// well-formed boolean.
     fn test_unchecked_function<UncheckedFn, ClearF>(
         param: PBSParameters,
         num_test: usize,
+        result_is_boolean: bool,
         unchecked_comparator_method: UncheckedFn,
         clear_fn: ClearF,
     ) where
@@ -1192,6 +1196,9 @@ mod tests {
             let b = cks.encrypt_radix(clear_b, num_block);
 
             let result = unchecked_comparator_method(&comparator, &a, &b);
+            if result_is_boolean {
+                sks.debug_assert_is_boolean(&result, &cks);
+            }
             let mut decrypted = U256::default();
             cks.decrypt_radix_into(&result, &mut decrypted);
 
@@ -1209,6 +1216,7 @@ mod tests {
     fn test_smart_function<SmartFn, ClearF>(
         param: PBSParameters,
         num_test: usize,
+        result_is_boolean: bool,
         smart_comparator_method: SmartFn,
         clear_fn: ClearF,
     ) where
@@ -1259,6 +1267,9 @@ mod tests {
             assert!(super::has_non_zero_carries(&ct_0));
             assert!(super::has_non_zero_carries(&ct_1));
             let encrypted_result = smart_comparator_method(&comparator, &mut ct_0, &mut ct_1);
+            if result_is_boolean {
+                sks.debug_assert_is_boolean(&encrypted_result, &cks);
+            }
             assert!(!super::has_non_zero_carries(&ct_0));
             assert!(!super::has_non_zero_carries(&ct_1));
 
@@ -1287,6 +1298,7 @@ mod tests {
     fn test_default_function<SmartFn, ClearF>(
         param: PBSParameters,
         num_test: usize,
+        result_is_boolean: bool,
         default_comparator_method: SmartFn,
         clear_fn: ClearF,
     ) where
@@ -1337,6 +1349,9 @@ mod tests {
             assert!(super::has_non_zero_carries(&ct_0));
             assert!(super::has_non_zero_carries(&ct_1));
             let encrypted_result = default_comparator_method(&comparator, &ct_0, &ct_1);
+            if result_is_boolean {
+                sks.debug_assert_is_boolean(&encrypted_result, &cks);
+            }
             assert!(!super::has_non_zero_carries(&encrypted_result));
 
             // Sanity decryption checks
@@ -1360,6 +1375,7 @@ mod tests {
         test_unchecked_function(
             params,
             num_tests,
+            false,
             |comparator, lhs, rhs| comparator.unchecked_min(lhs, rhs),
             std::cmp::min,
         )
@@ -1369,6 +1385,7 @@ mod tests {
         test_unchecked_function(
             params,
             num_tests,
+            false,
             |comparator, lhs, rhs| comparator.unchecked_max(lhs, rhs),
             std::cmp::max,
         )
@@ -1381,6 +1398,7 @@ mod tests {
         test_unchecked_function(
             params,
             num_tests,
+            false,
             |comparator, lhs, rhs| comparator.unchecked_min_parallelized(lhs, rhs),
             std::cmp::min,
         )
@@ -1393,6 +1411,7 @@ mod tests {
         test_unchecked_function(
             params,
             num_tests,
+            false,
             |comparator, lhs, rhs| comparator.unchecked_max_parallelized(lhs, rhs),
             std::cmp::max,
         )
@@ -1402,6 +1421,7 @@ mod tests {
         test_default_function(
             params,
             num_tests,
+            false,
             |comparator, lhs, rhs| comparator.min_parallelized(lhs, rhs),
             std::cmp::min,
         )
@@ -1411,6 +1431,7 @@ mod tests {
         test_default_function(
             params,
             num_tests,
+            false,
             |comparator, lhs, rhs| comparator.max_parallelized(lhs, rhs),
             std::cmp::max,
         )
@@ -1448,6 +1469,7 @@ mod tests {
                     test_unchecked_function(
                         params,
                         num_tests,
+                        true,
                         |comparator, lhs, rhs| comparator.[<unchecked_ $comparison_name>](lhs, rhs),
                         |lhs, rhs| U256::from(<U256>::$comparison_name(&lhs, &rhs) as u128),
                     )
@@ -1458,6 +1480,7 @@ mod tests {
                     test_unchecked_function(
                         params,
                         num_tests,
+                        true,
                         |comparator, lhs, rhs| comparator.[<unchecked_ $comparison_name _parallelized>](lhs, rhs),
                         |lhs, rhs| U256::from(<U256>::$comparison_name(&lhs, &rhs) as u128),
                     )
@@ -1468,6 +1491,7 @@ mod tests {
                     test_smart_function(
                         params,
                         num_tests,
+                        true,
                         |comparator, lhs, rhs| comparator.[<smart_ $comparison_name>](lhs, rhs),
                         |lhs, rhs| U256::from(<U256>::$comparison_name(&lhs, &rhs) as u128),
                     )
@@ -1478,6 +1502,7 @@ mod tests {
                     test_smart_function(
                         params,
                         num_tests,
+                        true,
                         |comparator, lhs, rhs| comparator.[<smart_ $comparison_name _parallelized>](lhs, rhs),
                         |lhs, rhs| U256::from(<U256>::$comparison_name(&lhs, &rhs) as u128),
                     )
@@ -1488,6 +1513,7 @@ mod tests {
                     test_default_function(
                         params,
                         num_tests,
+                        true,
                         |comparator, lhs, rhs| comparator.[<$comparison_name _parallelized>](lhs, rhs),
                         |lhs, rhs| U256::from(<U256>::$comparison_name(&lhs, &rhs) as u128),
                     )
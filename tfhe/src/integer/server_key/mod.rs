@@ -8,8 +8,14 @@ mod crt_parallel;
 mod radix;
 mod radix_parallel;
 
+pub use radix_parallel::WithServerKey;
+
+#[cfg(any(test, feature = "debug-decrypt"))]
+use crate::integer::ciphertext::RadixCiphertext;
 use crate::integer::client_key::ClientKey;
 use crate::shortint::server_key::MaxDegree;
+#[cfg(any(test, feature = "debug-decrypt"))]
+use crate::shortint::PBSOrderMarker;
 use serde::{Deserialize, Serialize};
 
 /// Error returned when the carry buffer is full.
@@ -92,6 +98,42 @@ impl ServerKey {
     }
 }
 
+#[cfg(any(test, feature = "debug-decrypt"))]
+impl ServerKey {
+    /// Asserts that the ciphertext encrypts a well-formed boolean, by decrypting it.
+    ///
+    /// Boolean results, such as the ones of comparisons, must encrypt 0 or 1 in their first
+    /// block, and 0 in all the other blocks, with empty carries. This checks the message and
+    /// carry of each block, so that a result polluted by leftover carries is caught even if
+    /// its message alone would look like a boolean.
+    ///
+    /// Like `debug_assert!`, the check is only done in builds with debug assertions enabled.
+    /// As it needs the client key, it is only meant for tests and debugging, and is only
+    /// available with the `debug-decrypt` feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ciphertext does not encrypt a well-formed boolean.
+    pub fn debug_assert_is_boolean<PBSOrder, C>(&self, ct: &RadixCiphertext<PBSOrder>, cks: C)
+    where
+        PBSOrder: PBSOrderMarker,
+        C: AsRef<ClientKey>,
+    {
+        if !cfg!(debug_assertions) {
+            return;
+        }
+        let cks = cks.as_ref();
+        for (i, block) in ct.blocks.iter().enumerate() {
+            let value = cks.key.decrypt_message_and_carry(block);
+            let max_value = u64::from(i == 0);
+            assert!(
+                value <= max_value,
+                "expected a boolean, but block {i} decrypts to {value} (message and carry)"
+            );
+        }
+    }
+}
+
 #[derive(Clone, Serialize, Deserialize)]
 pub struct CompressedServerKey {
     pub(crate) key: crate::shortint::CompressedServerKey,
@@ -99,7 +141,12 @@ pub struct CompressedServerKey {
 
 impl CompressedServerKey {
     pub fn new(client_key: &ClientKey) -> CompressedServerKey {
-        let key = crate::shortint::CompressedServerKey::new(&client_key.key);
+        let mut key = crate::shortint::CompressedServerKey::new(&client_key.key);
+        // Same max degree as ServerKey::new, so that the decompressed key behaves the same
+        let max = (client_key.key.parameters.message_modulus().0 - 1)
+            * client_key.key.parameters.carry_modulus().0
+            - 1;
+        key.max_degree = MaxDegree(max);
         Self { key }
     }
 }
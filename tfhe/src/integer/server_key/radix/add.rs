@@ -51,6 +51,20 @@ impl ServerKey {
     /// ciphertext.
     ///
     /// The result is assigned to the `ct_left` ciphertext.
+    ///
+    /// The blocks are added pairwise, without any carry propagation: the carries stay in the
+    /// carry space of the blocks. This allows batching many additions, and propagating the
+    /// carries once at the end with [`Self::full_propagate`] or
+    /// [`Self::full_propagate_parallelized`].
+    ///
+    /// The carry space bounds how many additions can be batched: the degree of each block grows
+    /// by the degree of the added block, and must stay below the maximum degree of the key.
+    /// E.g. with `PARAM_MESSAGE_2_CARRY_2`, the maximum degree is 11 and a fresh block has a
+    /// degree of 3, so at most 3 fresh ciphertexts can be summed before propagating. Use
+    /// [`Self::is_add_possible`] to check it beforehand, as exceeding it gives wrong results.
+    ///
+    /// # Example
+    ///
     /// ```rust
     /// use tfhe::integer::gen_keys_radix;
     /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
@@ -70,6 +84,16 @@ impl ServerKey {
     /// // Decrypt:
     /// let dec_ct1: u64 = cks.decrypt(&ct1);
     /// assert_eq!(dec_ct1, msg1 + msg2);
+    ///
+    /// // Batch another addition, and propagate the carries once
+    /// let msg3 = 33;
+    /// let ct3 = cks.encrypt(msg3);
+    /// assert!(sks.is_add_possible(&ct1, &ct3));
+    /// sks.unchecked_add_assign(&mut ct1, &ct3);
+    /// sks.full_propagate(&mut ct1);
+    ///
+    /// let dec_ct1: u64 = cks.decrypt(&ct1);
+    /// assert_eq!(dec_ct1, msg1 + msg2 + msg3);
     /// ```
     pub fn unchecked_add_assign<PBSOrder: PBSOrderMarker>(
         &self,
@@ -117,6 +141,52 @@ impl ServerKey {
         true
     }
 
+    /// Returns how many fresh ciphertexts can be added to `ct` with
+    /// [`Self::unchecked_add_assign`], one after the other, before its carries must be
+    /// propagated.
+    ///
+    /// The model is the one of the library's own checks ([`Self::is_add_possible`]): each block
+    /// tracks a degree, the largest value it may hold, and the degree must not exceed the
+    /// maximum degree of the key, which leaves one bit of carry space unused so that a
+    /// propagation can still take place. The parameters are chosen so that any sum within
+    /// this budget is also safe noise-wise, so the noise does not need to be tracked on its
+    /// own. Adding a fresh ciphertext, whose blocks have a degree of `message_modulus - 1`,
+    /// increases the degree of each block by that much, hence the result is
+    /// `(max_degree - degree) / (message_modulus - 1)` for the block with the largest degree.
+    ///
+    /// The result does not depend on the encrypted values, only on the degrees, and is 0 if
+    /// `ct` has no blocks.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// let num_blocks = 4;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, num_blocks);
+    ///
+    /// let mut ct = cks.encrypt(12u64);
+    ///
+    /// // The maximum degree is 11 and a fresh block has a degree of 3
+    /// assert_eq!(sks.max_noise_safe_chain_length(&ct), 2);
+    ///
+    /// let ct2 = cks.encrypt(34u64);
+    /// sks.unchecked_add_assign(&mut ct, &ct2);
+    /// assert_eq!(sks.max_noise_safe_chain_length(&ct), 1);
+    /// ```
+    pub fn max_noise_safe_chain_length<PBSOrder: PBSOrderMarker>(
+        &self,
+        ct: &RadixCiphertext<PBSOrder>,
+    ) -> usize {
+        let fresh_degree = self.key.message_modulus.0 - 1;
+        ct.blocks
+            .iter()
+            .map(|block| self.key.max_degree.0.saturating_sub(block.degree.0) / fresh_degree)
+            .min()
+            .unwrap_or(0)
+    }
+
     /// Computes homomorphically an addition between two ciphertexts encrypting integer values.
     ///
     /// If the operation can be performed, the result is returned in a new ciphertext.
@@ -88,6 +88,50 @@ impl ServerKey {
         )
     }
 
+    /// Create one trivial radix ciphertext per byte of a slice
+    ///
+    /// Each ciphertext has as many blocks as needed to hold a byte, i.e. `ceil(8 /
+    /// log2(message_modulus))` blocks. This allows to build on the server side a known constant,
+    /// such as a string to compare encrypted characters against, without the client key.
+    ///
+    /// Trivial means that the values are not encrypted: the constant is not secret, as anyone
+    /// holding the ciphertexts can read it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::{gen_keys_radix, RadixCiphertextBig};
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// let num_blocks = 4;
+    ///
+    /// // Generate the client key and the server key:
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, num_blocks);
+    ///
+    /// let ctxts: Vec<RadixCiphertextBig> = sks.create_trivial_radix_from_bytes(b"PK");
+    /// assert_eq!(ctxts.len(), 2);
+    ///
+    /// // Compare an encrypted byte against the known constant
+    /// let mut ct = cks.encrypt(b'P' as u64);
+    /// let mut ct_p = ctxts[0].clone();
+    /// let ct_res = sks.smart_eq(&mut ct, &mut ct_p);
+    ///
+    /// // Decrypt:
+    /// let dec: u64 = cks.decrypt(&ct_res);
+    /// assert_eq!(1, dec);
+    /// ```
+    pub fn create_trivial_radix_from_bytes<PBSOrder: PBSOrderMarker>(
+        &self,
+        bytes: &[u8],
+    ) -> Vec<RadixCiphertext<PBSOrder>> {
+        let num_bits_in_block = self.key.message_modulus.0.ilog2() as usize;
+        let num_blocks = (8 + num_bits_in_block - 1) / num_bits_in_block;
+        bytes
+            .iter()
+            .map(|byte| self.create_trivial_radix(*byte as u64, num_blocks))
+            .collect()
+    }
+
     /// Prepend trivial zero LSB blocks to an existing [`RadixCiphertext`]. This can be useful for
     /// casting operations.
     ///
@@ -1,4 +1,5 @@
 use crate::integer::keycache::KEY_CACHE;
+use crate::integer::{CompressedServerKey, RadixCiphertextBig, RadixClientKey, ServerKey};
 use crate::shortint::parameters::*;
 use crate::shortint::PBSParameters;
 use rand::Rng;
@@ -13,10 +14,16 @@ const NB_CTXT: usize = 4;
 
 create_parametrized_test!(integer_encrypt_decrypt);
 create_parametrized_test!(integer_encrypt_decrypt_128_bits);
+create_parametrized_test!(integer_encrypt_decrypt_signed);
 create_parametrized_test!(integer_encrypt_decrypt_128_bits_specific_values);
 create_parametrized_test!(integer_encrypt_decrypt_256_bits_specific_values);
 create_parametrized_test!(integer_encrypt_decrypt_256_bits);
+create_parametrized_test!(integer_trivial);
+create_parametrized_test!(integer_trivial_from_bytes);
+create_parametrized_test!(integer_compressed_server_key);
 create_parametrized_test!(integer_unchecked_add);
+create_parametrized_test!(integer_unchecked_add_sequence_then_propagate);
+create_parametrized_test!(integer_max_noise_safe_chain_length);
 create_parametrized_test!(integer_smart_add);
 create_parametrized_test! {
     integer_smart_add_128_bits {
@@ -79,6 +86,132 @@ fn integer_encrypt_decrypt(param: PBSParameters) {
     }
 }
 
+fn integer_trivial(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+
+    // RNG
+    let mut rng = rand::thread_rng();
+
+    // message_modulus^vec_length
+    let modulus = param.message_modulus.0.pow(NB_CTXT as u32) as u64;
+
+    for _ in 0..NB_TEST {
+        let clear = rng.gen::<u64>() % modulus;
+
+        let ct = RadixCiphertextBig::trivial(clear, NB_CTXT, &sks);
+        let dec: u64 = cks.decrypt_radix(&ct);
+        assert_eq!(clear, dec);
+
+        // Values above the max wrap around the modulus
+        let ct = RadixCiphertextBig::trivial(clear + modulus, NB_CTXT, &sks);
+        let dec: u64 = cks.decrypt_radix(&ct);
+        assert_eq!(clear, dec);
+    }
+}
+
+fn integer_trivial_from_bytes(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+
+    // RNG
+    let mut rng = rand::thread_rng();
+
+    let bytes = (0..NB_TEST).map(|_| rng.gen::<u8>()).collect::<Vec<_>>();
+    let ctxts: Vec<RadixCiphertextBig> = sks.create_trivial_radix_from_bytes(&bytes);
+    assert_eq!(bytes.len(), ctxts.len());
+
+    for (byte, ct) in bytes.iter().zip(ctxts.iter()) {
+        // Enough blocks to hold a byte
+        let num_blocks = ct.blocks.len();
+        assert!(param.message_modulus.0.pow(num_blocks as u32) >= 256);
+
+        let dec: u64 = cks.decrypt_radix(ct);
+        assert_eq!(*byte as u64, dec);
+
+        // Operations against the constant
+        let other = rng.gen::<u8>();
+        let mut ct_other = cks.encrypt_radix(other as u64, num_blocks);
+        let mut ct_byte = ct.clone();
+        let ct_res = sks.smart_eq(&mut ct_other, &mut ct_byte);
+        let dec: u64 = cks.decrypt_radix(&ct_res);
+        assert_eq!((other == *byte) as u64, dec);
+
+        let mut ct_same = cks.encrypt_radix(*byte as u64, num_blocks);
+        let mut ct_byte = ct.clone();
+        let ct_res = sks.smart_eq(&mut ct_same, &mut ct_byte);
+        let dec: u64 = cks.decrypt_radix(&ct_res);
+        assert_eq!(1, dec);
+
+        let mut ct_byte = ct.clone();
+        let ct_res = sks.smart_add(&mut ct_other, &mut ct_byte);
+        let dec: u64 = cks.decrypt_radix(&ct_res);
+        let modulus = param.message_modulus.0.pow(num_blocks as u32) as u64;
+        assert_eq!((other as u64 + *byte as u64) % modulus, dec);
+    }
+}
+
+fn integer_compressed_server_key(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+
+    let compressed_sks = CompressedServerKey::new(&cks);
+    let decompressed_sks = ServerKey::from(compressed_sks);
+    assert_eq!(sks.key.max_degree, decompressed_sks.key.max_degree);
+
+    // RNG
+    let mut rng = rand::thread_rng();
+
+    // message_modulus^vec_length
+    let modulus = param.message_modulus.0.pow(NB_CTXT as u32) as u64;
+
+    let clear_0 = rng.gen::<u64>() % modulus;
+    let mut ct_res = cks.encrypt_radix(clear_0, NB_CTXT);
+    let mut ct_res_decompressed = ct_res.clone();
+    let mut clear = clear_0;
+
+    // Enough additions for the carries to be propagated along the way
+    for _ in 0..NB_TEST_SMALLER {
+        let clear_1 = rng.gen::<u64>() % modulus;
+        let mut ct_1 = cks.encrypt_radix(clear_1, NB_CTXT);
+
+        ct_res = sks.smart_add(&mut ct_res, &mut ct_1);
+        ct_res_decompressed = decompressed_sks.smart_add(&mut ct_res_decompressed, &mut ct_1);
+        clear = (clear + clear_1) % modulus;
+
+        // Both keys take the same decisions, so the degrees match
+        for (block, block_decompressed) in ct_res.blocks.iter().zip(&ct_res_decompressed.blocks) {
+            assert_eq!(block.degree, block_decompressed.degree);
+        }
+
+        let dec: u64 = cks.decrypt_radix(&ct_res);
+        let dec_decompressed: u64 = cks.decrypt_radix(&ct_res_decompressed);
+        assert_eq!(clear, dec);
+        assert_eq!(clear, dec_decompressed);
+    }
+}
+
+fn integer_encrypt_decrypt_signed(param: PBSParameters) {
+    let (cks, _) = KEY_CACHE.get_from_params(param);
+    let cks = RadixClientKey::from((cks, NB_CTXT));
+
+    // RNG
+    let mut rng = rand::thread_rng();
+
+    let nb_bits = param.message_modulus.0.ilog2() * NB_CTXT as u32;
+    let min = -(1i64 << (nb_bits - 1));
+    let max = (1i64 << (nb_bits - 1)) - 1;
+
+    let random_values = (0..NB_TEST).map(|_| rng.gen_range(min..=max));
+    for clear in [min, max, -1, 0, 1].into_iter().chain(random_values) {
+        let ct = cks.encrypt_signed(clear);
+
+        // The two's complement representation fits in the width
+        let dec_unsigned: u64 = cks.decrypt(&ct);
+        assert_eq!(clear as u64 & ((1u64 << nb_bits) - 1), dec_unsigned);
+
+        let dec = cks.decrypt_signed(&ct);
+        assert_eq!(clear, dec);
+    }
+}
+
 fn integer_encrypt_decrypt_128_bits(param: PBSParameters) {
     let (cks, _) = KEY_CACHE.get_from_params(param);
 
@@ -279,6 +412,72 @@ fn integer_unchecked_add(param: PBSParameters) {
     }
 }
 
+fn integer_unchecked_add_sequence_then_propagate(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+
+    //RNG
+    let mut rng = rand::thread_rng();
+
+    // message_modulus^vec_length
+    let modulus = param.message_modulus.0.pow(NB_CTXT as u32) as u64;
+
+    for _ in 0..NB_TEST_SMALLER {
+        let mut clear = rng.gen::<u64>() % modulus;
+        let mut ct_res = cks.encrypt_radix(clear, NB_CTXT);
+
+        // Batch as many additions as the carry space allows
+        loop {
+            let clear_i = rng.gen::<u64>() % modulus;
+            let ctxt_i = cks.encrypt_radix(clear_i, NB_CTXT);
+            if !sks.is_add_possible(&ct_res, &ctxt_i) {
+                break;
+            }
+            sks.unchecked_add_assign(&mut ct_res, &ctxt_i);
+            clear = (clear + clear_i) % modulus;
+        }
+
+        sks.full_propagate(&mut ct_res);
+        assert!(ct_res.block_carries_are_empty());
+
+        let dec_res: u64 = cks.decrypt_radix(&ct_res);
+        assert_eq!(clear, dec_res);
+    }
+}
+
+fn integer_max_noise_safe_chain_length(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+
+    //RNG
+    let mut rng = rand::thread_rng();
+
+    // message_modulus^vec_length
+    let modulus = param.message_modulus.0.pow(NB_CTXT as u32) as u64;
+
+    let clear = rng.gen::<u64>() % modulus;
+    let mut ct_res = cks.encrypt_radix(clear, NB_CTXT);
+    let mut expected = clear;
+
+    // The estimate must match the point where additions stop being possible
+    let chain_length = sks.max_noise_safe_chain_length(&ct_res);
+    for i in 0..chain_length {
+        assert_eq!(sks.max_noise_safe_chain_length(&ct_res), chain_length - i);
+
+        let clear_i = rng.gen::<u64>() % modulus;
+        let ctxt_i = cks.encrypt_radix(clear_i, NB_CTXT);
+        assert!(sks.is_add_possible(&ct_res, &ctxt_i));
+        sks.unchecked_add_assign(&mut ct_res, &ctxt_i);
+        expected = (expected + clear_i) % modulus;
+    }
+    assert_eq!(sks.max_noise_safe_chain_length(&ct_res), 0);
+
+    let ctxt = cks.encrypt_radix(rng.gen::<u64>() % modulus, NB_CTXT);
+    assert!(!sks.is_add_possible(&ct_res, &ctxt));
+
+    // The whole chain still decrypts correctly
+    let dec_res: u64 = cks.decrypt_radix(&ct_res);
+    assert_eq!(expected, dec_res);
+}
+
 fn integer_smart_add(param: PBSParameters) {
     let (cks, sks) = KEY_CACHE.get_from_params(param);
 
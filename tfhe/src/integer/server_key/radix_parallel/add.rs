@@ -13,6 +13,58 @@ pub(crate) enum AddExtraOne {
     No,
 }
 
+/// The parallel algorithms the default addition chooses from to propagate carries
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum AddAlgorithm {
+    /// Hillis and Steele prefix sum: fewest sequential steps, but uses up to
+    /// `num_blocks - 1` threads
+    LowLatency,
+    /// Brent and Kung prefix sum: about twice as many sequential steps as
+    /// [AddAlgorithm::LowLatency], but does less work and uses at most `num_blocks / 2` threads
+    BrentKung,
+}
+
+/// Chooses the addition algorithm with the smallest estimated latency
+///
+/// The latency is estimated as the number of rounds of PBS each algorithm needs, a round
+/// being as many PBS as there are threads to run them in parallel.
+///
+/// The work efficient (Blelloch) algorithm is not considered, as it does at least as many
+/// rounds as [AddAlgorithm::BrentKung] and only supports powers of two numbers of blocks.
+fn choose_add_algorithm(num_blocks: usize, num_threads: usize) -> AddAlgorithm {
+    let num_threads = num_threads.max(1);
+    let num_rounds = |num_pbs: usize| (num_pbs + num_threads - 1) / num_threads;
+    // Number of blocks updated by a step that updates one block every `stride` blocks,
+    // starting from `first`
+    let num_updates = |first: usize, stride: usize| {
+        if first < num_blocks {
+            (num_blocks - 1 - first) / stride + 1
+        } else {
+            0
+        }
+    };
+
+    // At step i, all blocks but the first 2^i ones are updated
+    let low_latency_rounds = (0..num_blocks.next_power_of_two().ilog2())
+        .map(|i| num_rounds(num_blocks.saturating_sub(1 << i)))
+        .sum::<usize>();
+    // See ServerKey::compute_output_carries_brent_kung for the blocks updated at each step
+    let brent_kung_rounds = (0..num_blocks.checked_ilog2().unwrap_or(0))
+        .map(|i| {
+            let stride = 1 << (i + 1);
+            let up_sweep = num_updates(stride - 1, stride);
+            let down_sweep = num_updates(stride + (1 << i) - 1, stride);
+            num_rounds(up_sweep) + num_rounds(down_sweep)
+        })
+        .sum::<usize>();
+
+    if brent_kung_rounds < low_latency_rounds {
+        AddAlgorithm::BrentKung
+    } else {
+        AddAlgorithm::LowLatency
+    }
+}
+
 #[repr(u64)]
 #[derive(PartialEq, Eq)]
 enum OutputCarry {
@@ -184,13 +236,180 @@ impl ServerKey {
         };
 
         if self.is_eligible_for_parallel_carryless_add() {
-            self.unchecked_add_assign_parallelized_low_latency(lhs, rhs, AddExtraOne::No);
+            match choose_add_algorithm(lhs.blocks.len(), rayon::current_num_threads()) {
+                AddAlgorithm::LowLatency => {
+                    self.unchecked_add_assign_parallelized_low_latency(lhs, rhs, AddExtraOne::No)
+                }
+                AddAlgorithm::BrentKung => {
+                    self.unchecked_add_assign_parallelized_brent_kung(lhs, rhs, AddExtraOne::No)
+                }
+            }
         } else {
             self.unchecked_add_assign(lhs, rhs);
             self.full_propagate_parallelized(lhs);
         }
     }
 
+    /// Computes homomorphically an addition between two ciphertexts encrypting integer values,
+    /// telling whether it overflowed.
+    ///
+    /// Returns the sum, truncated to the number of blocks of the inputs as with
+    /// [`Self::add_parallelized`], and the carry out of its most significant block. The carry is
+    /// a single block encrypting 1 if the sum did not fit in the inputs' blocks (i.e. the
+    /// addition wrapped around), 0 otherwise.
+    ///
+    /// When the parameters allow the low latency parallel addition, the carry out is the last
+    /// carry of its prefix sum, which the addition computes anyway, so this costs no more than
+    /// [`Self::add_parallelized`]. Otherwise, the inputs are extended by one block, whose value
+    /// after the addition is the carry out.
+    ///
+    /// If necessary the carries of the inputs will be cleaned beforehand.
+    ///
+    /// # Warning
+    ///
+    /// - Multithreaded
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // Generate the client key and the server key:
+    /// let num_blocks = 4;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, num_blocks);
+    ///
+    /// let msg1 = 250;
+    /// let msg2 = 9;
+    ///
+    /// let ct1 = cks.encrypt(msg1);
+    /// let ct2 = cks.encrypt(msg2);
+    ///
+    /// // Compute homomorphically an addition:
+    /// let (ct_res, ct_overflow) = sks.overflowing_add_parallelized(&ct1, &ct2);
+    ///
+    /// // Decrypt:
+    /// let dec_result: u64 = cks.decrypt(&ct_res);
+    /// let dec_overflow = cks.decrypt_one_block(&ct_overflow);
+    /// assert_eq!(dec_result, (msg1 + msg2) % 256);
+    /// assert_eq!(dec_overflow, 1);
+    /// ```
+    pub fn overflowing_add_parallelized<PBSOrder: PBSOrderMarker>(
+        &self,
+        ct_left: &RadixCiphertext<PBSOrder>,
+        ct_right: &RadixCiphertext<PBSOrder>,
+    ) -> (
+        RadixCiphertext<PBSOrder>,
+        crate::shortint::CiphertextBase<PBSOrder>,
+    ) {
+        let mut ct_res = ct_left.clone();
+        let mut tmp_rhs: RadixCiphertext<PBSOrder>;
+
+        let rhs = match (
+            ct_res.block_carries_are_empty(),
+            ct_right.block_carries_are_empty(),
+        ) {
+            (true, true) => ct_right,
+            (true, false) => {
+                tmp_rhs = ct_right.clone();
+                self.full_propagate_parallelized(&mut tmp_rhs);
+                &tmp_rhs
+            }
+            (false, true) => {
+                self.full_propagate_parallelized(&mut ct_res);
+                ct_right
+            }
+            (false, false) => {
+                tmp_rhs = ct_right.clone();
+                rayon::join(
+                    || self.full_propagate_parallelized(&mut ct_res),
+                    || self.full_propagate_parallelized(&mut tmp_rhs),
+                );
+                &tmp_rhs
+            }
+        };
+
+        if self.is_eligible_for_parallel_carryless_add() {
+            let ct_overflow = self.unchecked_overflowing_add_assign_parallelized_low_latency(
+                &mut ct_res,
+                rhs,
+                AddExtraOne::No,
+            );
+            (ct_res, ct_overflow)
+        } else {
+            let extended_rhs = self.extend_radix_with_trivial_zero_blocks_msb(rhs, 1);
+            self.extend_radix_with_trivial_zero_blocks_msb_assign(&mut ct_res, 1);
+            self.unchecked_add_assign(&mut ct_res, &extended_rhs);
+            self.full_propagate_parallelized(&mut ct_res);
+            let ct_overflow = ct_res.blocks.pop().unwrap();
+            (ct_res, ct_overflow)
+        }
+    }
+
+    /// Computes homomorphically an addition between two ciphertexts encrypting integer values,
+    /// saturating instead of wrapping around.
+    ///
+    /// The inputs are treated as unsigned integers of as many blocks as they have: when the sum
+    /// does not fit in these blocks, the result is the largest value they can represent
+    /// (`message_modulus^num_blocks - 1`), otherwise it is the sum.
+    ///
+    /// The sum and its carry out are computed by [`Self::overflowing_add_parallelized`], then
+    /// each block of the sum is replaced by `message_modulus - 1` when the carry out is set
+    /// (one bivariate PBS per block, all in parallel).
+    ///
+    /// If necessary the carries of the inputs will be cleaned beforehand.
+    ///
+    /// # Warning
+    ///
+    /// - Multithreaded
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // Generate the client key and the server key:
+    /// let num_blocks = 4;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, num_blocks);
+    ///
+    /// let msg1 = 250;
+    /// let msg2 = 9;
+    ///
+    /// let ct1 = cks.encrypt(msg1);
+    /// let ct2 = cks.encrypt(msg2);
+    ///
+    /// // Compute homomorphically a saturating addition:
+    /// let ct_res = sks.saturating_add_parallelized(&ct1, &ct2);
+    ///
+    /// // Decrypt:
+    /// let dec_result: u64 = cks.decrypt(&ct_res);
+    /// // 250 + 9 does not fit in 8 bits, so the result is 2^8 - 1 instead of 3
+    /// assert_eq!(dec_result, 255);
+    /// ```
+    pub fn saturating_add_parallelized<PBSOrder: PBSOrderMarker>(
+        &self,
+        ct_left: &RadixCiphertext<PBSOrder>,
+        ct_right: &RadixCiphertext<PBSOrder>,
+    ) -> RadixCiphertext<PBSOrder> {
+        let (mut ct_res, ct_overflow) = self.overflowing_add_parallelized(ct_left, ct_right);
+
+        let max_block_value = self.key.message_modulus.0 as u64 - 1;
+        let lut = self.key.generate_accumulator_bivariate(|x, overflow| {
+            if overflow == 1 {
+                max_block_value
+            } else {
+                x
+            }
+        });
+        ct_res.blocks.par_iter_mut().for_each(|block| {
+            *block = self
+                .key
+                .unchecked_apply_lookup_table_bivariate(block, &ct_overflow, &lut);
+        });
+        ct_res
+    }
+
     pub fn add_parallelized_work_efficient<PBSOrder: PBSOrderMarker>(
         &self,
         ct_left: &RadixCiphertext<PBSOrder>,
@@ -235,6 +454,111 @@ impl ServerKey {
         self.unchecked_add_assign_parallelized_work_efficient(lhs, rhs, AddExtraOne::No);
     }
 
+    /// Computes homomorphically the sum of all the ciphertexts.
+    ///
+    /// The terms are reduced with a carry-save tree: at each level, chunks of terms are added
+    /// without any PBS, then each chunk is split back into two clean terms, its message blocks
+    /// and its carry blocks shifted by one block. No carry is propagated until a single pair of
+    /// terms remains, which is added and propagated once with
+    /// [Self::full_propagate_parallelized].
+    ///
+    /// Returns `None` if there are no ciphertexts to sum.
+    ///
+    /// # Warning
+    ///
+    /// All the ciphertexts are expected to have the same number of blocks, with empty carries.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // Generate the client key and the server key:
+    /// let num_blocks = 4;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, num_blocks);
+    ///
+    /// let clears = [3u64, 14, 15, 92, 65, 35, 89, 79];
+    /// let ctxts = clears.iter().map(|c| cks.encrypt(*c)).collect::<Vec<_>>();
+    ///
+    /// // Compute homomorphically the sum:
+    /// let ct_res = sks.unchecked_sum_ciphertexts_parallelized(&ctxts).unwrap();
+    ///
+    /// // Decrypt:
+    /// let res: u64 = cks.decrypt(&ct_res);
+    /// assert_eq!(clears.iter().sum::<u64>() % 256, res);
+    /// ```
+    pub fn unchecked_sum_ciphertexts_parallelized<'item, PBSOrder: PBSOrderMarker + 'item>(
+        &self,
+        ciphertexts: impl IntoIterator<Item = &'item RadixCiphertext<PBSOrder>>,
+    ) -> Option<RadixCiphertext<PBSOrder>> {
+        let mut terms = ciphertexts.into_iter().cloned().collect::<Vec<_>>();
+        if terms.len() <= 1 {
+            return terms.pop();
+        }
+
+        let num_blocks = terms[0].blocks.len();
+        if num_blocks == 0 {
+            return terms.pop();
+        }
+
+        let message_modulus = self.key.message_modulus.0;
+        // Number of clean terms that can be added in a block without exceeding the max degree of
+        // the server key, such that the carry of their sum is itself a clean block
+        let chunk_size = (self.key.max_degree.0 / (message_modulus - 1)).min(message_modulus + 1);
+        if chunk_size < 3 {
+            // Splitting chunks of 2 terms into 2 terms would not reduce anything
+            return self.default_binary_op_seq_parallelized(&terms, ServerKey::add_parallelized);
+        }
+
+        while terms.len() > 2 {
+            terms = terms
+                .par_chunks(chunk_size)
+                .flat_map_iter(|chunk| {
+                    if chunk.len() <= 2 {
+                        return chunk.to_vec();
+                    }
+
+                    let (first, rest) = chunk.split_first().unwrap();
+                    let mut sum = first.clone();
+                    for term in rest {
+                        self.unchecked_add_assign(&mut sum, term);
+                    }
+
+                    // The carry of the last block overflows the modulus, it is not extracted
+                    let (message_blocks, carry_blocks) = rayon::join(
+                        || {
+                            sum.blocks
+                                .par_iter()
+                                .map(|block| self.key.message_extract(block))
+                                .collect::<Vec<_>>()
+                        },
+                        || {
+                            let mut carry_blocks = sum.blocks[..num_blocks - 1]
+                                .par_iter()
+                                .map(|block| self.key.carry_extract(block))
+                                .collect::<Vec<_>>();
+                            carry_blocks.insert(0, self.key.create_trivial(0));
+                            carry_blocks
+                        },
+                    );
+
+                    vec![
+                        RadixCiphertext::from(message_blocks),
+                        RadixCiphertext::from(carry_blocks),
+                    ]
+                })
+                .collect();
+        }
+
+        let mut sum = terms.pop().unwrap();
+        for term in &terms {
+            self.unchecked_add_assign(&mut sum, term);
+        }
+        self.full_propagate_parallelized(&mut sum);
+        Some(sum)
+    }
+
     pub(crate) fn is_eligible_for_parallel_carryless_add(&self) -> bool {
         // having 4-bits is a hard requirement
         // as the parallel implementation uses a bivariate BPS where individual values need
@@ -273,8 +597,74 @@ impl ServerKey {
         debug_assert!(rhs.block_carries_are_empty());
         debug_assert!(self.key.message_modulus.0 * self.key.carry_modulus.0 >= (1 << 3));
 
-        let mut carry_out = self.add_and_generate_init_carry_array(lhs, rhs, add_extra_one);
+        self.unchecked_overflowing_add_assign_parallelized_low_latency(lhs, rhs, add_extra_one);
+    }
+
+    /// Same as [Self::unchecked_add_assign_parallelized_low_latency], but returns the carry out
+    /// of the most significant block, a block encrypting either 0 or 1.
+    pub(crate) fn unchecked_overflowing_add_assign_parallelized_low_latency<
+        PBSOrder: PBSOrderMarker,
+    >(
+        &self,
+        lhs: &mut RadixCiphertext<PBSOrder>,
+        rhs: &RadixCiphertext<PBSOrder>,
+        add_extra_one: AddExtraOne,
+    ) -> crate::shortint::CiphertextBase<PBSOrder> {
+        debug_assert!(lhs.block_carries_are_empty());
+        debug_assert!(rhs.block_carries_are_empty());
+        debug_assert!(self.key.message_modulus.0 * self.key.carry_modulus.0 >= (1 << 3));
+
+        let carry_out = self.add_and_generate_init_carry_array(lhs, rhs, add_extra_one);
+        let mut carries = self.compute_output_carries_low_latency(carry_out);
+
+        // The output carry of the last block is the overflow, the one of block i-1 becomes
+        // the input carry of block i
+        let ct_overflow = carries[carries.len() - 1].clone();
+        carries.rotate_right(1);
+        self.key.create_trivial_assign(&mut carries[0], 0);
 
+        lhs.blocks
+            .par_iter_mut()
+            .zip(carries.par_iter())
+            .for_each(|(block, input_carry)| {
+                self.key.unchecked_add_assign(block, input_carry);
+                self.key.message_extract_assign(block);
+            });
+
+        ct_overflow
+    }
+
+    /// Computes the carry each block receives from the previous one, from the array telling
+    /// whether each block generates or propagates a carry
+    /// (see [Self::generate_init_carry_array]).
+    ///
+    /// The returned carries are either 0 or 1, the one of the first block is a trivial 0.
+    pub(crate) fn compute_input_carries_low_latency<PBSOrder: PBSOrderMarker>(
+        &self,
+        carry_out: Vec<crate::shortint::CiphertextBase<PBSOrder>>,
+    ) -> Vec<crate::shortint::CiphertextBase<PBSOrder>> {
+        let mut carries = self.compute_output_carries_low_latency(carry_out);
+
+        // The output carry of block i-1 becomes the input
+        // carry of block i
+        carries.rotate_right(1);
+        self.key.create_trivial_assign(&mut carries[0], 0);
+        carries
+    }
+
+    /// Computes the carry each block outputs, from the array telling whether each block
+    /// generates or propagates a carry (see [Self::generate_init_carry_array]).
+    ///
+    /// It uses the Hillis and Steele algorithm to do
+    /// prefix sum / cumulative sum in parallel, as in
+    /// [Self::unchecked_add_assign_parallelized_low_latency].
+    ///
+    /// The returned carries are either 0 or 1, the one of the last block being the carry out of
+    /// the whole radix ciphertext.
+    fn compute_output_carries_low_latency<PBSOrder: PBSOrderMarker>(
+        &self,
+        mut carry_out: Vec<crate::shortint::CiphertextBase<PBSOrder>>,
+    ) -> Vec<crate::shortint::CiphertextBase<PBSOrder>> {
         let num_blocks = carry_out.len();
         let num_steps = carry_out.len().ilog2() as usize;
 
@@ -303,17 +693,7 @@ impl ServerKey {
             space *= 2;
         }
 
-        // The output carry of block i-1 becomes the input
-        // carry of block i
-        carry_out.rotate_right(1);
-        self.key.create_trivial_assign(&mut carry_out[0], 0);
-        lhs.blocks
-            .par_iter_mut()
-            .zip(carry_out.par_iter())
-            .for_each(|(block, input_carry)| {
-                self.key.unchecked_add_assign(block, input_carry);
-                self.key.message_extract_assign(block);
-            });
+        carry_out
     }
 
     /// This add_assign two numbers
@@ -450,6 +830,128 @@ impl ServerKey {
             });
     }
 
+    /// This add_assign two numbers
+    ///
+    /// It uses the Brent and Kung algorithm to do
+    /// prefix sum / cumulative sum in parallel.
+    ///
+    /// Like [Self::unchecked_add_assign_parallelized_work_efficient], it does not add much
+    /// work compared to the single threaded approach, but it computes the carries each block
+    /// outputs directly, so its down-sweep updates fewer blocks, and it is not restricted to
+    /// a number of blocks that is a power of two.
+    ///
+    /// At most num_block / 2 threads are used
+    ///
+    /// # Requirements
+    ///
+    /// - The parameters have 4 bits in total
+    /// - The input carries of both lhs and rhs must be empty
+    ///
+    /// # Output
+    ///
+    /// - lhs will have its carries empty
+    pub(crate) fn unchecked_add_assign_parallelized_brent_kung<PBSOrder: PBSOrderMarker>(
+        &self,
+        lhs: &mut RadixCiphertext<PBSOrder>,
+        rhs: &RadixCiphertext<PBSOrder>,
+        add_extra_one: AddExtraOne,
+    ) {
+        debug_assert!(lhs.block_carries_are_empty());
+        debug_assert!(rhs.block_carries_are_empty());
+        debug_assert!(self.key.message_modulus.0 * self.key.carry_modulus.0 >= (1 << 3));
+
+        let carry_out = self.add_and_generate_init_carry_array(lhs, rhs, add_extra_one);
+        let mut carries = self.compute_output_carries_brent_kung(carry_out);
+
+        // The output carry of block i-1 becomes the input
+        // carry of block i
+        carries.rotate_right(1);
+        self.key.create_trivial_assign(&mut carries[0], 0);
+
+        lhs.blocks
+            .par_iter_mut()
+            .zip(carries.par_iter())
+            .for_each(|(block, input_carry)| {
+                self.key.unchecked_add_assign(block, input_carry);
+                self.key.message_extract_assign(block);
+            });
+    }
+
+    /// Computes the carry each block outputs, from the array telling whether each block
+    /// generates or propagates a carry (see [Self::generate_init_carry_array]).
+    ///
+    /// It uses the Brent and Kung algorithm to do
+    /// prefix sum / cumulative sum in parallel:
+    ///
+    /// - the up-sweep phase makes each block at index `k * 2^(i+1) - 1` hold the prefix sum
+    ///   of the `2^(i+1)` blocks ending at it, so that the blocks at indices `2^i - 1` get
+    ///   their final value
+    /// - the down-sweep phase then completes the blocks in between, from the largest strides
+    ///   to the smallest, each from the closest completed block before it
+    ///
+    /// Within a step, the blocks updated and the blocks read are distinct, so each step is
+    /// done in parallel.
+    fn compute_output_carries_brent_kung<PBSOrder: PBSOrderMarker>(
+        &self,
+        mut carry_out: Vec<crate::shortint::CiphertextBase<PBSOrder>>,
+    ) -> Vec<crate::shortint::CiphertextBase<PBSOrder>> {
+        let num_blocks = carry_out.len();
+        let num_steps = num_blocks.ilog2();
+
+        let lut_carry_propagation_sum = self
+            .key
+            .generate_accumulator_bivariate(prefix_sum_carry_propagation);
+
+        // Updates one block every `stride` blocks starting from `first`,
+        // with the block `distance` blocks before it
+        let prefix_sum_step = |carry_out: &mut [crate::shortint::CiphertextBase<PBSOrder>],
+                               first: usize,
+                               stride: usize,
+                               distance: usize| {
+            let updated_blocks = (first..num_blocks)
+                .into_par_iter()
+                .step_by(stride)
+                .map(|index| {
+                    self.key.unchecked_apply_lookup_table_bivariate(
+                        &carry_out[index],
+                        &carry_out[index - distance],
+                        &lut_carry_propagation_sum,
+                    )
+                })
+                .collect::<Vec<_>>();
+
+            for (index, block) in (first..num_blocks).step_by(stride).zip(updated_blocks) {
+                carry_out[index] = block;
+            }
+        };
+
+        // Up-Sweep phase
+        for i in 0..num_steps {
+            let two_pow_i = 1 << i;
+            let two_pow_i_plus_1 = 1 << (i + 1);
+            prefix_sum_step(
+                &mut carry_out,
+                two_pow_i_plus_1 - 1,
+                two_pow_i_plus_1,
+                two_pow_i,
+            );
+        }
+
+        // Down-Sweep phase
+        for i in (0..num_steps).rev() {
+            let two_pow_i = 1 << i;
+            let two_pow_i_plus_1 = 1 << (i + 1);
+            prefix_sum_step(
+                &mut carry_out,
+                two_pow_i_plus_1 + two_pow_i - 1,
+                two_pow_i_plus_1,
+                two_pow_i,
+            );
+        }
+
+        carry_out
+    }
+
     /// Initialization function for parallal carryless sum
     ///
     /// This function adds rhs into lhs
@@ -463,11 +965,33 @@ impl ServerKey {
         lhs: &mut RadixCiphertext<PBSOrder>,
         rhs: &RadixCiphertext<PBSOrder>,
         add_extra_one: AddExtraOne,
+    ) -> Vec<crate::shortint::CiphertextBase<PBSOrder>> {
+        lhs.blocks
+            .par_iter_mut()
+            .zip(rhs.blocks.par_iter())
+            .enumerate()
+            .for_each(|(i, (ct_left_i, ct_right_i))| {
+                self.key.unchecked_add_assign(ct_left_i, ct_right_i);
+                if i == 0 && matches!(add_extra_one, AddExtraOne::Yes) {
+                    self.key.unchecked_scalar_add_assign(ct_left_i, 1);
+                }
+            });
+
+        self.generate_init_carry_array(&lhs.blocks)
+    }
+
+    /// Returns the array that tells whether each block will propagate or generate a carry.
+    ///
+    /// Each block must encrypt a value lower than twice the message modulus, so that it outputs
+    /// a carry of at most 1 even when receiving a carry of 1 itself.
+    pub(crate) fn generate_init_carry_array<PBSOrder: PBSOrderMarker>(
+        &self,
+        blocks: &[crate::shortint::CiphertextBase<PBSOrder>],
     ) -> Vec<crate::shortint::CiphertextBase<PBSOrder>> {
         let modulus = self.key.message_modulus.0 as u64;
 
-        // This used for the first pair of blocks
-        // as this pair can either generate or not, but never propagate
+        // This used for the first block
+        // as it can either generate or not, but never propagate
         let lut_does_block_generate_carry = self.key.generate_accumulator(|x| {
             if x >= modulus {
                 OutputCarry::Generated as u64
@@ -486,23 +1010,18 @@ impl ServerKey {
             }
         });
 
-        let mut carry_out = Vec::with_capacity(lhs.blocks.len());
-        lhs.blocks
-            .par_iter_mut()
-            .zip(rhs.blocks.par_iter())
+        let mut carry_out = Vec::with_capacity(blocks.len());
+        blocks
+            .par_iter()
             .enumerate()
-            .map(|(i, (ct_left_i, ct_right_i))| {
-                self.key.unchecked_add_assign(ct_left_i, ct_right_i);
+            .map(|(i, block)| {
                 if i == 0 {
-                    if matches!(add_extra_one, AddExtraOne::Yes) {
-                        self.key.unchecked_scalar_add_assign(ct_left_i, 1);
-                    }
                     // The first block can only ouput a carry
                     self.key
-                        .apply_lookup_table(ct_left_i, &lut_does_block_generate_carry)
+                        .apply_lookup_table(block, &lut_does_block_generate_carry)
                 } else {
                     self.key
-                        .apply_lookup_table(ct_left_i, &lut_does_block_generate_or_propagate)
+                        .apply_lookup_table(block, &lut_does_block_generate_or_propagate)
                 }
             })
             .collect_into_vec(&mut carry_out);
@@ -597,6 +1116,9 @@ impl ServerKey {
     }
 
     /// op must be associative and commutative
+    ///
+    /// The sequence is reduced with a balanced binary tree, see
+    /// [Self::default_op_seq_parallelized_with_arity] to reduce more terms at a time.
     pub fn default_binary_op_seq_parallelized<'this, 'item, PBSOrder: PBSOrderMarker + 'item>(
         &'this self,
         ct_seq: impl IntoIterator<Item = &'item RadixCiphertext<PBSOrder>>,
@@ -607,6 +1129,69 @@ impl ServerKey {
             ) -> RadixCiphertext<PBSOrder>
             + Sync,
     ) -> Option<RadixCiphertext<PBSOrder>> {
+        // With an arity of 2, all chunks hold exactly 2 terms
+        self.default_op_seq_parallelized_with_arity(ct_seq, 2, |sks, chunk| {
+            op(sks, chunk[0], chunk[1])
+        })
+    }
+
+    /// Reduces a sequence of ciphertexts with a tree of the given arity.
+    ///
+    /// At each level of the tree, consecutive terms are grouped by chunks of `arity` terms,
+    /// each chunk being reduced by `op` in parallel. `op` is given between 2 and `arity` terms,
+    /// and must be associative and commutative. With a larger arity the tree has fewer levels,
+    /// `ceil(log_arity(n))` for `n` terms, but each level does more work per chunk: this trades
+    /// parallel width for depth, which pays off when `op` reduces many terms for about the cost
+    /// of two, as a multi-operand addition does. An arity of 2 (a balanced binary tree) is what
+    /// [Self::default_binary_op_seq_parallelized] uses.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `arity` is lower than 2.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // Generate the client key and the server key:
+    /// let num_blocks = 4;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, num_blocks);
+    ///
+    /// let clears = [3u64, 14, 15, 92, 65, 35];
+    /// let ctxts = clears.iter().map(|c| cks.encrypt(*c)).collect::<Vec<_>>();
+    ///
+    /// // Multi-operand addition: up to 3 clean terms fit in the carries,
+    /// // so they are added without any PBS before a single propagation
+    /// let ct_res = sks
+    ///     .default_op_seq_parallelized_with_arity(&ctxts, 3, |sks, chunk| {
+    ///         let mut sum = chunk[0].clone();
+    ///         for ct in &chunk[1..] {
+    ///             sks.unchecked_add_assign(&mut sum, ct);
+    ///         }
+    ///         sks.full_propagate_parallelized(&mut sum);
+    ///         sum
+    ///     })
+    ///     .unwrap();
+    ///
+    /// // Decrypt:
+    /// let res: u64 = cks.decrypt(&ct_res);
+    /// assert_eq!(clears.iter().sum::<u64>() % 256, res);
+    /// ```
+    pub fn default_op_seq_parallelized_with_arity<
+        'this,
+        'item,
+        PBSOrder: PBSOrderMarker + 'item,
+    >(
+        &'this self,
+        ct_seq: impl IntoIterator<Item = &'item RadixCiphertext<PBSOrder>>,
+        arity: usize,
+        op: impl for<'a> Fn(&'a ServerKey, &[&'a RadixCiphertext<PBSOrder>]) -> RadixCiphertext<PBSOrder>
+            + Sync,
+    ) -> Option<RadixCiphertext<PBSOrder>> {
+        assert!(arity >= 2, "the arity must be at least 2, got {arity}");
+
         enum CiphertextCow<'a, O: PBSOrderMarker> {
             Borrowed(&'a RadixCiphertext<O>),
             Owned(RadixCiphertext<O>),
@@ -633,10 +1218,10 @@ impl ServerKey {
         fn reduce_impl<PBSOrder: PBSOrderMarker>(
             sks: &ServerKey,
             mut ct_seq: Vec<CiphertextCow<PBSOrder>>,
+            arity: usize,
             op: &(dyn for<'a> Fn(
                 &'a ServerKey,
-                &'a RadixCiphertext<PBSOrder>,
-                &'a RadixCiphertext<PBSOrder>,
+                &[&'a RadixCiphertext<PBSOrder>],
             ) -> RadixCiphertext<PBSOrder>
                   + Sync),
         ) -> Option<RadixCiphertext<PBSOrder>> {
@@ -645,24 +1230,21 @@ impl ServerKey {
             if ct_seq.is_empty() {
                 None
             } else {
-                // we repeatedly divide the number of terms by two by iteratively reducing
-                // consecutive terms in the array
-                let num_blocks = ct_seq[0].as_ref().blocks.len();
+                // we repeatedly divide the number of terms by the arity by iteratively reducing
+                // chunks of consecutive terms in the array
                 while ct_seq.len() > 1 {
-                    let mut results =
-                        vec![sks.create_trivial_radix(0u64, num_blocks); ct_seq.len() / 2];
-                    // if the number of elements is odd, we skip the first element
-                    let untouched_prefix = ct_seq.len() % 2;
-                    let ct_seq_slice = &mut ct_seq[untouched_prefix..];
+                    // if a single element would be left alone in the last chunk, we skip the
+                    // first element instead, so that all chunks have at least 2 elements
+                    let untouched_prefix = (ct_seq.len() % arity == 1) as usize;
+                    let ct_seq_slice = &ct_seq[untouched_prefix..];
 
-                    results
-                        .par_iter_mut()
-                        .zip(ct_seq_slice.par_chunks_exact(2))
-                        .for_each(|(ct_res, chunk)| {
-                            let first = chunk[0].as_ref();
-                            let second = chunk[1].as_ref();
-                            *ct_res = op(sks, first, second);
-                        });
+                    let results = ct_seq_slice
+                        .par_chunks(arity)
+                        .map(|chunk| {
+                            let terms = chunk.iter().map(CiphertextCow::as_ref).collect::<Vec<_>>();
+                            op(sks, &terms)
+                        })
+                        .collect::<Vec<_>>();
 
                     ct_seq.truncate(untouched_prefix);
                     ct_seq.extend(results.into_iter().map(CiphertextCow::Owned));
@@ -677,6 +1259,6 @@ impl ServerKey {
             }
         }
 
-        reduce_impl(self, ct_seq, op)
+        reduce_impl(self, ct_seq, arity, op)
     }
 }
@@ -510,4 +510,145 @@ impl ServerKey {
             .par_iter_mut()
             .for_each(|block| self.key.apply_lookup_table_assign(block, &lut))
     }
+
+    /// Extracts homomorphically the bit at index `bit` of a ciphertext.
+    ///
+    /// The result is an encrypted boolean, with as many blocks as the input and the bit value
+    /// in its first block, as returned by comparisons. As the bit index is in the clear, only
+    /// the block holding the bit goes through a PBS.
+    ///
+    /// If necessary the carries of the input will be cleaned beforehand,
+    /// but its value won't change, the result is returned in a new ciphertext
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bit` is not lower than the number of bits of message of the ciphertext.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // We have 4 * 2 = 8 bits of message
+    /// let size = 4;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, size);
+    ///
+    /// let msg = 0b1010_0100u64;
+    ///
+    /// let ct = cks.encrypt(msg);
+    ///
+    /// let ct_bit_2 = sks.get_bit_parallelized(&ct, 2);
+    /// let ct_bit_3 = sks.get_bit_parallelized(&ct, 3);
+    ///
+    /// // Decrypt:
+    /// let bit_2: u64 = cks.decrypt(&ct_bit_2);
+    /// let bit_3: u64 = cks.decrypt(&ct_bit_3);
+    /// assert_eq!(1, bit_2);
+    /// assert_eq!(0, bit_3);
+    /// ```
+    pub fn get_bit_parallelized<PBSOrder: PBSOrderMarker>(
+        &self,
+        ct: &RadixCiphertext<PBSOrder>,
+        bit: usize,
+    ) -> RadixCiphertext<PBSOrder> {
+        let (block_index, bit_in_block) = self.bit_position(ct, bit);
+
+        let mut tmp_ct: RadixCiphertext<PBSOrder>;
+        let ct = if ct.block_carries_are_empty() {
+            ct
+        } else {
+            tmp_ct = ct.clone();
+            self.full_propagate_parallelized(&mut tmp_ct);
+            &tmp_ct
+        };
+
+        let lut = self.key.generate_accumulator(|x| (x >> bit_in_block) & 1);
+        let mut result = self.create_trivial_zero_radix(ct.blocks.len());
+        result.blocks[0] = self.key.apply_lookup_table(&ct.blocks[block_index], &lut);
+        result
+    }
+
+    /// Sets homomorphically the bit at index `bit` of a ciphertext to an encrypted boolean.
+    ///
+    /// The value is an encrypted boolean, i.e. a ciphertext encrypting 0 or 1, as returned by
+    /// comparisons. As the bit index is in the clear, only the block holding the bit goes
+    /// through a PBS, a bivariate one with the value, that keeps the other bits of the block.
+    ///
+    /// If necessary carries will be cleaned beforehand
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bit` is not lower than the number of bits of message of the ciphertext.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // We have 4 * 2 = 8 bits of message
+    /// let size = 4;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, size);
+    ///
+    /// let msg = 0b1010_0100u64;
+    ///
+    /// let mut ct = cks.encrypt(msg);
+    /// let ct_true = cks.encrypt(1u64);
+    /// let ct_false = cks.encrypt(0u64);
+    ///
+    /// sks.set_bit_parallelized(&mut ct, 3, &ct_true);
+    /// sks.set_bit_parallelized(&mut ct, 5, &ct_false);
+    ///
+    /// // Decrypt:
+    /// let dec: u64 = cks.decrypt(&ct);
+    /// assert_eq!(0b1000_1100, dec);
+    /// ```
+    pub fn set_bit_parallelized<PBSOrder: PBSOrderMarker>(
+        &self,
+        ct: &mut RadixCiphertext<PBSOrder>,
+        bit: usize,
+        value: &RadixCiphertext<PBSOrder>,
+    ) {
+        let (block_index, bit_in_block) = self.bit_position(ct, bit);
+
+        let mut tmp_value: RadixCiphertext<PBSOrder>;
+        let value = if value.block_carries_are_empty() {
+            value
+        } else {
+            tmp_value = value.clone();
+            self.full_propagate_parallelized(&mut tmp_value);
+            &tmp_value
+        };
+        if !ct.block_carries_are_empty() {
+            self.full_propagate_parallelized(ct);
+        }
+
+        // The value is a boolean, so only its first block can be non zero
+        let mask = 1 << bit_in_block;
+        let lut = self
+            .key
+            .generate_accumulator_bivariate(|x, v| (x & !mask) | ((v & 1) << bit_in_block));
+        ct.blocks[block_index] = self.key.unchecked_apply_lookup_table_bivariate(
+            &ct.blocks[block_index],
+            &value.blocks[0],
+            &lut,
+        );
+    }
+
+    /// Returns the index of the block holding the bit at index `bit`, and the index of the bit
+    /// within that block
+    fn bit_position<PBSOrder: PBSOrderMarker>(
+        &self,
+        ct: &RadixCiphertext<PBSOrder>,
+        bit: usize,
+    ) -> (usize, u64) {
+        let num_bits_in_block = self.key.message_modulus.0.ilog2() as usize;
+        let num_bits = num_bits_in_block * ct.blocks.len();
+        assert!(
+            bit < num_bits,
+            "bit index {bit} out of range for a ciphertext of {num_bits} bits"
+        );
+        (bit / num_bits_in_block, (bit % num_bits_in_block) as u64)
+    }
 }
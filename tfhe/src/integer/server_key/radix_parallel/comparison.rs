@@ -2,7 +2,9 @@ use super::ServerKey;
 
 use crate::integer::ciphertext::RadixCiphertext;
 use crate::integer::server_key::comparator::Comparator;
-use crate::shortint::PBSOrderMarker;
+use crate::shortint::{CiphertextBase, PBSOrderMarker};
+
+use rayon::prelude::*;
 
 impl ServerKey {
     pub fn unchecked_eq_parallelized<PBSOrder: PBSOrderMarker>(
@@ -172,4 +174,365 @@ impl ServerKey {
     ) -> RadixCiphertext<PBSOrder> {
         Comparator::new(self).min_parallelized(lhs, rhs)
     }
+
+    /// Homomorphically clamps `ct` between the encrypted bounds `lo` and `hi`.
+    ///
+    /// The result is computed as `min(max(ct, lo), hi)`. As the bounds are encrypted, their
+    /// order cannot be checked: if `lo > hi` the result is `hi`, whatever the value of `ct`.
+    ///
+    /// # Warning
+    ///
+    /// - Multithreaded
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // Generate the client key and the server key:
+    /// let num_blocks = 4;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, num_blocks);
+    ///
+    /// let msg = 97u64;
+    /// let lo = 10u64;
+    /// let hi = 42u64;
+    ///
+    /// let ct = cks.encrypt(msg);
+    /// let ct_lo = cks.encrypt(lo);
+    /// let ct_hi = cks.encrypt(hi);
+    ///
+    /// // Compute homomorphically a clamp:
+    /// let ct_res = sks.clamp_parallelized(&ct, &ct_lo, &ct_hi);
+    ///
+    /// // Decrypt:
+    /// let dec_result: u64 = cks.decrypt(&ct_res);
+    /// assert_eq!(dec_result, msg.clamp(lo, hi));
+    /// ```
+    pub fn clamp_parallelized<PBSOrder: PBSOrderMarker>(
+        &self,
+        ct: &RadixCiphertext<PBSOrder>,
+        lo: &RadixCiphertext<PBSOrder>,
+        hi: &RadixCiphertext<PBSOrder>,
+    ) -> RadixCiphertext<PBSOrder> {
+        let at_least_lo = self.max_parallelized(ct, lo);
+        self.min_parallelized(&at_least_lo, hi)
+    }
+
+    /// Homomorphically computes the index of the maximum among the ciphertexts.
+    ///
+    /// The maximum is found with a parallel reduction tree over consecutive pairs, each
+    /// node keeping the running maximum and its (encrypted) index.
+    ///
+    /// When several ciphertexts encrypt the maximum value, the lowest index among them is
+    /// returned.
+    ///
+    /// Returns `None` if `cts` is empty.
+    ///
+    /// # Requirements
+    ///
+    /// - All ciphertexts must have the same number of blocks
+    /// - The index `cts.len() - 1` must be representable with that number of blocks
+    ///
+    /// # Warning
+    ///
+    /// - Multithreaded
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // Generate the client key and the server key:
+    /// let num_blocks = 4;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, num_blocks);
+    ///
+    /// let msgs = [14u64, 97, 3, 97];
+    /// let cts = msgs.iter().map(|msg| cks.encrypt(*msg)).collect::<Vec<_>>();
+    ///
+    /// // Compute homomorphically the index of the maximum:
+    /// let ct_res = sks.argmax_parallelized(&cts).unwrap();
+    ///
+    /// // Decrypt:
+    /// let dec_result: u64 = cks.decrypt(&ct_res);
+    /// assert_eq!(dec_result, 1);
+    /// ```
+    pub fn argmax_parallelized<PBSOrder: PBSOrderMarker>(
+        &self,
+        cts: &[RadixCiphertext<PBSOrder>],
+    ) -> Option<RadixCiphertext<PBSOrder>> {
+        let num_blocks = cts.first()?.blocks.len();
+        debug_assert!(cts.iter().all(|ct| ct.blocks.len() == num_blocks));
+
+        let mut candidates = cts
+            .par_iter()
+            .enumerate()
+            .map(|(i, ct)| {
+                let mut ct = ct.clone();
+                if !ct.block_carries_are_empty() {
+                    self.full_propagate_parallelized(&mut ct);
+                }
+                let index: RadixCiphertext<PBSOrder> =
+                    self.create_trivial_radix(i as u64, num_blocks);
+                (ct, index)
+            })
+            .collect::<Vec<_>>();
+
+        while candidates.len() > 1 {
+            // The lhs of a pair always holds lower indices than the rhs,
+            // so only selecting the rhs if strictly greater resolves ties to the lowest index
+            candidates = candidates
+                .par_chunks(2)
+                .map(|chunk| match chunk {
+                    [lhs, rhs] => {
+                        let rhs_is_greater = self.unchecked_gt_parallelized(&rhs.0, &lhs.0);
+                        let condition = &rhs_is_greater.blocks[0];
+                        rayon::join(
+                            || self.unchecked_select_parallelized(condition, &rhs.0, &lhs.0),
+                            || self.unchecked_select_parallelized(condition, &rhs.1, &lhs.1),
+                        )
+                    }
+                    [single] => single.clone(),
+                    _ => unreachable!(),
+                })
+                .collect();
+        }
+
+        candidates.pop().map(|(_, index)| index)
+    }
+
+    /// Homomorphically checks whether the ciphertexts are sorted in non-decreasing order.
+    ///
+    /// Returns an encrypted boolean, with as many blocks as the ciphertexts, that is true if
+    /// each ciphertext is lower than or equal to the next one. A single ciphertext is always
+    /// sorted. Returns `None` if `cts` is empty.
+    ///
+    /// Each adjacent pair is compared independently, all of them in parallel, and the results
+    /// are combined with [`Self::and_reduce`].
+    ///
+    /// # Requirements
+    ///
+    /// - All ciphertexts must have the same number of blocks
+    ///
+    /// # Warning
+    ///
+    /// - Multithreaded
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // Generate the client key and the server key:
+    /// let num_blocks = 4;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, num_blocks);
+    ///
+    /// let msgs = [3u64, 14, 14, 97];
+    /// let cts = msgs.iter().map(|msg| cks.encrypt(*msg)).collect::<Vec<_>>();
+    ///
+    /// // Compute homomorphically whether the ciphertexts are sorted:
+    /// let ct_res = sks.is_sorted_parallelized(&cts).unwrap();
+    ///
+    /// // Decrypt:
+    /// let dec_result: u64 = cks.decrypt(&ct_res);
+    /// assert_eq!(dec_result, 1);
+    /// ```
+    pub fn is_sorted_parallelized<PBSOrder: PBSOrderMarker>(
+        &self,
+        cts: &[RadixCiphertext<PBSOrder>],
+    ) -> Option<RadixCiphertext<PBSOrder>> {
+        let num_blocks = cts.first()?.blocks.len();
+        debug_assert!(cts.iter().all(|ct| ct.blocks.len() == num_blocks));
+
+        if cts.len() == 1 {
+            return Some(self.create_trivial_radix(1u64, num_blocks));
+        }
+
+        let pairs_are_sorted = cts
+            .par_windows(2)
+            .map(|pair| self.le_parallelized(&pair[0], &pair[1]))
+            .collect::<Vec<_>>();
+        self.and_reduce(&pairs_are_sorted)
+    }
+
+    /// Homomorphically selects the ciphertext at the encrypted `index` among `options`.
+    ///
+    /// This is a multiplexer: each option is kept if `index` equals its position and zeroed
+    /// otherwise, all of them in parallel, and the results are summed. As at most one of them
+    /// is kept, the sum is the selected option. If `index` is out of range, no option is kept
+    /// and the result is an encrypted 0.
+    ///
+    /// The result has as many blocks as the options, and empty carries.
+    /// Returns `None` if `options` is empty.
+    ///
+    /// If necessary the carries of the inputs will be cleaned beforehand.
+    ///
+    /// # Requirements
+    ///
+    /// - All the options must have the same number of blocks
+    ///
+    /// # Warning
+    ///
+    /// - Multithreaded
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // Generate the client key and the server key:
+    /// let num_blocks = 4;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, num_blocks);
+    ///
+    /// let msgs = [14u64, 97, 3, 200];
+    /// let options = msgs.iter().map(|msg| cks.encrypt(*msg)).collect::<Vec<_>>();
+    /// let ct_index = cks.encrypt(2u64);
+    ///
+    /// // Compute homomorphically options[index]:
+    /// let ct_res = sks.select_parallelized(&options, &ct_index).unwrap();
+    ///
+    /// // Decrypt:
+    /// let dec_result: u64 = cks.decrypt(&ct_res);
+    /// assert_eq!(dec_result, 3);
+    /// ```
+    pub fn select_parallelized<PBSOrder: PBSOrderMarker>(
+        &self,
+        options: &[RadixCiphertext<PBSOrder>],
+        index: &RadixCiphertext<PBSOrder>,
+    ) -> Option<RadixCiphertext<PBSOrder>> {
+        let num_blocks = options.first()?.blocks.len();
+        debug_assert!(options.iter().all(|ct| ct.blocks.len() == num_blocks));
+
+        let mut tmp_index: RadixCiphertext<PBSOrder>;
+        let index = if index.block_carries_are_empty() {
+            index
+        } else {
+            tmp_index = index.clone();
+            self.full_propagate_parallelized(&mut tmp_index);
+            &tmp_index
+        };
+
+        // Positions that index can't encrypt are never selected
+        let num_index_bits = self.key.message_modulus.0.ilog2() * index.blocks.len() as u32;
+        let num_selectable = if num_index_bits >= usize::BITS {
+            options.len()
+        } else {
+            options.len().min(1 << num_index_bits)
+        };
+
+        let lut_keep_if_selected =
+            self.key
+                .generate_accumulator_bivariate(|x, c| if c == 1 { x } else { 0 });
+        let kept_options = options[..num_selectable]
+            .par_iter()
+            .enumerate()
+            .map(|(i, option)| {
+                let mut tmp_option: RadixCiphertext<PBSOrder>;
+                let option = if option.block_carries_are_empty() {
+                    option
+                } else {
+                    tmp_option = option.clone();
+                    self.full_propagate_parallelized(&mut tmp_option);
+                    &tmp_option
+                };
+                let trivial_i: RadixCiphertext<PBSOrder> =
+                    self.create_trivial_radix(i as u64, index.blocks.len());
+                let is_selected = self.unchecked_eq_parallelized(index, &trivial_i);
+                option
+                    .blocks
+                    .par_iter()
+                    .map(|block| {
+                        self.key.unchecked_apply_lookup_table_bivariate(
+                            block,
+                            &is_selected.blocks[0],
+                            &lut_keep_if_selected,
+                        )
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        // At most one of the terms of each sum is non zero, so the sums never exceed the message
+        // space, but the degrees of the terms add up, so as many terms as the max degree of the
+        // server key allows are summed at a time before cleaning the degree with a PBS
+        let max_num_terms = (self.key.max_degree.0 / (self.key.message_modulus.0 - 1)).max(2);
+        let blocks = (0..num_blocks)
+            .into_par_iter()
+            .map(|j| {
+                let mut terms = kept_options
+                    .iter()
+                    .map(|kept_option| kept_option[j].clone())
+                    .collect::<Vec<_>>();
+                while terms.len() > 1 {
+                    terms = terms
+                        .par_chunks(max_num_terms)
+                        .map(|chunk| {
+                            let mut sum = chunk[0].clone();
+                            for term in &chunk[1..] {
+                                self.key.unchecked_add_assign(&mut sum, term);
+                            }
+                            self.key.message_extract_assign(&mut sum);
+                            sum
+                        })
+                        .collect();
+                }
+                terms.pop().unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        Some(RadixCiphertext::from(blocks))
+    }
+
+    /// Selects `if_true` if the `condition` block encrypts 1, `if_false` if it encrypts 0.
+    ///
+    /// All the inputs are expected to have empty carries.
+    ///
+    /// The output has empty carries.
+    pub(crate) fn unchecked_select_parallelized<PBSOrder: PBSOrderMarker>(
+        &self,
+        condition: &CiphertextBase<PBSOrder>,
+        if_true: &RadixCiphertext<PBSOrder>,
+        if_false: &RadixCiphertext<PBSOrder>,
+    ) -> RadixCiphertext<PBSOrder> {
+        let lut_keep_if_true = self
+            .key
+            .generate_accumulator_bivariate(|x, c| if c == 1 { x } else { 0 });
+        let lut_keep_if_false = self
+            .key
+            .generate_accumulator_bivariate(|x, c| if c == 0 { x } else { 0 });
+
+        let blocks = if_true
+            .blocks
+            .par_iter()
+            .zip(if_false.blocks.par_iter())
+            .map(|(true_block, false_block)| {
+                let (mut kept_true, kept_false) = rayon::join(
+                    || {
+                        self.key.unchecked_apply_lookup_table_bivariate(
+                            true_block,
+                            condition,
+                            &lut_keep_if_true,
+                        )
+                    },
+                    || {
+                        self.key.unchecked_apply_lookup_table_bivariate(
+                            false_block,
+                            condition,
+                            &lut_keep_if_false,
+                        )
+                    },
+                );
+                // At most one of them is non zero, however the degree
+                // has to be brought back to have empty carries
+                self.key.unchecked_add_assign(&mut kept_true, &kept_false);
+                self.key.message_extract_assign(&mut kept_true);
+                kept_true
+            })
+            .collect::<Vec<_>>();
+
+        RadixCiphertext::from(blocks)
+    }
 }
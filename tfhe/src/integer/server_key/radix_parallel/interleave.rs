@@ -0,0 +1,240 @@
+use crate::integer::ciphertext::RadixCiphertext;
+use crate::integer::ServerKey;
+use crate::shortint::PBSOrderMarker;
+
+use rayon::prelude::*;
+
+impl ServerKey {
+    /// Computes homomorphically the interleaving of the bits of two ciphertexts, also known as
+    /// the Morton code or Z-order of `(lhs, rhs)`.
+    ///
+    /// Bit `i` of `lhs` becomes bit `2 * i` of the result, and bit `i` of `rhs` becomes bit
+    /// `2 * i + 1`. So interleaving two ciphertexts of `N` bits (`num_blocks` blocks) results in a
+    /// ciphertext of `2 * N` bits (`2 * num_blocks` blocks), and no bit is lost.
+    ///
+    /// If necessary the carries of the inputs will be cleaned beforehand,
+    /// but their values won't change, the result is returned in a new ciphertext
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ciphertexts don't have the same number of blocks.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // We have 2 * 2 = 4 bits of message
+    /// let size = 2;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, size);
+    ///
+    /// let x = 0b0011u64;
+    /// let y = 0b0101u64;
+    ///
+    /// let ct_x = cks.encrypt(x);
+    /// let ct_y = cks.encrypt(y);
+    ///
+    /// // The result has 2 * 4 = 8 bits
+    /// let ct_res = sks.interleave_bits_parallelized(&ct_x, &ct_y);
+    ///
+    /// // Decrypt:
+    /// let dec: u64 = cks.decrypt(&ct_res);
+    /// assert_eq!(dec, 0b0010_0111);
+    /// ```
+    pub fn interleave_bits_parallelized<PBSOrder: PBSOrderMarker>(
+        &self,
+        lhs: &RadixCiphertext<PBSOrder>,
+        rhs: &RadixCiphertext<PBSOrder>,
+    ) -> RadixCiphertext<PBSOrder> {
+        let mut tmp_lhs: RadixCiphertext<PBSOrder>;
+        let mut tmp_rhs: RadixCiphertext<PBSOrder>;
+
+        let (lhs, rhs) = match (lhs.block_carries_are_empty(), rhs.block_carries_are_empty()) {
+            (true, true) => (lhs, rhs),
+            (true, false) => {
+                tmp_rhs = rhs.clone();
+                self.full_propagate_parallelized(&mut tmp_rhs);
+                (lhs, &tmp_rhs)
+            }
+            (false, true) => {
+                tmp_lhs = lhs.clone();
+                self.full_propagate_parallelized(&mut tmp_lhs);
+                (&tmp_lhs, rhs)
+            }
+            (false, false) => {
+                tmp_lhs = lhs.clone();
+                tmp_rhs = rhs.clone();
+                rayon::join(
+                    || self.full_propagate_parallelized(&mut tmp_lhs),
+                    || self.full_propagate_parallelized(&mut tmp_rhs),
+                );
+                (&tmp_lhs, &tmp_rhs)
+            }
+        };
+        self.unchecked_interleave_bits_parallelized(lhs, rhs)
+    }
+
+    /// Computes homomorphically the interleaving of the bits of two ciphertexts.
+    ///
+    /// See [`Self::interleave_bits_parallelized`] for the bit layout.
+    ///
+    /// Block `j` of the result only holds bits of block `j / 2` of each input: the lower half of
+    /// their bits for even `j`, the upper half for odd `j`. So each block of the result is
+    /// computed with a single bivariate PBS, all of them in parallel.
+    ///
+    /// # Requirements
+    ///
+    /// - The input ciphertexts carry buffers are emtpy / clean
+    /// - The blocks parameter's carry space is at least as big as the message space
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ciphertexts don't have the same number of blocks.
+    pub fn unchecked_interleave_bits_parallelized<PBSOrder: PBSOrderMarker>(
+        &self,
+        lhs: &RadixCiphertext<PBSOrder>,
+        rhs: &RadixCiphertext<PBSOrder>,
+    ) -> RadixCiphertext<PBSOrder> {
+        assert_eq!(
+            lhs.blocks.len(),
+            rhs.blocks.len(),
+            "The ciphertexts must have the same number of blocks"
+        );
+        let num_bits_in_block = self.key.message_modulus.0.ilog2() as u64;
+
+        // Bit t of a block of the result is bit p = j * b + t of the result, which is bit p / 2
+        // of lhs if p is even and of rhs otherwise, at position p / 2 - (j / 2) * b in block
+        // j / 2. This only depends on the parity of j
+        let luts = [0, 1].map(|j_parity| {
+            self.key
+                .generate_accumulator_bivariate(|lhs_block, rhs_block| {
+                    (0..num_bits_in_block)
+                        .map(|t| {
+                            let p = j_parity * num_bits_in_block + t;
+                            let source = if p % 2 == 0 { lhs_block } else { rhs_block };
+                            ((source >> (p / 2)) & 1) << t
+                        })
+                        .sum()
+                })
+        });
+
+        let blocks = (0..2 * lhs.blocks.len())
+            .into_par_iter()
+            .map(|j| {
+                self.key.unchecked_apply_lookup_table_bivariate(
+                    &lhs.blocks[j / 2],
+                    &rhs.blocks[j / 2],
+                    &luts[j % 2],
+                )
+            })
+            .collect::<Vec<_>>();
+        RadixCiphertext::from(blocks)
+    }
+
+    /// Computes homomorphically the inverse of [`Self::interleave_bits_parallelized`], splitting
+    /// the even and odd bits of a ciphertext.
+    ///
+    /// Returns `(even, odd)`, where bit `i` of `even` is bit `2 * i` of the input and bit `i` of
+    /// `odd` is bit `2 * i + 1`. Each of them has half the number of blocks of the input.
+    ///
+    /// If necessary the carries of the input will be cleaned beforehand,
+    /// but its value won't change, the result is returned in new ciphertexts
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ciphertext has an odd number of blocks.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // We have 4 * 2 = 8 bits of message
+    /// let size = 4;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, size);
+    ///
+    /// let msg = 0b0010_0111u64;
+    ///
+    /// let ct = cks.encrypt(msg);
+    ///
+    /// // The results have 4 bits
+    /// let (ct_x, ct_y) = sks.deinterleave_bits_parallelized(&ct);
+    ///
+    /// // Decrypt:
+    /// let x: u64 = cks.decrypt(&ct_x);
+    /// let y: u64 = cks.decrypt(&ct_y);
+    /// assert_eq!(x, 0b0011);
+    /// assert_eq!(y, 0b0101);
+    /// ```
+    pub fn deinterleave_bits_parallelized<PBSOrder: PBSOrderMarker>(
+        &self,
+        ct: &RadixCiphertext<PBSOrder>,
+    ) -> (RadixCiphertext<PBSOrder>, RadixCiphertext<PBSOrder>) {
+        let mut tmp_ct: RadixCiphertext<PBSOrder>;
+        let ct = if ct.block_carries_are_empty() {
+            ct
+        } else {
+            tmp_ct = ct.clone();
+            self.full_propagate_parallelized(&mut tmp_ct);
+            &tmp_ct
+        };
+        self.unchecked_deinterleave_bits_parallelized(ct)
+    }
+
+    /// Computes homomorphically the inverse of [`Self::interleave_bits_parallelized`].
+    ///
+    /// Block `k` of each result is made of bits of blocks `2 * k` and `2 * k + 1` of the input,
+    /// so it is computed with a single bivariate PBS, all of them in parallel.
+    ///
+    /// # Requirements
+    ///
+    /// - The input ciphertext carry buffer is emtpy / clean
+    /// - The blocks parameter's carry space is at least as big as the message space
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ciphertext has an odd number of blocks.
+    pub fn unchecked_deinterleave_bits_parallelized<PBSOrder: PBSOrderMarker>(
+        &self,
+        ct: &RadixCiphertext<PBSOrder>,
+    ) -> (RadixCiphertext<PBSOrder>, RadixCiphertext<PBSOrder>) {
+        assert_eq!(
+            ct.blocks.len() % 2,
+            0,
+            "The ciphertext must have an even number of blocks"
+        );
+        let num_bits_in_block = self.key.message_modulus.0.ilog2() as u64;
+
+        // Bit t of block k of the even (resp. odd) result is bit 2 * t (resp. 2 * t + 1) of
+        // the concatenation of blocks 2 * k and 2 * k + 1 of the input
+        let luts = [0, 1].map(|parity| {
+            self.key
+                .generate_accumulator_bivariate(|low_block, high_block| {
+                    let value = low_block | (high_block << num_bits_in_block);
+                    (0..num_bits_in_block)
+                        .map(|t| ((value >> (2 * t + parity)) & 1) << t)
+                        .sum()
+                })
+        });
+
+        let num_blocks = ct.blocks.len() / 2;
+        let mut blocks = (0..2 * num_blocks)
+            .into_par_iter()
+            .map(|i| {
+                let k = i % num_blocks;
+                self.key.unchecked_apply_lookup_table_bivariate(
+                    &ct.blocks[2 * k],
+                    &ct.blocks[2 * k + 1],
+                    &luts[i / num_blocks],
+                )
+            })
+            .collect::<Vec<_>>();
+        let odd_blocks = blocks.split_off(num_blocks);
+        (
+            RadixCiphertext::from(blocks),
+            RadixCiphertext::from(odd_blocks),
+        )
+    }
+}
@@ -1,26 +1,37 @@
 mod add;
 mod bitwise_op;
 mod comparison;
+mod interleave;
 mod mul;
 mod neg;
+mod ops;
+mod reduce;
 mod rotate;
 mod scalar_add;
+mod scalar_div;
 mod scalar_mul;
 mod scalar_sub;
 mod shift;
 mod sub;
+mod swap_bytes;
 
 #[cfg(test)]
 mod tests;
 
+pub use ops::WithServerKey;
+
 use super::ServerKey;
 use crate::integer::ciphertext::RadixCiphertext;
 use crate::shortint::PBSOrderMarker;
 
+use rayon::prelude::*;
+
 // parallelized versions
 impl ServerKey {
     /// Propagate the carry of the 'index' block to the next one.
     ///
+    /// See [Self::propagate_carry_at].
+    ///
     /// # Example
     ///
     ///```rust
@@ -48,17 +59,61 @@ impl ServerKey {
         &self,
         ctxt: &mut RadixCiphertext<PBSOrder>,
         index: usize,
+    ) {
+        self.propagate_carry_at(ctxt, index)
+    }
+
+    /// Propagate the carry of the block at `block_index` into the next block, leaving the
+    /// other blocks untouched.
+    ///
+    /// After the call, the carry of the block at `block_index` is empty and the next block
+    /// (if any) holds the propagated carry on top of its own content, so its carry may no
+    /// longer be empty: only the block at `block_index` is guaranteed to be clean. The
+    /// encrypted value of the whole ciphertext does not change, and the carry of the last
+    /// block is simply discarded, as in a full propagation.
+    ///
+    /// Applying it to every block, from the least significant to the most significant one,
+    /// is equivalent to [Self::full_propagate_parallelized].
+    ///
+    /// # Example
+    ///
+    ///```rust
+    /// use tfhe::integer::{gen_keys_radix, IntegerCiphertext};
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // Generate the client key and the server key:
+    /// let num_blocks = 4;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, num_blocks);
+    ///
+    /// let msg = 7u64;
+    ///
+    /// let ct1 = cks.encrypt(msg);
+    /// let ct2 = cks.encrypt(msg);
+    ///
+    /// // Compute homomorphically an addition, only the first block overflows:
+    /// let mut ct_res = sks.unchecked_add(&ct1, &ct2);
+    /// sks.propagate_carry_at(&mut ct_res, 0);
+    /// assert!(ct_res.blocks()[0].carry_is_empty());
+    ///
+    /// // Decrypt:
+    /// let res: u64 = cks.decrypt(&ct_res);
+    /// assert_eq!(msg + msg, res);
+    /// ```
+    pub fn propagate_carry_at<PBSOrder: PBSOrderMarker>(
+        &self,
+        ctxt: &mut RadixCiphertext<PBSOrder>,
+        block_index: usize,
     ) {
         let (carry, message) = rayon::join(
-            || self.key.carry_extract(&ctxt.blocks[index]),
-            || self.key.message_extract(&ctxt.blocks[index]),
+            || self.key.carry_extract(&ctxt.blocks[block_index]),
+            || self.key.message_extract(&ctxt.blocks[block_index]),
         );
-        ctxt.blocks[index] = message;
+        ctxt.blocks[block_index] = message;
 
         //add the carry to the next block
-        if index < ctxt.blocks.len() - 1 {
+        if block_index < ctxt.blocks.len() - 1 {
             self.key
-                .unchecked_add_assign(&mut ctxt.blocks[index + 1], &carry);
+                .unchecked_add_assign(&mut ctxt.blocks[block_index + 1], &carry);
         }
     }
 
@@ -69,12 +124,18 @@ impl ServerKey {
     ) {
         let len = ctxt.blocks.len();
         for i in start_index..len {
-            self.propagate_parallelized(ctxt, i);
+            self.propagate_carry_at(ctxt, i);
         }
     }
 
     /// Propagate all the carries.
     ///
+    /// The carries are propagated one block after the other, as the carry of a block has to be
+    /// added to the next one before that block's own carry can be extracted. Only the
+    /// extractions of the carry and the message of each block are done in parallel, so the
+    /// latency grows linearly with the number of blocks. See
+    /// [Self::full_propagate_lookahead] for a propagation in logarithmic depth.
+    ///
     /// # Example
     ///
     ///```rust
@@ -104,4 +165,93 @@ impl ServerKey {
     ) {
         self.partial_propagate_parallelized(ctxt, 0)
     }
+
+    /// Propagate all the carries, using a carry-lookahead scheme.
+    ///
+    /// Instead of propagating the carries one block after the other as
+    /// [Self::full_propagate_parallelized] does, each block is first classified as generating,
+    /// propagating or not outputting a carry, and the carry each block receives is then computed
+    /// with a parallel prefix sum over these classifications, as the parallel addition does. This
+    /// takes a number of PBS levels logarithmic in the number of blocks, at the cost of more
+    /// PBS in total.
+    ///
+    /// The classification requires each block to output a carry of at most 1, so when some
+    /// block carries hold more than that, all blocks first have their carry moved to the next
+    /// block in a single parallel step (repeated if needed).
+    ///
+    /// For `n` blocks, this is a depth of `ceil(log2(n)) + 2` PBS levels (one more when carries
+    /// exceed 1) against `n` for [Self::full_propagate_parallelized], with about `log2(n)`
+    /// times as many PBS. Assuming at least as many threads as blocks, the lookahead version
+    /// only becomes noticeably shallower from about 8 blocks (16 bits with 2 bits of message per
+    /// block), and the sequential one should be preferred below that or when fewer threads are
+    /// available.
+    /// Both are benchmarked in the `misc` group of the integer benchmarks.
+    ///
+    /// If the parameters do not allow it (at least 4 bits of message and carry are required),
+    /// this falls back to [Self::full_propagate_parallelized].
+    ///
+    /// # Example
+    ///
+    ///```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // Generate the client key and the server key:
+    /// let num_blocks = 8;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, num_blocks);
+    ///
+    /// let msg = 10_000u64;
+    ///
+    /// let mut ct1 = cks.encrypt(msg);
+    /// let mut ct2 = cks.encrypt(msg);
+    ///
+    /// // Compute homomorphically an addition:
+    /// let mut ct_res = sks.unchecked_add(&mut ct1, &mut ct2);
+    /// sks.full_propagate_lookahead(&mut ct_res);
+    ///
+    /// // Decrypt:
+    /// let res: u64 = cks.decrypt(&ct_res);
+    /// assert_eq!(msg + msg, res);
+    /// ```
+    pub fn full_propagate_lookahead<PBSOrder: PBSOrderMarker>(
+        &self,
+        ctxt: &mut RadixCiphertext<PBSOrder>,
+    ) {
+        if !self.is_eligible_for_parallel_carryless_add() {
+            self.full_propagate_parallelized(ctxt);
+            return;
+        }
+
+        // A block receiving a carry of 1 outputs a carry of at most 1
+        // as long as it is below twice the message modulus
+        let max_degree = 2 * self.key.message_modulus.0 - 1;
+        while ctxt.blocks.iter().any(|block| block.degree.0 > max_degree) {
+            let (messages, carries): (Vec<_>, Vec<_>) = ctxt
+                .blocks
+                .par_iter()
+                .map(|block| {
+                    rayon::join(
+                        || self.key.message_extract(block),
+                        || self.key.carry_extract(block),
+                    )
+                })
+                .unzip();
+            ctxt.blocks = messages;
+            // The carry of the last block is discarded
+            for (block, carry) in ctxt.blocks[1..].iter_mut().zip(carries.iter()) {
+                self.key.unchecked_add_assign(block, carry);
+            }
+        }
+
+        let carry_out = self.generate_init_carry_array(&ctxt.blocks);
+        let input_carries = self.compute_input_carries_low_latency(carry_out);
+
+        ctxt.blocks
+            .par_iter_mut()
+            .zip(input_carries.par_iter())
+            .for_each(|(block, input_carry)| {
+                self.key.unchecked_add_assign(block, input_carry);
+                self.key.message_extract_assign(block);
+            });
+    }
 }
@@ -671,4 +671,183 @@ impl ServerKey {
 
         self.unchecked_mul_assign_parallelized(lhs, rhs);
     }
+
+    /// Computes homomorphically a multiplication, detecting overflows.
+    ///
+    /// Returns the product truncated to the number of blocks of the inputs (the same value as
+    /// [`Self::mul_parallelized`]), and an encrypted boolean that is true if the full product
+    /// does not fit in that many blocks.
+    ///
+    /// The full product is computed on twice the number of blocks, the flag is then whether its
+    /// high half, which is discarded from the result, is non zero. This makes this operation
+    /// more expensive than the truncated multiplication.
+    ///
+    /// # Warning
+    ///
+    /// - Multithreaded
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // Generate the client key and the server key:
+    /// let num_blocks = 4;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, num_blocks);
+    ///
+    /// let clear_1 = 170;
+    /// let clear_2 = 6;
+    ///
+    /// // Encrypt two messages
+    /// let ctxt_1 = cks.encrypt(clear_1);
+    /// let ctxt_2 = cks.encrypt(clear_2);
+    ///
+    /// // Compute homomorphically a multiplication
+    /// let (ct_res, ct_overflow) = sks.overflowing_mul_parallelized(&ctxt_1, &ctxt_2);
+    /// // Decrypt
+    /// let res: u64 = cks.decrypt(&ct_res);
+    /// let overflow: u64 = cks.decrypt(&ct_overflow);
+    /// assert_eq!((clear_1 * clear_2) % 256, res);
+    /// assert_eq!(1, overflow);
+    /// ```
+    pub fn overflowing_mul_parallelized<PBSOrder: PBSOrderMarker>(
+        &self,
+        ct1: &RadixCiphertext<PBSOrder>,
+        ct2: &RadixCiphertext<PBSOrder>,
+    ) -> (RadixCiphertext<PBSOrder>, RadixCiphertext<PBSOrder>) {
+        let num_blocks = ct1.blocks.len();
+
+        let (mut ct_res, extended_ct2) = rayon::join(
+            || self.extend_radix_with_trivial_zero_blocks_msb(ct1, num_blocks),
+            || self.extend_radix_with_trivial_zero_blocks_msb(ct2, num_blocks),
+        );
+        self.mul_assign_parallelized(&mut ct_res, &extended_ct2);
+
+        let high = self.trim_radix_blocks_lsb(&ct_res, num_blocks);
+        self.trim_radix_blocks_msb_assign(&mut ct_res, num_blocks);
+
+        let zero = self.create_trivial_zero_radix(num_blocks);
+        let ct_overflow = self.gt_parallelized(&high, &zero);
+
+        (ct_res, ct_overflow)
+    }
+
+    /// Computes homomorphically the power of a ciphertext by a clear exponent.
+    ///
+    /// The result is `base^exp` truncated to the number of blocks of `base`, i.e. computed
+    /// modulo the ciphertext modulus, like repeated [`Self::mul_parallelized`] would. `base^0`
+    /// is 1, including for a base encrypting 0.
+    ///
+    /// The power is computed by square-and-multiply, scanning the bits of `exp` from the most
+    /// significant one. As `exp` is clear only the multiplications to perform depend on it:
+    /// `floor(log2(exp))` squarings and one more multiplication per bit set in `exp` after the
+    /// most significant one, so at most `2 * log2(exp)` multiplications.
+    ///
+    /// # Warning
+    ///
+    /// - Multithreaded
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // Generate the client key and the server key:
+    /// let num_blocks = 4;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, num_blocks);
+    ///
+    /// let clear = 3u64;
+    /// let exp = 5;
+    ///
+    /// // Encrypt a message
+    /// let ctxt = cks.encrypt(clear);
+    ///
+    /// // Compute homomorphically a power
+    /// let ct_res = sks.scalar_pow_parallelized(&ctxt, exp);
+    /// // Decrypt
+    /// let res: u64 = cks.decrypt(&ct_res);
+    /// assert_eq!(clear.pow(exp as u32) % 256, res);
+    /// ```
+    pub fn scalar_pow_parallelized<PBSOrder: PBSOrderMarker>(
+        &self,
+        base: &RadixCiphertext<PBSOrder>,
+        exp: u64,
+    ) -> RadixCiphertext<PBSOrder> {
+        if exp == 0 {
+            return self.create_trivial_radix(1u64, base.blocks.len());
+        }
+
+        let mut ct_res = base.clone();
+        for i in (0..exp.ilog2()).rev() {
+            ct_res = self.mul_parallelized(&ct_res, &ct_res);
+            if (exp >> i) & 1 == 1 {
+                ct_res = self.mul_parallelized(&ct_res, base);
+            }
+        }
+
+        if !ct_res.block_carries_are_empty() {
+            self.full_propagate_parallelized(&mut ct_res);
+        }
+        ct_res
+    }
+
+    /// Computes homomorphically the sum of the squares of a sequence of ciphertexts.
+    ///
+    /// Unlike the other arithmetic operations, the result is not truncated to the width of the
+    /// inputs: it is widened so that the exact sum always fits. For `k` inputs of at most `n`
+    /// bits, each square has at most `2 * n` bits, and the sum at most
+    /// `2 * n + ceil(log2(k))` bits, so the result has as many blocks as needed to hold that many
+    /// bits. Returns `None` if there are no inputs.
+    ///
+    /// Each input is extended to the result width and squared, the squares being computed in
+    /// parallel, then they are summed with a balanced tree of additions.
+    ///
+    /// # Warning
+    ///
+    /// - Multithreaded
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // Generate the client key and the server key:
+    /// let num_blocks = 4;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, num_blocks);
+    ///
+    /// let clears = [200u64, 255, 17];
+    /// let cts = clears.iter().map(|c| cks.encrypt(*c)).collect::<Vec<_>>();
+    ///
+    /// // Compute homomorphically a sum of squares
+    /// let ct_res = sks.sum_of_squares_parallelized(&cts).unwrap();
+    /// // The result is wide enough to hold the exact sum
+    /// let res: u64 = cks.decrypt(&ct_res);
+    /// assert_eq!(clears.iter().map(|c| c * c).sum::<u64>(), res);
+    /// ```
+    pub fn sum_of_squares_parallelized<PBSOrder: PBSOrderMarker>(
+        &self,
+        cts: &[RadixCiphertext<PBSOrder>],
+    ) -> Option<RadixCiphertext<PBSOrder>> {
+        let max_num_blocks = cts.iter().map(|ct| ct.blocks.len()).max()?;
+        let num_bits_in_block = self.key.message_modulus.0.ilog2() as usize;
+        let num_input_bits = max_num_blocks * num_bits_in_block;
+        let num_output_bits = 2 * num_input_bits + cts.len().next_power_of_two().ilog2() as usize;
+        let num_output_blocks = (num_output_bits + num_bits_in_block - 1) / num_bits_in_block;
+
+        let squares = cts
+            .par_iter()
+            .map(|ct| {
+                let extended = self.extend_radix_with_trivial_zero_blocks_msb(
+                    ct,
+                    num_output_blocks - ct.blocks.len(),
+                );
+                self.mul_parallelized(&extended, &extended)
+            })
+            .collect::<Vec<_>>();
+
+        self.default_binary_op_seq_parallelized(&squares, ServerKey::add_parallelized)
+    }
 }
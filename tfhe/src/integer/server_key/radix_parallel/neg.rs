@@ -2,6 +2,10 @@ use crate::integer::ciphertext::RadixCiphertext;
 use crate::integer::ServerKey;
 use crate::shortint::PBSOrderMarker;
 
+use super::add::AddExtraOne;
+
+use rayon::prelude::*;
+
 impl ServerKey {
     /// Homomorphically computes the opposite of a ciphertext encrypting an integer message.
     ///
@@ -78,16 +82,184 @@ impl ServerKey {
         &self,
         ctxt: &RadixCiphertext<PBSOrder>,
     ) -> RadixCiphertext<PBSOrder> {
-        let mut tmp_ctxt: RadixCiphertext<PBSOrder>;
+        let mut ct_res = ctxt.clone();
+        self.neg_assign_parallelized(&mut ct_res);
+        ct_res
+    }
 
-        let mut ctxt = if ctxt.block_carries_are_empty() {
-            self.unchecked_neg(ctxt)
+    /// Homomorphically computes the opposite of a ciphertext encrypting an integer message.
+    ///
+    /// The result is assigned to the input ciphertext.
+    ///
+    /// The negation is computed as the two's complement of the input, that is
+    /// `bitwise_not(ctxt) + 1`. When the parameters allow it, the `+ 1` is folded into the
+    /// parallel carry propagation of the addition, so that no extra carry propagation is needed.
+    ///
+    /// This function, like all "default" operations (i.e. not smart, checked or unchecked), will
+    /// check that the input ciphertexts block carries are empty and clears them if it's not the
+    /// case and the operation requires it. It outputs a ciphertext whose block carries are always
+    /// empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // We have 4 * 2 = 8 bits of message
+    /// let size = 4;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, size);
+    ///
+    /// let msg = 1u64;
+    ///
+    /// // Encrypt a message:
+    /// let mut ctxt = cks.encrypt(msg);
+    ///
+    /// // Compute homomorphically a negation
+    /// sks.neg_assign_parallelized(&mut ctxt);
+    ///
+    /// // Decrypt
+    /// let dec: u64 = cks.decrypt(&ctxt);
+    /// assert_eq!(255, dec);
+    /// ```
+    pub fn neg_assign_parallelized<PBSOrder: PBSOrderMarker>(
+        &self,
+        ctxt: &mut RadixCiphertext<PBSOrder>,
+    ) {
+        if !ctxt.block_carries_are_empty() {
+            self.full_propagate_parallelized(ctxt);
+        }
+
+        if self.is_eligible_for_parallel_carryless_add() {
+            // neg(a) = bitwise_not(a) + 1
+            //
+            // The extra one is added by the add impl while it computes
+            // the carries, so adding a trivial zero is enough
+            self.bitnot_assign_parallelized(ctxt);
+            let zero = self.create_trivial_zero_radix(ctxt.blocks.len());
+            self.unchecked_add_assign_parallelized_low_latency(ctxt, &zero, AddExtraOne::Yes);
         } else {
-            tmp_ctxt = ctxt.clone();
-            self.full_propagate_parallelized(&mut tmp_ctxt);
-            self.unchecked_neg(&tmp_ctxt)
+            self.unchecked_neg_assign(ctxt);
+            self.full_propagate_parallelized(ctxt);
+        }
+    }
+
+    /// Homomorphically computes the opposite of a ciphertext if an encrypted condition is true,
+    /// and leaves it unchanged otherwise.
+    ///
+    /// The condition is an encrypted boolean, as returned by comparisons, i.e. a ciphertext
+    /// encrypting 0 or 1. The result is returned as a new ciphertext.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // We have 4 * 2 = 8 bits of message
+    /// let size = 4;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, size);
+    ///
+    /// let msg = 1u64;
+    ///
+    /// // Encrypt a message and the conditions:
+    /// let ctxt = cks.encrypt(msg);
+    /// let ct_true = cks.encrypt(1u64);
+    /// let ct_false = cks.encrypt(0u64);
+    ///
+    /// // Compute homomorphically conditional negations
+    /// let ct_negated = sks.conditional_negate_parallelized(&ctxt, &ct_true);
+    /// let ct_kept = sks.conditional_negate_parallelized(&ctxt, &ct_false);
+    ///
+    /// // Decrypt
+    /// let dec_negated: u64 = cks.decrypt(&ct_negated);
+    /// let dec_kept: u64 = cks.decrypt(&ct_kept);
+    /// assert_eq!(255, dec_negated);
+    /// assert_eq!(1, dec_kept);
+    /// ```
+    pub fn conditional_negate_parallelized<PBSOrder: PBSOrderMarker>(
+        &self,
+        ctxt: &RadixCiphertext<PBSOrder>,
+        condition: &RadixCiphertext<PBSOrder>,
+    ) -> RadixCiphertext<PBSOrder> {
+        let mut ct_res = ctxt.clone();
+        self.conditional_negate_assign_parallelized(&mut ct_res, condition);
+        ct_res
+    }
+
+    /// Homomorphically computes the opposite of a ciphertext if an encrypted condition is true,
+    /// and leaves it unchanged otherwise.
+    ///
+    /// The result is assigned to the input ciphertext.
+    ///
+    /// This is the two's complement negation made branchless: each block is bitwise negated
+    /// only when the condition is true (one bivariate PBS per block), then the condition itself
+    /// is added, so that one is only added when negating.
+    ///
+    /// This function, like all "default" operations (i.e. not smart, checked or unchecked), will
+    /// check that the input ciphertexts block carries are empty and clears them if it's not the
+    /// case and the operation requires it. It outputs a ciphertext whose block carries are always
+    /// empty.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // We have 4 * 2 = 8 bits of message
+    /// let size = 4;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, size);
+    ///
+    /// let msg = 1u64;
+    ///
+    /// // Encrypt a message and the condition:
+    /// let mut ctxt = cks.encrypt(msg);
+    /// let ct_true = cks.encrypt(1u64);
+    ///
+    /// // Compute homomorphically a conditional negation
+    /// sks.conditional_negate_assign_parallelized(&mut ctxt, &ct_true);
+    ///
+    /// // Decrypt
+    /// let dec: u64 = cks.decrypt(&ctxt);
+    /// assert_eq!(255, dec);
+    /// ```
+    pub fn conditional_negate_assign_parallelized<PBSOrder: PBSOrderMarker>(
+        &self,
+        ctxt: &mut RadixCiphertext<PBSOrder>,
+        condition: &RadixCiphertext<PBSOrder>,
+    ) {
+        let mut tmp_condition: RadixCiphertext<PBSOrder>;
+        let condition = if condition.block_carries_are_empty() {
+            condition
+        } else {
+            tmp_condition = condition.clone();
+            self.full_propagate_parallelized(&mut tmp_condition);
+            &tmp_condition
         };
-        self.full_propagate_parallelized(&mut ctxt);
-        ctxt
+        if !ctxt.block_carries_are_empty() {
+            self.full_propagate_parallelized(ctxt);
+        }
+
+        // The condition is a boolean, so only its first block can be non zero
+        let condition_block = &condition.blocks[0];
+
+        let message_modulus = self.key.message_modulus.0 as u64;
+        let lut = self.key.generate_accumulator_bivariate(|x, c| {
+            if c == 1 {
+                message_modulus - 1 - x
+            } else {
+                x
+            }
+        });
+        ctxt.blocks.par_iter_mut().for_each(|block| {
+            *block = self
+                .key
+                .unchecked_apply_lookup_table_bivariate(block, condition_block, &lut);
+        });
+
+        let mut one_if_negated = self.create_trivial_zero_radix(ctxt.blocks.len());
+        one_if_negated.blocks[0] = condition_block.clone();
+        self.add_assign_parallelized(ctxt, &one_if_negated);
     }
 }
@@ -0,0 +1,142 @@
+use crate::integer::ciphertext::RadixCiphertext;
+use crate::integer::ServerKey;
+use crate::shortint::PBSOrderMarker;
+
+/// A radix ciphertext bound to the server key used to compute on it.
+///
+/// Operators cannot take the server key as a parameter, binding the key to the ciphertext
+/// makes it possible to use `+`, `-` and `*` (and their assign variants). They respectively
+/// dispatch to [`ServerKey::add_parallelized`], [`ServerKey::sub_parallelized`] and
+/// [`ServerKey::mul_parallelized`], so they have the same guarantees as these "default"
+/// operations.
+///
+/// When both operands are bound, the server key of the left hand side operand is used.
+///
+/// # Example
+///
+/// ```rust
+/// use tfhe::integer::gen_keys_radix;
+/// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+///
+/// // Generate the client key and the server key:
+/// let num_blocks = 4;
+/// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, num_blocks);
+///
+/// let msg1 = 14;
+/// let msg2 = 97;
+///
+/// let ct1 = sks.with_ciphertext(cks.encrypt(msg1));
+/// let ct2 = sks.with_ciphertext(cks.encrypt(msg2));
+///
+/// // Compute homomorphically (msg1 + msg2) * msg1 - msg2:
+/// let ct_res = &(&(&ct1 + &ct2) * &ct1) - &ct2;
+///
+/// // Decrypt:
+/// let dec_result: u64 = cks.decrypt(ct_res.ciphertext());
+/// assert_eq!(dec_result, ((msg1 + msg2) * msg1 - msg2) % 256);
+/// ```
+#[derive(Clone)]
+pub struct WithServerKey<'a, PBSOrder: PBSOrderMarker> {
+    server_key: &'a ServerKey,
+    ct: RadixCiphertext<PBSOrder>,
+}
+
+impl<'a, PBSOrder: PBSOrderMarker> WithServerKey<'a, PBSOrder> {
+    /// Returns the server key the ciphertext is bound to.
+    pub fn server_key(&self) -> &'a ServerKey {
+        self.server_key
+    }
+
+    /// Returns the bound ciphertext.
+    pub fn ciphertext(&self) -> &RadixCiphertext<PBSOrder> {
+        &self.ct
+    }
+
+    /// Unbinds the ciphertext from the server key, returning it.
+    pub fn into_inner(self) -> RadixCiphertext<PBSOrder> {
+        self.ct
+    }
+}
+
+impl ServerKey {
+    /// Binds a ciphertext to this server key, so that operators can be used on it.
+    ///
+    /// See [`WithServerKey`].
+    pub fn with_ciphertext<PBSOrder: PBSOrderMarker>(
+        &self,
+        ct: RadixCiphertext<PBSOrder>,
+    ) -> WithServerKey<'_, PBSOrder> {
+        WithServerKey {
+            server_key: self,
+            ct,
+        }
+    }
+}
+
+macro_rules! impl_binary_op_with_server_key {
+    ($op:ident, $op_fn:ident, $op_assign:ident, $op_assign_fn:ident, $sks_fn:ident, $sks_assign_fn:ident) => {
+        impl<'a, 'b, PBSOrder: PBSOrderMarker> std::ops::$op<&'b RadixCiphertext<PBSOrder>>
+            for &WithServerKey<'a, PBSOrder>
+        {
+            type Output = WithServerKey<'a, PBSOrder>;
+
+            fn $op_fn(self, rhs: &'b RadixCiphertext<PBSOrder>) -> Self::Output {
+                WithServerKey {
+                    server_key: self.server_key,
+                    ct: self.server_key.$sks_fn(&self.ct, rhs),
+                }
+            }
+        }
+
+        impl<'a, 'b, PBSOrder: PBSOrderMarker> std::ops::$op<&WithServerKey<'b, PBSOrder>>
+            for &WithServerKey<'a, PBSOrder>
+        {
+            type Output = WithServerKey<'a, PBSOrder>;
+
+            fn $op_fn(self, rhs: &WithServerKey<'b, PBSOrder>) -> Self::Output {
+                self.$op_fn(&rhs.ct)
+            }
+        }
+
+        impl<'a, 'b, PBSOrder: PBSOrderMarker> std::ops::$op_assign<&'b RadixCiphertext<PBSOrder>>
+            for WithServerKey<'a, PBSOrder>
+        {
+            fn $op_assign_fn(&mut self, rhs: &'b RadixCiphertext<PBSOrder>) {
+                self.server_key.$sks_assign_fn(&mut self.ct, rhs);
+            }
+        }
+
+        impl<'a, 'b, PBSOrder: PBSOrderMarker> std::ops::$op_assign<&WithServerKey<'b, PBSOrder>>
+            for WithServerKey<'a, PBSOrder>
+        {
+            fn $op_assign_fn(&mut self, rhs: &WithServerKey<'b, PBSOrder>) {
+                self.server_key.$sks_assign_fn(&mut self.ct, &rhs.ct);
+            }
+        }
+    };
+}
+
+impl_binary_op_with_server_key!(
+    Add,
+    add,
+    AddAssign,
+    add_assign,
+    add_parallelized,
+    add_assign_parallelized
+);
+impl_binary_op_with_server_key!(
+    Sub,
+    sub,
+    SubAssign,
+    sub_assign,
+    sub_parallelized,
+    sub_assign_parallelized
+);
+impl_binary_op_with_server_key!(
+    Mul,
+    mul,
+    MulAssign,
+    mul_assign,
+    mul_parallelized,
+    mul_assign_parallelized
+);
@@ -0,0 +1,129 @@
+use crate::integer::ciphertext::RadixCiphertext;
+use crate::integer::ServerKey;
+use crate::shortint::{CiphertextBase, PBSOrderMarker};
+
+use rayon::prelude::*;
+
+impl ServerKey {
+    /// Computes homomorphically the OR of a sequence of encrypted booleans.
+    ///
+    /// Each input must encrypt either 0 or 1, as the results of comparisons do. The result is an
+    /// encrypted boolean with as many blocks as the first input, or `None` if there are no
+    /// inputs.
+    ///
+    /// As booleans only use their first block, as many of them as the max degree of the server key
+    /// allows are summed without any PBS, and a single PBS per such chunk tells whether the
+    /// sum is non zero. The chunks are processed in parallel, and the process is repeated on the
+    /// chunk results until a single boolean remains. This is much cheaper than a tree of
+    /// [`Self::bitor_parallelized`], which needs a PBS per block for each pair of inputs.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // Generate the client key and the server key:
+    /// let num_blocks = 4;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, num_blocks);
+    ///
+    /// let booleans = [0u64, 0, 1, 0]
+    ///     .iter()
+    ///     .map(|b| cks.encrypt(*b))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// let ct_res = sks.or_reduce(&booleans).unwrap();
+    ///
+    /// // Decrypt:
+    /// let dec: u64 = cks.decrypt(&ct_res);
+    /// assert_eq!(dec, 1);
+    /// ```
+    pub fn or_reduce<PBSOrder: PBSOrderMarker>(
+        &self,
+        booleans: &[RadixCiphertext<PBSOrder>],
+    ) -> Option<RadixCiphertext<PBSOrder>> {
+        self.boolean_reduce(booleans, |sum, _| (sum != 0) as u64)
+    }
+
+    /// Computes homomorphically the AND of a sequence of encrypted booleans.
+    ///
+    /// Each input must encrypt either 0 or 1, as the results of comparisons do. The result is an
+    /// encrypted boolean with as many blocks as the first input, or `None` if there are no
+    /// inputs.
+    ///
+    /// See [`Self::or_reduce`] for how the reduction is computed, here a chunk results in true
+    /// if its sum equals its number of booleans.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // Generate the client key and the server key:
+    /// let num_blocks = 4;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, num_blocks);
+    ///
+    /// let booleans = [1u64, 1, 0, 1]
+    ///     .iter()
+    ///     .map(|b| cks.encrypt(*b))
+    ///     .collect::<Vec<_>>();
+    ///
+    /// let ct_res = sks.and_reduce(&booleans).unwrap();
+    ///
+    /// // Decrypt:
+    /// let dec: u64 = cks.decrypt(&ct_res);
+    /// assert_eq!(dec, 0);
+    /// ```
+    pub fn and_reduce<PBSOrder: PBSOrderMarker>(
+        &self,
+        booleans: &[RadixCiphertext<PBSOrder>],
+    ) -> Option<RadixCiphertext<PBSOrder>> {
+        self.boolean_reduce(booleans, |sum, num_terms| (sum == num_terms) as u64)
+    }
+
+    /// `f` maps the sum of a chunk of booleans and the number of booleans in the chunk to the
+    /// reduced boolean
+    fn boolean_reduce<PBSOrder, F>(
+        &self,
+        booleans: &[RadixCiphertext<PBSOrder>],
+        f: F,
+    ) -> Option<RadixCiphertext<PBSOrder>>
+    where
+        PBSOrder: PBSOrderMarker,
+        F: Fn(u64, u64) -> u64 + Sync,
+    {
+        let num_blocks = booleans.first()?.blocks.len();
+        // Each boolean adds at most one to the degree of the sum, which must stay within the
+        // max degree of the server key
+        let max_num_terms = self.key.max_degree.0.max(2);
+
+        let mut blocks: Vec<CiphertextBase<PBSOrder>> = booleans
+            .iter()
+            .map(|boolean| boolean.blocks[0].clone())
+            .collect();
+        if blocks.len() == 1 {
+            // Still go through a PBS, to output a block with empty carries
+            let lut = self.key.generate_accumulator(|x| f(x, 1));
+            self.key.apply_lookup_table_assign(&mut blocks[0], &lut);
+        }
+        while blocks.len() > 1 {
+            blocks = blocks
+                .par_chunks(max_num_terms)
+                .map(|chunk| {
+                    let num_terms = chunk.len() as u64;
+                    let mut sum = chunk[0].clone();
+                    for block in &chunk[1..] {
+                        self.key.unchecked_add_assign(&mut sum, block);
+                    }
+                    let lut = self.key.generate_accumulator(|x| f(x, num_terms));
+                    self.key.apply_lookup_table(&sum, &lut)
+                })
+                .collect();
+        }
+
+        let mut result = self.create_trivial_zero_radix(num_blocks);
+        result.blocks[0] = blocks.pop().unwrap();
+        Some(result)
+    }
+}
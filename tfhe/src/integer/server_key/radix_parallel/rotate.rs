@@ -662,4 +662,170 @@ impl ServerKey {
 
         debug_assert!(ct.block_carries_are_empty());
     }
+
+    //======================================================================
+    //                Rotate by an encrypted amount
+    //======================================================================
+
+    /// Computes homomorphically a rotation of bits by an encrypted amount.
+    ///
+    /// Shifts the bits to the left by the amount encrypted in `amount`,
+    /// wrapping the truncated bits to the end of the resulting integer.
+    ///
+    /// This is a barrel shifter: for each bit `i` of `amount`, the current value is
+    /// rotated by the clear amount `2^i`, and the encrypted bit selects between the rotated
+    /// and unrotated values. The cost is fixed and does not depend on the encrypted value:
+    /// one clear rotation and one selection per bit of `amount`, applied one after the other.
+    /// So the depth is logarithmic in the largest amount `amount` can encrypt.
+    ///
+    /// If necessary the carries of the inputs will be cleaned beforehand,
+    /// the result is returned in a new ciphertext
+    ///
+    /// # Warning
+    ///
+    /// - Multithreaded
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // We have 4 * 2 = 8 bits of message
+    /// let size = 4;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, size);
+    ///
+    /// let msg = 128u8;
+    /// let n = 3u8;
+    ///
+    /// let ct = cks.encrypt(msg as u64);
+    /// let ct_n = cks.encrypt(n as u64);
+    ///
+    /// let ct_res = sks.rotate_left_parallelized(&ct, &ct_n);
+    ///
+    /// // Decrypt:
+    /// let dec: u64 = cks.decrypt(&ct_res);
+    /// assert_eq!(msg.rotate_left(n as u32) as u64, dec);
+    /// ```
+    pub fn rotate_left_parallelized<PBSOrder: PBSOrderMarker>(
+        &self,
+        ct: &RadixCiphertext<PBSOrder>,
+        amount: &RadixCiphertext<PBSOrder>,
+    ) -> RadixCiphertext<PBSOrder> {
+        let mut result = ct.clone();
+        self.rotate_left_assign_parallelized(&mut result, amount);
+        result
+    }
+
+    pub fn rotate_left_assign_parallelized<PBSOrder: PBSOrderMarker>(
+        &self,
+        ct: &mut RadixCiphertext<PBSOrder>,
+        amount: &RadixCiphertext<PBSOrder>,
+    ) {
+        self.rotate_by_encrypted_amount_assign(ct, amount, RotationDirection::Left);
+    }
+
+    /// Computes homomorphically a rotation of bits by an encrypted amount.
+    ///
+    /// Shifts the bits to the right by the amount encrypted in `amount`,
+    /// wrapping the truncated bits to the beginning of the resulting integer.
+    ///
+    /// See [`Self::rotate_left_parallelized`] for how it is computed.
+    ///
+    /// # Warning
+    ///
+    /// - Multithreaded
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // We have 4 * 2 = 8 bits of message
+    /// let size = 4;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, size);
+    ///
+    /// let msg = 128u8;
+    /// let n = 3u8;
+    ///
+    /// let ct = cks.encrypt(msg as u64);
+    /// let ct_n = cks.encrypt(n as u64);
+    ///
+    /// let ct_res = sks.rotate_right_parallelized(&ct, &ct_n);
+    ///
+    /// // Decrypt:
+    /// let dec: u64 = cks.decrypt(&ct_res);
+    /// assert_eq!(msg.rotate_right(n as u32) as u64, dec);
+    /// ```
+    pub fn rotate_right_parallelized<PBSOrder: PBSOrderMarker>(
+        &self,
+        ct: &RadixCiphertext<PBSOrder>,
+        amount: &RadixCiphertext<PBSOrder>,
+    ) -> RadixCiphertext<PBSOrder> {
+        let mut result = ct.clone();
+        self.rotate_right_assign_parallelized(&mut result, amount);
+        result
+    }
+
+    pub fn rotate_right_assign_parallelized<PBSOrder: PBSOrderMarker>(
+        &self,
+        ct: &mut RadixCiphertext<PBSOrder>,
+        amount: &RadixCiphertext<PBSOrder>,
+    ) {
+        self.rotate_by_encrypted_amount_assign(ct, amount, RotationDirection::Right);
+    }
+
+    fn rotate_by_encrypted_amount_assign<PBSOrder: PBSOrderMarker>(
+        &self,
+        ct: &mut RadixCiphertext<PBSOrder>,
+        amount: &RadixCiphertext<PBSOrder>,
+        direction: RotationDirection,
+    ) {
+        let mut tmp_amount: RadixCiphertext<PBSOrder>;
+        let amount = if amount.block_carries_are_empty() {
+            amount
+        } else {
+            tmp_amount = amount.clone();
+            self.full_propagate_parallelized(&mut tmp_amount);
+            &tmp_amount
+        };
+        if !ct.block_carries_are_empty() {
+            self.full_propagate_parallelized(ct);
+        }
+
+        let num_bits_in_message = self.key.message_modulus.0.ilog2() as usize;
+        let total_num_bits = (num_bits_in_message * ct.blocks.len()) as u64;
+
+        // Extract each bit of the amount in its own block
+        let amount_bits = (0..num_bits_in_message * amount.blocks.len())
+            .into_par_iter()
+            .map(|i| {
+                let bit_in_block = i % num_bits_in_message;
+                let lut = self.key.generate_accumulator(|x| (x >> bit_in_block) & 1);
+                self.key
+                    .apply_lookup_table(&amount.blocks[i / num_bits_in_message], &lut)
+            })
+            .collect::<Vec<_>>();
+
+        for (i, amount_bit) in amount_bits.iter().enumerate() {
+            // 2^i may not fit in a u64 for large amounts, but only
+            // 2^i mod total_num_bits matters
+            let n = (0..i).fold(1u64, |n, _| (n * 2) % total_num_bits);
+            if n == 0 {
+                continue;
+            }
+
+            let rotated = match direction {
+                RotationDirection::Left => self.unchecked_scalar_rotate_left_parallelized(ct, n),
+                RotationDirection::Right => self.unchecked_scalar_rotate_right_parallelized(ct, n),
+            };
+            *ct = self.unchecked_select_parallelized(amount_bit, &rotated, ct);
+        }
+    }
+}
+
+enum RotationDirection {
+    Left,
+    Right,
 }
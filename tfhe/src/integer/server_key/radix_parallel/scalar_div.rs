@@ -0,0 +1,151 @@
+use crate::integer::ciphertext::RadixCiphertext;
+use crate::integer::ServerKey;
+use crate::shortint::PBSOrderMarker;
+
+use rayon::prelude::*;
+
+impl ServerKey {
+    /// Computes homomorphically the division of a ciphertext by a clear divisor.
+    ///
+    /// The result is the quotient rounded towards zero, as the `/` operator of unsigned
+    /// integers, with as many blocks as the input.
+    ///
+    /// The quotient is computed by long division: for each shift `i` such that `divisor << i`
+    /// fits in the ciphertext, from the largest one, the remainder is compared to `divisor << i`
+    /// and the shifted divisor subtracted when it is lower, bit `i` of the quotient being the
+    /// result of the comparison. As the divisor is clear, only these comparisons are computed on
+    /// ciphertexts, so the larger the divisor the cheaper the division.
+    ///
+    /// If necessary the carries of the input will be cleaned beforehand,
+    /// but its value won't change, the result is returned in a new ciphertext
+    ///
+    /// # Panics
+    ///
+    /// Panics if `divisor` is 0, or if the ciphertext has more than 64 bits of message.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // Generate the client key and the server key:
+    /// let num_blocks = 4;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, num_blocks);
+    ///
+    /// let msg = 230u64;
+    /// let divisor = 7;
+    ///
+    /// let ct = cks.encrypt(msg);
+    ///
+    /// // Compute homomorphically a division by a clear divisor
+    /// let ct_res = sks.scalar_div_parallelized(&ct, divisor);
+    ///
+    /// // Decrypt:
+    /// let dec: u64 = cks.decrypt(&ct_res);
+    /// assert_eq!(msg / divisor, dec);
+    /// ```
+    pub fn scalar_div_parallelized<PBSOrder: PBSOrderMarker>(
+        &self,
+        ct: &RadixCiphertext<PBSOrder>,
+        divisor: u64,
+    ) -> RadixCiphertext<PBSOrder> {
+        assert_ne!(divisor, 0, "attempt to divide by zero");
+        let num_blocks = ct.blocks.len();
+        let total_num_bits = self.key.message_modulus.0.ilog2() as usize * num_blocks;
+        assert!(
+            total_num_bits <= 64,
+            "The ciphertext width ({total_num_bits} bits) must be at most 64 bits"
+        );
+
+        let mut remainder = ct.clone();
+        if !remainder.block_carries_are_empty() {
+            self.full_propagate_parallelized(&mut remainder);
+        }
+        let mut quotient = self.create_trivial_zero_radix(num_blocks);
+
+        for i in (0..total_num_bits).rev() {
+            let shifted_divisor = (divisor as u128) << i;
+            if shifted_divisor >= 1u128 << total_num_bits {
+                continue;
+            }
+            let shifted_divisor = shifted_divisor as u64;
+
+            let ct_shifted_divisor = self.create_trivial_radix(shifted_divisor, num_blocks);
+            let is_ge = self.ge_parallelized(&remainder, &ct_shifted_divisor);
+
+            let (to_sub, to_add) = rayon::join(
+                || self.scalar_mul_parallelized(&is_ge, shifted_divisor),
+                || self.scalar_mul_parallelized(&is_ge, 1u64 << i),
+            );
+            rayon::join(
+                || self.sub_assign_parallelized(&mut remainder, &to_sub),
+                || self.add_assign_parallelized(&mut quotient, &to_add),
+            );
+        }
+
+        quotient
+    }
+
+    /// Computes homomorphically the mean of a sequence of ciphertexts.
+    ///
+    /// The result is the sum of the inputs divided by their number, rounded towards zero as an
+    /// integer division is, so it has as many blocks as the widest input. Returns `None` if there
+    /// are no inputs.
+    ///
+    /// The sum is computed on enough blocks to never overflow, i.e. with `ceil(log2(count))`
+    /// more bits than the inputs, and then divided by the count of inputs with
+    /// [`Self::scalar_div_parallelized`]. As the count is the length of the slice, it is public
+    /// and the division is by a clear divisor, which is much cheaper than a division by a
+    /// ciphertext.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the widened sum has more than 64 bits of message.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // Generate the client key and the server key:
+    /// let num_blocks = 4;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, num_blocks);
+    ///
+    /// let clears = [200u64, 255, 17];
+    /// let cts = clears.iter().map(|c| cks.encrypt(*c)).collect::<Vec<_>>();
+    ///
+    /// // Compute homomorphically the mean
+    /// let ct_res = sks.mean_parallelized(&cts).unwrap();
+    ///
+    /// // Decrypt:
+    /// let dec: u64 = cks.decrypt(&ct_res);
+    /// assert_eq!(clears.iter().sum::<u64>() / 3, dec);
+    /// ```
+    pub fn mean_parallelized<PBSOrder: PBSOrderMarker>(
+        &self,
+        cts: &[RadixCiphertext<PBSOrder>],
+    ) -> Option<RadixCiphertext<PBSOrder>> {
+        let num_blocks = cts.iter().map(|ct| ct.blocks.len()).max()?;
+        let num_bits_in_block = self.key.message_modulus.0.ilog2() as usize;
+        let num_extra_bits = cts.len().next_power_of_two().ilog2() as usize;
+        let num_extra_blocks = (num_extra_bits + num_bits_in_block - 1) / num_bits_in_block;
+
+        let extended = cts
+            .par_iter()
+            .map(|ct| {
+                self.extend_radix_with_trivial_zero_blocks_msb(
+                    ct,
+                    num_blocks + num_extra_blocks - ct.blocks.len(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let sum =
+            self.default_binary_op_seq_parallelized(&extended, ServerKey::add_parallelized)?;
+
+        // The mean is at most the largest input, so the extra blocks of the quotient are zeros
+        let mean = self.scalar_div_parallelized(&sum, cts.len() as u64);
+        Some(self.trim_radix_blocks_msb(&mean, num_extra_blocks))
+    }
+}
@@ -536,4 +536,73 @@ impl ServerKey {
 
         self.unchecked_scalar_mul_assign_parallelized(lhs, scalar);
     }
+
+    /// Computes homomorphically the sum of ciphertexts weighted by clear scalars.
+    ///
+    /// The result is `sum(values[i] * weights[i])`, i.e. the dot product of the ciphertexts with
+    /// the weights. Unlike [`Self::scalar_mul_parallelized`], the result is not truncated to the
+    /// width of the inputs: for inputs of at most `n` bits, the sum is at most
+    /// `(2^n - 1) * sum(weights)`, so the result has as many blocks as needed to hold
+    /// `n + bit_length(sum(weights))` bits. Returns `None` if there are no values.
+    ///
+    /// Each value is extended to the result width and multiplied by its weight, the terms being
+    /// computed in parallel, then they are summed with a balanced tree of additions.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values` and `weights` don't have the same length.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // We have 4 * 2 = 8 bits of message
+    /// let size = 4;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, size);
+    ///
+    /// let msgs = [230u64, 17, 255];
+    /// let weights = [3u64, 1000, 12];
+    ///
+    /// let cts = msgs.iter().map(|msg| cks.encrypt(*msg)).collect::<Vec<_>>();
+    ///
+    /// // Compute homomorphically a weighted sum:
+    /// let ct_res = sks.weighted_sum_parallelized(&cts, &weights).unwrap();
+    ///
+    /// // Decrypt, the result is wide enough to hold the exact sum:
+    /// let clear: u64 = cks.decrypt(&ct_res);
+    /// assert_eq!(230 * 3 + 17 * 1000 + 255 * 12, clear);
+    /// ```
+    pub fn weighted_sum_parallelized<PBSOrder: PBSOrderMarker>(
+        &self,
+        values: &[RadixCiphertext<PBSOrder>],
+        weights: &[u64],
+    ) -> Option<RadixCiphertext<PBSOrder>> {
+        assert_eq!(
+            values.len(),
+            weights.len(),
+            "values and weights must have the same length"
+        );
+        let max_num_blocks = values.iter().map(|ct| ct.blocks.len()).max()?;
+        let num_bits_in_block = self.key.message_modulus.0.ilog2() as usize;
+        let weights_sum = weights.iter().map(|w| *w as u128).sum::<u128>();
+        let num_output_bits = max_num_blocks * num_bits_in_block
+            + (u128::BITS - weights_sum.leading_zeros()) as usize;
+        let num_output_blocks = (num_output_bits + num_bits_in_block - 1) / num_bits_in_block;
+
+        let terms = values
+            .par_iter()
+            .zip(weights.par_iter())
+            .map(|(ct, weight)| {
+                let extended = self.extend_radix_with_trivial_zero_blocks_msb(
+                    ct,
+                    num_output_blocks - ct.blocks.len(),
+                );
+                self.scalar_mul_parallelized(&extended, *weight)
+            })
+            .collect::<Vec<_>>();
+
+        self.default_binary_op_seq_parallelized(&terms, ServerKey::add_parallelized)
+    }
 }
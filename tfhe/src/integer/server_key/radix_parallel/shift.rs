@@ -1,7 +1,7 @@
 use crate::core_crypto::commons::utils::izip;
 use crate::integer::ciphertext::RadixCiphertext;
 use crate::integer::ServerKey;
-use crate::shortint::PBSOrderMarker;
+use crate::shortint::{CiphertextBase, PBSOrderMarker};
 
 use rayon::prelude::*;
 
@@ -545,4 +545,196 @@ impl ServerKey {
 
         self.unchecked_scalar_left_shift_assign_parallelized(ct, shift);
     }
+
+    //======================================================================
+    //                Shift by an encrypted amount
+    //======================================================================
+
+    /// Computes homomorphically a left shift by an encrypted amount.
+    ///
+    /// Amounts greater than or equal to the number of bits of the ciphertext
+    /// shift every bit out, resulting in 0.
+    ///
+    /// This is a barrel shifter: for each bit `i` of `amount` such that `2^i` is less than the
+    /// number of bits of the ciphertext, the current value is shifted by the clear amount `2^i`,
+    /// and the encrypted bit selects between the shifted and unshifted values. The remaining,
+    /// higher bits of `amount` are only used in a final range check, which sets the result to 0
+    /// if any of them is set. The cost is fixed and does not depend on the encrypted value.
+    ///
+    /// If necessary the carries of the inputs will be cleaned beforehand,
+    /// the result is returned in a new ciphertext
+    ///
+    /// # Warning
+    ///
+    /// - Multithreaded
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // We have 4 * 2 = 8 bits of message
+    /// let size = 4;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, size);
+    ///
+    /// let msg = 13u8;
+    /// let shift = 3u8;
+    ///
+    /// let ct = cks.encrypt(msg as u64);
+    /// let ct_shift = cks.encrypt(shift as u64);
+    ///
+    /// let ct_res = sks.left_shift_by_encrypted_parallelized(&ct, &ct_shift);
+    ///
+    /// // Decrypt:
+    /// let dec: u64 = cks.decrypt(&ct_res);
+    /// assert_eq!((msg << shift) as u64, dec);
+    /// ```
+    pub fn left_shift_by_encrypted_parallelized<PBSOrder: PBSOrderMarker>(
+        &self,
+        ct: &RadixCiphertext<PBSOrder>,
+        amount: &RadixCiphertext<PBSOrder>,
+    ) -> RadixCiphertext<PBSOrder> {
+        let mut result = ct.clone();
+        self.left_shift_by_encrypted_assign_parallelized(&mut result, amount);
+        result
+    }
+
+    pub fn left_shift_by_encrypted_assign_parallelized<PBSOrder: PBSOrderMarker>(
+        &self,
+        ct: &mut RadixCiphertext<PBSOrder>,
+        amount: &RadixCiphertext<PBSOrder>,
+    ) {
+        self.shift_by_encrypted_amount_assign(ct, amount, ShiftDirection::Left);
+    }
+
+    /// Computes homomorphically a right shift by an encrypted amount.
+    ///
+    /// Amounts greater than or equal to the number of bits of the ciphertext
+    /// shift every bit out, resulting in 0.
+    ///
+    /// See [`Self::left_shift_by_encrypted_parallelized`] for how it is computed.
+    ///
+    /// # Warning
+    ///
+    /// - Multithreaded
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // We have 4 * 2 = 8 bits of message
+    /// let size = 4;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, size);
+    ///
+    /// let msg = 200u8;
+    /// let shift = 9u8;
+    ///
+    /// let ct = cks.encrypt(msg as u64);
+    /// let ct_shift = cks.encrypt(shift as u64);
+    ///
+    /// let ct_res = sks.right_shift_by_encrypted_parallelized(&ct, &ct_shift);
+    ///
+    /// // Decrypt:
+    /// let dec: u64 = cks.decrypt(&ct_res);
+    /// assert_eq!(0, dec);
+    /// ```
+    pub fn right_shift_by_encrypted_parallelized<PBSOrder: PBSOrderMarker>(
+        &self,
+        ct: &RadixCiphertext<PBSOrder>,
+        amount: &RadixCiphertext<PBSOrder>,
+    ) -> RadixCiphertext<PBSOrder> {
+        let mut result = ct.clone();
+        self.right_shift_by_encrypted_assign_parallelized(&mut result, amount);
+        result
+    }
+
+    pub fn right_shift_by_encrypted_assign_parallelized<PBSOrder: PBSOrderMarker>(
+        &self,
+        ct: &mut RadixCiphertext<PBSOrder>,
+        amount: &RadixCiphertext<PBSOrder>,
+    ) {
+        self.shift_by_encrypted_amount_assign(ct, amount, ShiftDirection::Right);
+    }
+
+    fn shift_by_encrypted_amount_assign<PBSOrder: PBSOrderMarker>(
+        &self,
+        ct: &mut RadixCiphertext<PBSOrder>,
+        amount: &RadixCiphertext<PBSOrder>,
+        direction: ShiftDirection,
+    ) {
+        let mut tmp_amount: RadixCiphertext<PBSOrder>;
+        let amount = if amount.block_carries_are_empty() {
+            amount
+        } else {
+            tmp_amount = amount.clone();
+            self.full_propagate_parallelized(&mut tmp_amount);
+            &tmp_amount
+        };
+        if !ct.block_carries_are_empty() {
+            self.full_propagate_parallelized(ct);
+        }
+
+        let num_bits_in_message = self.key.message_modulus.0.ilog2() as usize;
+        let total_num_bits = num_bits_in_message * ct.blocks.len();
+
+        // Extract each bit of the amount in its own block
+        let mut amount_bits = (0..num_bits_in_message * amount.blocks.len())
+            .into_par_iter()
+            .map(|i| {
+                let bit_in_block = i % num_bits_in_message;
+                let lut = self.key.generate_accumulator(|x| (x >> bit_in_block) & 1);
+                self.key
+                    .apply_lookup_table(&amount.blocks[i / num_bits_in_message], &lut)
+            })
+            .collect::<Vec<_>>();
+
+        // Bits i such that 2^i >= total_num_bits shift everything out
+        let num_shifting_bits =
+            (total_num_bits.next_power_of_two().ilog2() as usize).min(amount_bits.len());
+        let out_of_range_bits = amount_bits.split_off(num_shifting_bits);
+
+        for (i, amount_bit) in amount_bits.iter().enumerate() {
+            let n = 1u64 << i;
+            let shifted = match direction {
+                ShiftDirection::Left => self.unchecked_scalar_left_shift_parallelized(ct, n),
+                ShiftDirection::Right => self.unchecked_scalar_right_shift_parallelized(ct, n),
+            };
+            *ct = self.unchecked_select_parallelized(amount_bit, &shifted, ct);
+        }
+
+        if let Some(is_out_of_range) = self.any_bit_set(out_of_range_bits) {
+            let lut = self
+                .key
+                .generate_accumulator_bivariate(|x, c| if c == 0 { x } else { 0 });
+            ct.blocks.par_iter_mut().for_each(|block| {
+                self.key.unchecked_apply_lookup_table_bivariate_assign(
+                    block,
+                    &is_out_of_range,
+                    &lut,
+                );
+            });
+        }
+    }
+
+    /// Returns a block encrypting whether any of the given bits is set,
+    /// or `None` if there are no bits
+    fn any_bit_set<PBSOrder: PBSOrderMarker>(
+        &self,
+        bits: Vec<CiphertextBase<PBSOrder>>,
+    ) -> Option<CiphertextBase<PBSOrder>> {
+        let bits = bits
+            .into_iter()
+            .map(|bit| RadixCiphertext::from(vec![bit]))
+            .collect::<Vec<_>>();
+        self.or_reduce(&bits)
+            .map(|mut is_set| is_set.blocks.pop().unwrap())
+    }
+}
+
+enum ShiftDirection {
+    Left,
+    Right,
 }
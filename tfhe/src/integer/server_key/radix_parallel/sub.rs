@@ -136,6 +136,11 @@ impl ServerKey {
     /// // Decrypt:
     /// let res: u64 = cks.decrypt(&ct_res);
     /// assert_eq!(msg_1.wrapping_sub(msg_2) as u64, res);
+    ///
+    /// // Without wrapping around:
+    /// let ct_res = sks.sub_parallelized(&ctxt_2, &ctxt_1);
+    /// let res: u64 = cks.decrypt(&ct_res);
+    /// assert_eq!((msg_2 - msg_1) as u64, res);
     /// ```
     pub fn sub_parallelized<PBSOrder: PBSOrderMarker>(
         &self,
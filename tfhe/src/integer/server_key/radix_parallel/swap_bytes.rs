@@ -0,0 +1,166 @@
+use crate::integer::ciphertext::RadixCiphertext;
+use crate::integer::ServerKey;
+use crate::shortint::PBSOrderMarker;
+
+use rayon::prelude::*;
+
+impl ServerKey {
+    /// Computes homomorphically the reversal of the byte order of a ciphertext.
+    ///
+    /// The bytes are those of the full ciphertext width, i.e. `num_blocks * log2(message_modulus)`
+    /// bits, which must be a multiple of 8.
+    ///
+    /// If necessary the carries of the input will be cleaned beforehand,
+    /// but its value won't change, the result is returned in a new ciphertext
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ciphertext width is not a multiple of 8 bits.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // We have 8 * 2 = 16 bits of message
+    /// let size = 8;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, size);
+    ///
+    /// let msg = 0x12abu16;
+    ///
+    /// let ct = cks.encrypt(msg as u64);
+    ///
+    /// let ct_res = sks.swap_bytes_parallelized(&ct);
+    ///
+    /// // Decrypt:
+    /// let dec: u64 = cks.decrypt(&ct_res);
+    /// assert_eq!(msg.swap_bytes() as u64, dec);
+    /// ```
+    pub fn swap_bytes_parallelized<PBSOrder: PBSOrderMarker>(
+        &self,
+        ct: &RadixCiphertext<PBSOrder>,
+    ) -> RadixCiphertext<PBSOrder> {
+        let mut result = ct.clone();
+        self.swap_bytes_assign_parallelized(&mut result);
+        result
+    }
+
+    /// Computes homomorphically the reversal of the byte order of a ciphertext.
+    ///
+    /// The result is assigned to the input ciphertext
+    ///
+    /// If necessary carries will be cleaned beforehand
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ciphertext width is not a multiple of 8 bits.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // We have 8 * 2 = 16 bits of message
+    /// let size = 8;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, size);
+    ///
+    /// let msg = 0x12abu16;
+    ///
+    /// let mut ct = cks.encrypt(msg as u64);
+    ///
+    /// sks.swap_bytes_assign_parallelized(&mut ct);
+    ///
+    /// // Decrypt:
+    /// let dec: u64 = cks.decrypt(&ct);
+    /// assert_eq!(msg.swap_bytes() as u64, dec);
+    /// ```
+    pub fn swap_bytes_assign_parallelized<PBSOrder: PBSOrderMarker>(
+        &self,
+        ct: &mut RadixCiphertext<PBSOrder>,
+    ) {
+        if !ct.block_carries_are_empty() {
+            self.full_propagate_parallelized(ct);
+        }
+        self.unchecked_swap_bytes_assign_parallelized(ct);
+    }
+
+    /// Computes homomorphically the reversal of the byte order of a ciphertext.
+    ///
+    /// When the number of bits of message per block divides 8, each byte is made of whole
+    /// blocks and the reversal only moves blocks around, without any PBS. Otherwise bytes
+    /// straddle blocks, and each byte is isolated and moved to its place with scalar shifts
+    /// before the bytes are recombined.
+    ///
+    /// # Requirements
+    ///
+    /// - The input ciphertext carry buffer is emtpy / clean
+    ///
+    /// # Panics
+    ///
+    /// Panics if the ciphertext width is not a multiple of 8 bits.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use tfhe::integer::gen_keys_radix;
+    /// use tfhe::shortint::parameters::PARAM_MESSAGE_2_CARRY_2;
+    ///
+    /// // We have 8 * 2 = 16 bits of message
+    /// let size = 8;
+    /// let (cks, sks) = gen_keys_radix(PARAM_MESSAGE_2_CARRY_2, size);
+    ///
+    /// let msg = 0x12abu16;
+    ///
+    /// let mut ct = cks.encrypt(msg as u64);
+    ///
+    /// sks.unchecked_swap_bytes_assign_parallelized(&mut ct);
+    ///
+    /// // Decrypt:
+    /// let dec: u64 = cks.decrypt(&ct);
+    /// assert_eq!(msg.swap_bytes() as u64, dec);
+    /// ```
+    pub fn unchecked_swap_bytes_assign_parallelized<PBSOrder: PBSOrderMarker>(
+        &self,
+        ct: &mut RadixCiphertext<PBSOrder>,
+    ) {
+        let num_bits_in_message = self.key.message_modulus.0.ilog2() as usize;
+        let total_num_bits = num_bits_in_message * ct.blocks.len();
+        assert_eq!(
+            total_num_bits % 8,
+            0,
+            "The ciphertext width ({total_num_bits} bits) must be a multiple of 8 bits"
+        );
+        let num_bytes = total_num_bits / 8;
+        if num_bytes <= 1 {
+            return;
+        }
+
+        if 8 % num_bits_in_message == 0 {
+            let num_blocks_per_byte = 8 / num_bits_in_message;
+            let mut blocks = std::mem::take(&mut ct.blocks);
+            let mut reversed_blocks = Vec::with_capacity(blocks.len());
+            while !blocks.is_empty() {
+                reversed_blocks.extend(blocks.drain(blocks.len() - num_blocks_per_byte..));
+            }
+            ct.blocks = reversed_blocks;
+            return;
+        }
+
+        let total_num_bits = total_num_bits as u64;
+        let source = &*ct;
+        let result = (0..num_bytes as u64)
+            .into_par_iter()
+            .map(|i| {
+                // Isolate byte i in the low bits, then move it to byte num_bytes - 1 - i
+                let byte =
+                    self.scalar_left_shift_parallelized(source, total_num_bits - 8 * (i + 1));
+                let byte = self.scalar_right_shift_parallelized(&byte, total_num_bits - 8);
+                self.scalar_left_shift_parallelized(&byte, 8 * (num_bytes as u64 - 1 - i))
+            })
+            .reduce_with(|lhs, rhs| self.bitor_parallelized(&lhs, &rhs))
+            .unwrap();
+        *ct = result;
+    }
+}
@@ -1,7 +1,9 @@
+use super::add::AddExtraOne;
 use crate::integer::keycache::KEY_CACHE;
-use crate::integer::{RadixClientKey, ServerKey};
+use crate::integer::{RadixCiphertextBig, RadixClientKey, ServerKey};
 use crate::shortint::parameters::*;
 use crate::shortint::PBSParameters;
+use itertools::iproduct;
 use paste::paste;
 use rand::Rng;
 
@@ -35,10 +37,27 @@ macro_rules! create_parametrized_test{
     };
 }
 
+create_parametrized_test!(integer_propagate_carry_at);
+create_parametrized_test!(integer_full_propagate_lookahead);
 create_parametrized_test!(integer_smart_add);
 create_parametrized_test!(integer_smart_add_sequence_multi_thread);
 create_parametrized_test!(integer_smart_add_sequence_single_thread);
 create_parametrized_test!(integer_default_add);
+create_parametrized_test!(integer_overflowing_add);
+create_parametrized_test!(integer_saturating_add);
+create_parametrized_test!(integer_unchecked_sum_ciphertexts);
+create_parametrized_test!(integer_default_add_thread_counts {
+    // Both parallel algorithms require 4 bits
+    PARAM_MESSAGE_2_CARRY_2,
+    PARAM_MESSAGE_3_CARRY_3,
+    PARAM_MESSAGE_4_CARRY_4
+});
+create_parametrized_test!(integer_unchecked_add_brent_kung {
+    // This algorithm requires 4 bits
+    PARAM_MESSAGE_2_CARRY_2,
+    PARAM_MESSAGE_3_CARRY_3,
+    PARAM_MESSAGE_4_CARRY_4
+});
 create_parametrized_test!(integer_default_add_work_efficient {
     // This algorithm requires 3 bits
     PARAM_MESSAGE_2_CARRY_2,
@@ -46,6 +65,7 @@ create_parametrized_test!(integer_default_add_work_efficient {
     PARAM_MESSAGE_4_CARRY_4
 });
 create_parametrized_test!(integer_default_add_sequence_multi_thread);
+create_parametrized_test!(integer_default_add_sequence_with_arity);
 // Other tests are pretty slow, and the code is the same as a smart add but slower
 #[test]
 fn test_integer_default_add_sequence_single_thread_param_message_2_carry_2() {
@@ -57,6 +77,7 @@ create_parametrized_test!(integer_smart_bitxor);
 create_parametrized_test!(integer_default_bitand);
 create_parametrized_test!(integer_default_bitor);
 create_parametrized_test!(integer_default_bitxor);
+create_parametrized_test!(integer_get_set_bit);
 create_parametrized_test!(integer_unchecked_small_scalar_mul);
 create_parametrized_test!(integer_smart_small_scalar_mul);
 create_parametrized_test!(integer_default_small_scalar_mul);
@@ -70,19 +91,26 @@ create_parametrized_test!(integer_default_scalar_mul_u128_fix_non_reg_test {
 });
 create_parametrized_test!(integer_smart_scalar_mul);
 create_parametrized_test!(integer_default_scalar_mul);
+create_parametrized_test!(integer_weighted_sum);
 // left/right shifts
 create_parametrized_test!(integer_unchecked_scalar_left_shift);
 create_parametrized_test!(integer_default_scalar_left_shift);
 create_parametrized_test!(integer_unchecked_scalar_right_shift);
 create_parametrized_test!(integer_default_scalar_right_shift);
+create_parametrized_test!(integer_left_shift_by_encrypted);
+create_parametrized_test!(integer_right_shift_by_encrypted);
 // left/right rotations
 create_parametrized_test!(integer_unchecked_scalar_rotate_right);
 create_parametrized_test!(integer_unchecked_scalar_rotate_left);
 create_parametrized_test!(integer_scalar_rotate_right);
 create_parametrized_test!(integer_scalar_rotate_left);
+create_parametrized_test!(integer_rotate_right);
+create_parametrized_test!(integer_rotate_left);
 // negations
 create_parametrized_test!(integer_smart_neg);
 create_parametrized_test!(integer_default_neg);
+create_parametrized_test!(integer_default_neg_add_is_zero);
+create_parametrized_test!(integer_conditional_negate);
 create_parametrized_test!(integer_smart_sub);
 create_parametrized_test!(integer_default_sub);
 create_parametrized_test!(integer_default_sub_work_efficient {
@@ -96,10 +124,109 @@ create_parametrized_test!(integer_smart_block_mul);
 create_parametrized_test!(integer_default_block_mul);
 create_parametrized_test!(integer_smart_mul);
 create_parametrized_test!(integer_default_mul);
+create_parametrized_test!(integer_overflowing_mul);
+create_parametrized_test!(integer_scalar_pow);
+create_parametrized_test!(integer_sum_of_squares);
+create_parametrized_test!(integer_scalar_div);
+create_parametrized_test!(integer_mean);
 create_parametrized_test!(integer_smart_scalar_sub);
 create_parametrized_test!(integer_default_scalar_sub);
 create_parametrized_test!(integer_smart_scalar_add);
 create_parametrized_test!(integer_default_scalar_add);
+create_parametrized_test!(integer_with_server_key_ops);
+create_parametrized_test!(integer_argmax);
+create_parametrized_test!(integer_is_sorted);
+create_parametrized_test!(integer_select);
+create_parametrized_test!(integer_clamp);
+create_parametrized_test!(integer_or_reduce);
+create_parametrized_test!(integer_and_reduce);
+create_parametrized_test!(integer_swap_bytes {
+    PARAM_MESSAGE_1_CARRY_1,
+    PARAM_MESSAGE_2_CARRY_2,
+    PARAM_MESSAGE_4_CARRY_4
+});
+create_parametrized_test!(integer_swap_bytes_unaligned {
+    PARAM_MESSAGE_3_CARRY_3
+});
+create_parametrized_test!(integer_interleave_bits);
+
+fn integer_propagate_carry_at(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+    let cks = RadixClientKey::from((cks, NB_CTXT));
+
+    //RNG
+    let mut rng = rand::thread_rng();
+
+    // message_modulus^vec_length
+    let modulus = param.message_modulus.0.pow(NB_CTXT as u32) as u64;
+
+    for _ in 0..NB_TEST_SMALLER {
+        let clear_0 = rng.gen::<u64>() % modulus;
+        let clear_1 = rng.gen::<u64>() % modulus;
+
+        let ctxt_0 = cks.encrypt(clear_0);
+        let ctxt_1 = cks.encrypt(clear_1);
+
+        let mut ct_res = sks.unchecked_add(&ctxt_0, &ctxt_1);
+        let mut expected = ct_res.clone();
+        sks.full_propagate_parallelized(&mut expected);
+
+        for i in 0..NB_CTXT {
+            let before = ct_res.clone();
+            sks.propagate_carry_at(&mut ct_res, i);
+            assert!(ct_res.blocks[i].carry_is_empty());
+            // Only the block and the one receiving its carry are modified
+            for j in (0..NB_CTXT).filter(|j| *j != i && *j != i + 1) {
+                assert_eq!(ct_res.blocks[j], before.blocks[j]);
+            }
+        }
+        // Propagating each block in turn is a full propagation
+        assert_eq!(ct_res, expected);
+
+        let dec_res: u64 = cks.decrypt(&ct_res);
+        assert_eq!((clear_0 + clear_1) % modulus, dec_res);
+    }
+}
+
+fn integer_full_propagate_lookahead(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+    let cks = RadixClientKey::from((cks, NB_CTXT));
+
+    //RNG
+    let mut rng = rand::thread_rng();
+
+    // message_modulus^vec_length
+    let modulus = param.message_modulus.0.pow(NB_CTXT as u32) as u64;
+
+    for _ in 0..NB_TEST_SMALLER {
+        let clear_0 = rng.gen::<u64>() % modulus;
+        let clear_1 = rng.gen::<u64>() % modulus;
+
+        let ctxt_0 = cks.encrypt(clear_0);
+        let ctxt_1 = cks.encrypt(clear_1);
+
+        // Carries of at most 1
+        let mut ct_res = sks.unchecked_add(&ctxt_0, &ctxt_1);
+        let mut clear = clear_0 + clear_1;
+        let mut tmp = ct_res.clone();
+        sks.full_propagate_lookahead(&mut tmp);
+        assert!(tmp.block_carries_are_empty());
+        let dec_res: u64 = cks.decrypt(&tmp);
+        assert_eq!(clear % modulus, dec_res);
+
+        // Fill the carries up
+        for _ in 1..param.carry_modulus.0 {
+            let clear_2 = rng.gen::<u64>() % modulus;
+            let ctxt_2 = cks.encrypt(clear_2);
+            sks.unchecked_add_assign(&mut ct_res, &ctxt_2);
+            clear += clear_2;
+        }
+        sks.full_propagate_lookahead(&mut ct_res);
+        assert!(ct_res.block_carries_are_empty());
+        let dec_res: u64 = cks.decrypt(&ct_res);
+        assert_eq!(clear % modulus, dec_res);
+    }
+}
 
 fn integer_smart_add(param: PBSParameters) {
     let (cks, sks) = KEY_CACHE.get_from_params(param);
@@ -296,6 +423,77 @@ fn integer_default_add_work_efficient(param: PBSParameters) {
     }
 }
 
+fn integer_default_add_thread_counts(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+
+    //RNG
+    let mut rng = rand::thread_rng();
+
+    // The carry propagation algorithm is chosen from the number of blocks and threads,
+    // so cover block counts that are and are not powers of two, with few and many threads
+    for (num_blocks, num_threads) in iproduct!([3, NB_CTXT, 5], [1, 2, 8]) {
+        // message_modulus^vec_length
+        let modulus = param.message_modulus.0.pow(num_blocks as u32) as u64;
+
+        let threadpool = rayon::ThreadPoolBuilder::new()
+            .num_threads(num_threads)
+            .build()
+            .unwrap();
+
+        for _ in 0..NB_TEST_SMALLER {
+            let clear_0 = rng.gen::<u64>() % modulus;
+            let clear_1 = rng.gen::<u64>() % modulus;
+
+            let ctxt_0 = cks.encrypt_radix(clear_0, num_blocks);
+            let ctxt_1 = cks.encrypt_radix(clear_1, num_blocks);
+
+            let ct_res = threadpool.install(|| sks.add_parallelized(&ctxt_0, &ctxt_1));
+            assert!(ct_res.block_carries_are_empty());
+
+            let expected = (clear_0 + clear_1) % modulus;
+            let dec_res: u64 = cks.decrypt_radix(&ct_res);
+            assert_eq!(
+                expected, dec_res,
+                "on {num_blocks} blocks with {num_threads} threads"
+            );
+        }
+    }
+}
+
+fn integer_unchecked_add_brent_kung(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+
+    //RNG
+    let mut rng = rand::thread_rng();
+
+    // Block counts that are and are not powers of two
+    for num_blocks in [1, 2, 3, 4, 5, 7, 8] {
+        let cks = RadixClientKey::from((cks.clone(), num_blocks));
+
+        // message_modulus^vec_length
+        let modulus = param.message_modulus.0.pow(num_blocks as u32) as u64;
+
+        for _ in 0..NB_TEST_SMALLER {
+            let clear_0 = rng.gen::<u64>() % modulus;
+            let clear_1 = rng.gen::<u64>() % modulus;
+
+            let ctxt_0 = cks.encrypt(clear_0);
+            let ctxt_1 = cks.encrypt(clear_1);
+
+            let mut ct_res = ctxt_0.clone();
+            sks.unchecked_add_assign_parallelized_brent_kung(&mut ct_res, &ctxt_1, AddExtraOne::No);
+            assert!(ct_res.block_carries_are_empty());
+
+            let ct_expected = sks.unchecked_add(&ctxt_0, &ctxt_1);
+
+            let dec_res: u64 = cks.decrypt(&ct_res);
+            let dec_expected: u64 = cks.decrypt(&ct_expected);
+            assert_eq!(dec_expected % modulus, dec_res, "with {num_blocks} blocks");
+            assert_eq!((clear_0 + clear_1) % modulus, dec_res);
+        }
+    }
+}
+
 fn integer_default_add_sequence_multi_thread(param: PBSParameters) {
     let (cks, sks) = KEY_CACHE.get_from_params(param);
     let cks = RadixClientKey::from((cks, NB_CTXT));
@@ -376,6 +574,108 @@ fn integer_default_add_sequence_single_thread(param: PBSParameters) {
     }
 }
 
+fn integer_default_add_sequence_with_arity(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+    let cks = RadixClientKey::from((cks, NB_CTXT));
+
+    //RNG
+    let mut rng = rand::thread_rng();
+
+    // message_modulus^vec_length
+    let modulus = param.message_modulus.0.pow(NB_CTXT as u32) as u64;
+
+    let add_all = |sks: &ServerKey, chunk: &[&RadixCiphertextBig]| {
+        chunk[1..]
+            .iter()
+            .fold(chunk[0].clone(), |acc, ct| sks.add_parallelized(&acc, ct))
+    };
+    // 3 clean terms always fit in the carries
+    let multi_operand_add = |sks: &ServerKey, chunk: &[&RadixCiphertextBig]| {
+        assert!(chunk.len() <= 3);
+        let mut sum = chunk[0].clone();
+        for ct in &chunk[1..] {
+            sks.unchecked_add_assign(&mut sum, ct);
+        }
+        sks.full_propagate_parallelized(&mut sum);
+        sum
+    };
+
+    for len in [1, 2, 3, 4, 5, 16, 17] {
+        let clears = (0..len)
+            .map(|_| rng.gen::<u64>() % modulus)
+            .collect::<Vec<_>>();
+        let clear = clears.iter().sum::<u64>() % modulus;
+
+        // encryption of integers
+        let ctxts = clears
+            .iter()
+            .copied()
+            .map(|clear| cks.encrypt(clear))
+            .collect::<Vec<_>>();
+
+        for arity in [2, 3, 4] {
+            let ct_res = sks
+                .default_op_seq_parallelized_with_arity(&ctxts, arity, add_all)
+                .unwrap();
+            assert!(ct_res.block_carries_are_empty());
+            let ct_res: u64 = cks.decrypt(&ct_res);
+            assert_eq!(ct_res, clear);
+        }
+
+        let ct_res = sks
+            .default_op_seq_parallelized_with_arity(&ctxts, 3, multi_operand_add)
+            .unwrap();
+        assert!(ct_res.block_carries_are_empty());
+        let ct_res: u64 = cks.decrypt(&ct_res);
+        assert_eq!(ct_res, clear);
+    }
+}
+
+fn integer_or_reduce(param: PBSParameters) {
+    integer_boolean_reduce(param, ServerKey::or_reduce, |a, b| a | b);
+}
+
+fn integer_and_reduce(param: PBSParameters) {
+    integer_boolean_reduce(param, ServerKey::and_reduce, |a, b| a & b);
+}
+
+fn integer_boolean_reduce<ReduceFn>(
+    param: PBSParameters,
+    reduce: ReduceFn,
+    clear_op: fn(bool, bool) -> bool,
+) where
+    ReduceFn: Fn(&ServerKey, &[RadixCiphertextBig]) -> Option<RadixCiphertextBig>,
+{
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+    let cks = RadixClientKey::from((cks, NB_CTXT));
+
+    //RNG
+    let mut rng = rand::thread_rng();
+
+    assert!(reduce(&sks, &[]).is_none());
+
+    for len in [1, 2, 15, 16, 17, 64] {
+        for _ in 0..NB_TEST_SMALLER {
+            // Bias towards true, so that AND reductions are not almost always false
+            let clears = (0..len)
+                .map(|_| rng.gen_range(0..len + 1) != 0)
+                .collect::<Vec<_>>();
+
+            let ctxts = clears
+                .iter()
+                .map(|clear| cks.encrypt(*clear as u64))
+                .collect::<Vec<_>>();
+
+            let ct_res = reduce(&sks, &ctxts).unwrap();
+            assert!(ct_res.block_carries_are_empty());
+            let dec: u64 = cks.decrypt(&ct_res);
+            let clear = clears.into_iter().reduce(clear_op).unwrap();
+
+            assert_eq!(clear as u64, dec);
+        }
+    }
+}
+
 fn integer_smart_bitand(param: PBSParameters) {
     let (cks, sks) = KEY_CACHE.get_from_params(param);
     let cks = RadixClientKey::from((cks, NB_CTXT));
@@ -658,6 +958,48 @@ fn integer_default_bitxor(param: PBSParameters) {
     }
 }
 
+fn integer_get_set_bit(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+    let cks = RadixClientKey::from((cks, NB_CTXT));
+
+    //RNG
+    let mut rng = rand::thread_rng();
+
+    // message_modulus^vec_length
+    let modulus = param.message_modulus.0.pow(NB_CTXT as u32) as u64;
+
+    let num_bits_in_block = param.message_modulus.0.ilog2() as usize;
+    let num_bits = num_bits_in_block * NB_CTXT;
+    // First and last bits of the ciphertext, and bits on both sides of a block boundary
+    let bits = [0, num_bits_in_block - 1, num_bits_in_block, num_bits - 1];
+
+    for _ in 0..NB_TEST_SMALLER {
+        let clear = rng.gen::<u64>() % modulus;
+        let clear_value = rng.gen::<u64>() % 2;
+
+        let ctxt = cks.encrypt(clear);
+        let ctxt_value = cks.encrypt(clear_value);
+
+        for bit in bits {
+            let ct_bit = sks.get_bit_parallelized(&ctxt, bit);
+            assert!(ct_bit.block_carries_are_empty());
+            let dec_bit: u64 = cks.decrypt(&ct_bit);
+            assert_eq!((clear >> bit) & 1, dec_bit);
+
+            let mut ct_res = ctxt.clone();
+            sks.set_bit_parallelized(&mut ct_res, bit, &ctxt_value);
+            assert!(ct_res.block_carries_are_empty());
+            let dec_res: u64 = cks.decrypt(&ct_res);
+            assert_eq!((clear & !(1 << bit)) | (clear_value << bit), dec_res);
+
+            // Reading the bit back gives the value it was set to
+            let ct_bit = sks.get_bit_parallelized(&ct_res, bit);
+            let dec_bit: u64 = cks.decrypt(&ct_bit);
+            assert_eq!(clear_value, dec_bit);
+        }
+    }
+}
+
 fn integer_unchecked_small_scalar_mul(param: PBSParameters) {
     let (cks, sks) = KEY_CACHE.get_from_params(param);
     let cks = RadixClientKey::from((cks, NB_CTXT));
@@ -829,6 +1171,42 @@ fn integer_default_scalar_mul(param: PBSParameters) {
     }
 }
 
+fn integer_weighted_sum(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+    let cks = RadixClientKey::from((cks, NB_CTXT));
+
+    //RNG
+    let mut rng = rand::thread_rng();
+
+    // message_modulus^vec_length
+    let modulus = param.message_modulus.0.pow(NB_CTXT as u32) as u64;
+
+    let no_cts: [RadixCiphertextBig; 0] = [];
+    assert!(sks.weighted_sum_parallelized(&no_cts, &[]).is_none());
+
+    for num_terms in 1..=4 {
+        let clears = (0..num_terms)
+            .map(|_| rng.gen::<u64>() % modulus)
+            .collect::<Vec<_>>();
+        // Weights larger than the modulus, so that the sum needs the widening
+        let weights = (0..num_terms)
+            .map(|_| rng.gen::<u64>() % (4 * modulus))
+            .collect::<Vec<_>>();
+        let ctxts = clears.iter().map(|c| cks.encrypt(*c)).collect::<Vec<_>>();
+
+        let ct_res = sks.weighted_sum_parallelized(&ctxts, &weights).unwrap();
+        assert!(ct_res.block_carries_are_empty());
+
+        let expected = clears
+            .iter()
+            .zip(weights.iter())
+            .map(|(c, w)| c * w)
+            .sum::<u64>();
+        let dec: u64 = cks.decrypt(&ct_res);
+        assert_eq!(expected, dec);
+    }
+}
+
 fn integer_unchecked_mul_corner_cases(param: PBSParameters) {
     let (cks, sks) = KEY_CACHE.get_from_params(param);
 
@@ -1141,6 +1519,76 @@ fn integer_default_scalar_right_shift(param: PBSParameters) {
     }
 }
 
+fn integer_left_shift_by_encrypted(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+    let cks = RadixClientKey::from((cks, NB_CTXT));
+
+    let mut rng = rand::thread_rng();
+
+    // message_modulus^vec_length
+    let modulus = param.message_modulus.0.pow(NB_CTXT as u32) as u64;
+    let nb_bits = modulus.ilog2();
+
+    for _ in 0..NB_TEST_SMALLER {
+        let clear = rng.gen::<u64>() % modulus;
+        let ct = cks.encrypt(clear);
+
+        // Amounts below the number of bits, and at or above it, which give 0
+        for clear_amount in [
+            rng.gen::<u64>() % nb_bits as u64,
+            nb_bits as u64,
+            rng.gen::<u64>() % modulus,
+        ] {
+            let ct_amount = cks.encrypt(clear_amount);
+
+            let ct_res = sks.left_shift_by_encrypted_parallelized(&ct, &ct_amount);
+            let tmp = sks.left_shift_by_encrypted_parallelized(&ct, &ct_amount);
+            assert!(ct_res.block_carries_are_empty());
+            assert_eq!(ct_res, tmp);
+            let dec_res: u64 = cks.decrypt(&ct_res);
+            let expected = clear.checked_shl(clear_amount as u32).unwrap_or(0) % modulus;
+            assert_eq!(expected, dec_res);
+        }
+    }
+}
+
+fn integer_right_shift_by_encrypted(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+    let cks = RadixClientKey::from((cks, NB_CTXT));
+
+    let mut rng = rand::thread_rng();
+
+    // message_modulus^vec_length
+    let modulus = param.message_modulus.0.pow(NB_CTXT as u32) as u64;
+    let nb_bits = modulus.ilog2();
+
+    for _ in 0..NB_TEST_SMALLER {
+        let clear = rng.gen::<u64>() % modulus;
+        let ct = cks.encrypt(clear);
+
+        // Amounts below the number of bits, and at or above it, which give 0
+        for clear_amount in [
+            rng.gen::<u64>() % nb_bits as u64,
+            nb_bits as u64,
+            rng.gen::<u64>() % modulus,
+        ] {
+            let ct_amount = cks.encrypt(clear_amount);
+
+            let ct_res = sks.right_shift_by_encrypted_parallelized(&ct, &ct_amount);
+            let tmp = sks.right_shift_by_encrypted_parallelized(&ct, &ct_amount);
+            assert!(ct_res.block_carries_are_empty());
+            assert_eq!(ct_res, tmp);
+            let dec_res: u64 = cks.decrypt(&ct_res);
+            let expected = if clear_amount >= nb_bits as u64 {
+                0
+            } else {
+                clear >> clear_amount
+            };
+            assert_eq!(expected, dec_res);
+        }
+    }
+}
+
 /// helper function to do a rotate left when the type used to store
 /// the value is bigger than the actual intended bit size
 fn rotate_left_helper(value: u64, n: u32, actual_bit_size: u32) -> u64 {
@@ -1438,37 +1886,206 @@ fn integer_scalar_rotate_left(param: PBSParameters) {
     }
 }
 
-fn integer_smart_neg(param: PBSParameters) {
+fn integer_rotate_right(param: PBSParameters) {
     let (cks, sks) = KEY_CACHE.get_from_params(param);
     let cks = RadixClientKey::from((cks, NB_CTXT));
 
-    //RNG
     let mut rng = rand::thread_rng();
 
     // message_modulus^vec_length
     let modulus = param.message_modulus.0.pow(NB_CTXT as u32) as u64;
+    let nb_bits = modulus.ilog2();
 
-    for _ in 0..NB_TEST {
-        // Define the cleartexts
+    for _ in 0..NB_TEST_SMALLER {
         let clear = rng.gen::<u64>() % modulus;
+        // amounts above the number of bits wrap around
+        let clear_amount = rng.gen::<u64>() % modulus;
 
-        // Encrypt the integers
-        let mut ctxt = cks.encrypt(clear);
-
-        // Negates the ctxt
-        let ct_tmp = sks.smart_neg_parallelized(&mut ctxt);
-
-        // Decrypt the result
-        let dec: u64 = cks.decrypt(&ct_tmp);
-
-        // Check the correctness
-        let clear_result = clear.wrapping_neg() % modulus;
+        let ct = cks.encrypt(clear);
+        let ct_amount = cks.encrypt(clear_amount);
 
-        assert_eq!(clear_result, dec);
+        let ct_res = sks.rotate_right_parallelized(&ct, &ct_amount);
+        let tmp = sks.rotate_right_parallelized(&ct, &ct_amount);
+        assert!(ct_res.block_carries_are_empty());
+        assert_eq!(ct_res, tmp);
+        let dec_res: u64 = cks.decrypt(&ct_res);
+        let expected = rotate_right_helper(clear, clear_amount as u32, nb_bits);
+        assert_eq!(expected, dec_res);
     }
 }
 
-fn integer_default_neg(param: PBSParameters) {
+fn integer_rotate_left(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+    let cks = RadixClientKey::from((cks, NB_CTXT));
+
+    let mut rng = rand::thread_rng();
+
+    // message_modulus^vec_length
+    let modulus = param.message_modulus.0.pow(NB_CTXT as u32) as u64;
+    let nb_bits = modulus.ilog2();
+
+    for _ in 0..NB_TEST_SMALLER {
+        let clear = rng.gen::<u64>() % modulus;
+        // amounts above the number of bits wrap around
+        let clear_amount = rng.gen::<u64>() % modulus;
+
+        let ct = cks.encrypt(clear);
+        let ct_amount = cks.encrypt(clear_amount);
+
+        let ct_res = sks.rotate_left_parallelized(&ct, &ct_amount);
+        let tmp = sks.rotate_left_parallelized(&ct, &ct_amount);
+        assert!(ct_res.block_carries_are_empty());
+        assert_eq!(ct_res, tmp);
+        let dec_res: u64 = cks.decrypt(&ct_res);
+        let expected = rotate_left_helper(clear, clear_amount as u32, nb_bits);
+        assert_eq!(expected, dec_res);
+    }
+}
+
+fn integer_swap_bytes(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+    // 64 bits of message, bytes are made of whole blocks
+    let num_blocks = 64 / param.message_modulus.0.ilog2() as usize;
+    let cks = RadixClientKey::from((cks, num_blocks));
+
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NB_TEST_SMALLER {
+        let clear = rng.gen::<u64>();
+
+        let ct = cks.encrypt(clear);
+
+        let ct_res = sks.swap_bytes_parallelized(&ct);
+        assert!(ct_res.block_carries_are_empty());
+        let dec_res: u64 = cks.decrypt(&ct_res);
+        assert_eq!(clear.swap_bytes(), dec_res);
+    }
+}
+
+fn integer_swap_bytes_unaligned(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+    // 24 bits of message, bytes straddle blocks
+    let num_blocks = 24 / param.message_modulus.0.ilog2() as usize;
+    let cks = RadixClientKey::from((cks, num_blocks));
+
+    let mut rng = rand::thread_rng();
+
+    for _ in 0..NB_TEST_SMALLER {
+        let clear = rng.gen::<u32>() % (1 << 24);
+
+        let ct = cks.encrypt(clear as u64);
+
+        let ct_res = sks.swap_bytes_parallelized(&ct);
+        assert!(ct_res.block_carries_are_empty());
+        let dec_res: u64 = cks.decrypt(&ct_res);
+        assert_eq!((clear.swap_bytes() >> 8) as u64, dec_res);
+    }
+}
+
+/// Cleartext Morton code of (x, y), bit i of x going to bit 2 * i and bit i of y to bit 2 * i + 1
+fn interleave_bits_helper(x: u64, y: u64, nb_bits: u32) -> u64 {
+    (0..nb_bits)
+        .map(|i| (((x >> i) & 1) << (2 * i)) | (((y >> i) & 1) << (2 * i + 1)))
+        .sum()
+}
+
+fn integer_interleave_bits(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+    let cks = RadixClientKey::from((cks, NB_CTXT));
+
+    let mut rng = rand::thread_rng();
+
+    // message_modulus^vec_length
+    let modulus = param.message_modulus.0.pow(NB_CTXT as u32) as u64;
+    let nb_bits = modulus.ilog2();
+
+    for _ in 0..NB_TEST_SMALLER {
+        let clear_x = rng.gen::<u64>() % modulus;
+        let clear_y = rng.gen::<u64>() % modulus;
+
+        let ct_x = cks.encrypt(clear_x);
+        let ct_y = cks.encrypt(clear_y);
+
+        let ct_res = sks.interleave_bits_parallelized(&ct_x, &ct_y);
+        assert!(ct_res.block_carries_are_empty());
+        assert_eq!(ct_res.blocks.len(), 2 * NB_CTXT);
+        let dec_res: u64 = cks.decrypt(&ct_res);
+        assert_eq!(interleave_bits_helper(clear_x, clear_y, nb_bits), dec_res);
+
+        let (ct_even, ct_odd) = sks.deinterleave_bits_parallelized(&ct_res);
+        assert!(ct_even.block_carries_are_empty());
+        assert!(ct_odd.block_carries_are_empty());
+        assert_eq!(ct_even.blocks.len(), NB_CTXT);
+        assert_eq!(ct_odd.blocks.len(), NB_CTXT);
+        let dec_even: u64 = cks.decrypt(&ct_even);
+        let dec_odd: u64 = cks.decrypt(&ct_odd);
+        assert_eq!(clear_x, dec_even);
+        assert_eq!(clear_y, dec_odd);
+    }
+}
+
+fn integer_conditional_negate(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+    let cks = RadixClientKey::from((cks, NB_CTXT));
+
+    //RNG
+    let mut rng = rand::thread_rng();
+
+    // message_modulus^vec_length
+    let modulus = param.message_modulus.0.pow(NB_CTXT as u32) as u64;
+
+    for _ in 0..NB_TEST_SMALLER {
+        let clear = rng.gen::<u64>() % modulus;
+        let ctxt = cks.encrypt(clear);
+
+        for clear_condition in [false, true] {
+            let ct_condition = cks.encrypt(clear_condition as u64);
+
+            let ct_res = sks.conditional_negate_parallelized(&ctxt, &ct_condition);
+            assert!(ct_res.block_carries_are_empty());
+
+            let dec: u64 = cks.decrypt(&ct_res);
+            let expected = if clear_condition {
+                clear.wrapping_neg() % modulus
+            } else {
+                clear
+            };
+            assert_eq!(expected, dec);
+        }
+    }
+}
+
+fn integer_smart_neg(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+    let cks = RadixClientKey::from((cks, NB_CTXT));
+
+    //RNG
+    let mut rng = rand::thread_rng();
+
+    // message_modulus^vec_length
+    let modulus = param.message_modulus.0.pow(NB_CTXT as u32) as u64;
+
+    for _ in 0..NB_TEST {
+        // Define the cleartexts
+        let clear = rng.gen::<u64>() % modulus;
+
+        // Encrypt the integers
+        let mut ctxt = cks.encrypt(clear);
+
+        // Negates the ctxt
+        let ct_tmp = sks.smart_neg_parallelized(&mut ctxt);
+
+        // Decrypt the result
+        let dec: u64 = cks.decrypt(&ct_tmp);
+
+        // Check the correctness
+        let clear_result = clear.wrapping_neg() % modulus;
+
+        assert_eq!(clear_result, dec);
+    }
+}
+
+fn integer_default_neg(param: PBSParameters) {
     let (cks, sks) = KEY_CACHE.get_from_params(param);
     let cks = RadixClientKey::from((cks, NB_CTXT));
 
@@ -1501,6 +2118,32 @@ fn integer_default_neg(param: PBSParameters) {
     }
 }
 
+fn integer_default_neg_add_is_zero(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+    let cks = RadixClientKey::from((cks, NB_CTXT));
+
+    //RNG
+    let mut rng = rand::thread_rng();
+
+    // message_modulus^vec_length
+    let modulus = param.message_modulus.0.pow(NB_CTXT as u32) as u64;
+
+    for _ in 0..NB_TEST_SMALLER {
+        let clear = rng.gen::<u64>() % modulus;
+
+        let ctxt = cks.encrypt(clear);
+
+        let mut ct_neg = ctxt.clone();
+        sks.neg_assign_parallelized(&mut ct_neg);
+        assert!(ct_neg.block_carries_are_empty());
+
+        let ct_res = sks.add_parallelized(&ctxt, &ct_neg);
+
+        let dec: u64 = cks.decrypt(&ct_res);
+        assert_eq!(0, dec);
+    }
+}
+
 fn integer_smart_sub(param: PBSParameters) {
     let (cks, sks) = KEY_CACHE.get_from_params(param);
     let cks = RadixClientKey::from((cks, NB_CTXT));
@@ -1757,6 +2400,276 @@ fn integer_smart_mul(param: PBSParameters) {
     }
 }
 
+fn integer_overflowing_add(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+    let cks = RadixClientKey::from((cks, NB_CTXT));
+
+    //RNG
+    let mut rng = rand::thread_rng();
+
+    // message_modulus^vec_length
+    let modulus = param.message_modulus.0.pow(NB_CTXT as u32) as u64;
+
+    for _ in 0..NB_TEST_SMALLER {
+        let clear1 = rng.gen::<u64>() % modulus;
+        let clear2 = rng.gen::<u64>() % modulus;
+
+        let ctxt_1 = cks.encrypt(clear1);
+        let ctxt_2 = cks.encrypt(clear2);
+
+        let (ct_res, ct_overflow) = sks.overflowing_add_parallelized(&ctxt_1, &ctxt_2);
+        assert!(ct_res.block_carries_are_empty());
+        assert_eq!(ct_res.blocks.len(), NB_CTXT);
+
+        let dec_res: u64 = cks.decrypt(&ct_res);
+        let dec_overflow = cks.decrypt_one_block(&ct_overflow);
+        assert_eq!((clear1 + clear2) % modulus, dec_res);
+        assert_eq!((clear1 + clear2 >= modulus) as u64, dec_overflow);
+    }
+
+    // An input with non empty carries, encrypting the largest value
+    let mut ctxt_1 = cks.encrypt(modulus - 2);
+    let ctxt_2 = cks.encrypt(1u64);
+    let ct_sum = sks.smart_add_parallelized(&mut ctxt_1, &mut ctxt_2.clone());
+    assert!(!ct_sum.block_carries_are_empty());
+    let (ct_res, ct_overflow) = sks.overflowing_add_parallelized(&ct_sum, &ctxt_2);
+    let dec_res: u64 = cks.decrypt(&ct_res);
+    assert_eq!(0, dec_res);
+    assert_eq!(1, cks.decrypt_one_block(&ct_overflow));
+}
+
+fn integer_saturating_add(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+    let cks = RadixClientKey::from((cks, NB_CTXT));
+
+    //RNG
+    let mut rng = rand::thread_rng();
+
+    // message_modulus^vec_length
+    let modulus = param.message_modulus.0.pow(NB_CTXT as u32) as u64;
+
+    for _ in 0..NB_TEST_SMALLER {
+        let clear1 = rng.gen::<u64>() % modulus;
+        let clear2 = rng.gen::<u64>() % modulus;
+
+        let ctxt_1 = cks.encrypt(clear1);
+        let ctxt_2 = cks.encrypt(clear2);
+
+        let ct_res = sks.saturating_add_parallelized(&ctxt_1, &ctxt_2);
+        assert!(ct_res.block_carries_are_empty());
+        assert_eq!(ct_res.blocks.len(), NB_CTXT);
+
+        let dec_res: u64 = cks.decrypt(&ct_res);
+        assert_eq!((clear1 + clear2).min(modulus - 1), dec_res);
+    }
+
+    // Sums just below, at and just above the largest value
+    for (clear1, clear2) in [(modulus - 2, 0), (modulus - 2, 1), (modulus - 1, 1)] {
+        let ctxt_1 = cks.encrypt(clear1);
+        let ctxt_2 = cks.encrypt(clear2);
+
+        let ct_res = sks.saturating_add_parallelized(&ctxt_1, &ctxt_2);
+        let dec_res: u64 = cks.decrypt(&ct_res);
+        assert_eq!((clear1 + clear2).min(modulus - 1), dec_res);
+    }
+}
+
+fn integer_unchecked_sum_ciphertexts(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+    let cks = RadixClientKey::from((cks, NB_CTXT));
+
+    //RNG
+    let mut rng = rand::thread_rng();
+
+    // message_modulus^vec_length
+    let modulus = param.message_modulus.0.pow(NB_CTXT as u32) as u64;
+
+    const NB_OPERANDS: usize = 16;
+
+    for _ in 0..NB_TEST_SMALLER {
+        let clears = (0..NB_OPERANDS)
+            .map(|_| rng.gen::<u64>() % modulus)
+            .collect::<Vec<_>>();
+        let ctxts = clears.iter().map(|c| cks.encrypt(*c)).collect::<Vec<_>>();
+
+        let ct_res = sks.unchecked_sum_ciphertexts_parallelized(&ctxts).unwrap();
+        let ct_expected = sks
+            .default_binary_op_seq_parallelized(&ctxts, ServerKey::add_parallelized)
+            .unwrap();
+
+        assert!(ct_res.block_carries_are_empty());
+        assert_eq!(ct_res.blocks.len(), NB_CTXT);
+
+        let dec_res: u64 = cks.decrypt(&ct_res);
+        let dec_expected: u64 = cks.decrypt(&ct_expected);
+        assert_eq!(clears.iter().sum::<u64>() % modulus, dec_res);
+        assert_eq!(dec_expected, dec_res);
+    }
+
+    // Edge cases
+    assert!(sks
+        .unchecked_sum_ciphertexts_parallelized(&[] as &[RadixCiphertextBig])
+        .is_none());
+    let clear = rng.gen::<u64>() % modulus;
+    let ct_res = sks
+        .unchecked_sum_ciphertexts_parallelized(&[cks.encrypt(clear)])
+        .unwrap();
+    let dec_res: u64 = cks.decrypt(&ct_res);
+    assert_eq!(clear, dec_res);
+    let empty = RadixCiphertextBig::from(vec![]);
+    let ct_res = sks
+        .unchecked_sum_ciphertexts_parallelized(&[empty.clone(), empty.clone(), empty])
+        .unwrap();
+    assert!(ct_res.blocks.is_empty());
+}
+
+fn integer_overflowing_mul(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+    let cks = RadixClientKey::from((cks, NB_CTXT));
+
+    //RNG
+    let mut rng = rand::thread_rng();
+
+    // message_modulus^vec_length
+    let modulus = param.message_modulus.0.pow(NB_CTXT as u32) as u64;
+
+    for i in 0..NB_TEST_SMALLER {
+        // Define the cleartexts, every other test uses a single block rhs so that both
+        // overflowing and non overflowing products are checked
+        let clear1 = rng.gen::<u64>() % modulus;
+        let clear2 = if i % 2 == 0 {
+            rng.gen::<u64>() % modulus
+        } else {
+            rng.gen::<u64>() % param.message_modulus.0 as u64
+        };
+
+        // Encrypt the integers
+        let ctxt_1 = cks.encrypt(clear1);
+        let ctxt_2 = cks.encrypt(clear2);
+
+        let (ct_res, ct_overflow) = sks.overflowing_mul_parallelized(&ctxt_1, &ctxt_2);
+        assert!(ct_res.block_carries_are_empty());
+        assert_eq!(ct_res.blocks.len(), NB_CTXT);
+
+        let dec_res: u64 = cks.decrypt(&ct_res);
+        let dec_overflow: u64 = cks.decrypt(&ct_overflow);
+
+        // Check the correctness, against a checked multiplication on the truncated width
+        let expected = clear1
+            .checked_mul(clear2)
+            .filter(|product| *product < modulus);
+        assert_eq!((clear1 * clear2) % modulus, dec_res);
+        assert_eq!(expected.is_none() as u64, dec_overflow);
+    }
+}
+
+fn integer_scalar_pow(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+    let cks = RadixClientKey::from((cks, NB_CTXT));
+
+    //RNG
+    let mut rng = rand::thread_rng();
+
+    // message_modulus^vec_length
+    let modulus = param.message_modulus.0.pow(NB_CTXT as u32) as u64;
+
+    for _ in 0..NB_TEST_SMALLER {
+        // Small bases, so that some of the powers don't wrap around
+        let clear = rng.gen::<u64>() % 8;
+        let exp = rng.gen::<u64>() % 12;
+
+        let ctxt = cks.encrypt(clear);
+
+        let ct_res = sks.scalar_pow_parallelized(&ctxt, exp);
+        assert!(ct_res.block_carries_are_empty());
+
+        let dec: u64 = cks.decrypt(&ct_res);
+        assert_eq!(clear.pow(exp as u32) % modulus, dec);
+    }
+}
+
+fn integer_sum_of_squares(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+    let cks = RadixClientKey::from((cks, NB_CTXT));
+
+    //RNG
+    let mut rng = rand::thread_rng();
+
+    // message_modulus^vec_length
+    let modulus = param.message_modulus.0.pow(NB_CTXT as u32) as u64;
+
+    let no_cts: [RadixCiphertextBig; 0] = [];
+    assert!(sks.sum_of_squares_parallelized(&no_cts).is_none());
+
+    for num_terms in 1..=4 {
+        // Values close to the modulus, so that the squares and their sum need the widening
+        let clears = (0..num_terms)
+            .map(|_| modulus - 1 - rng.gen::<u64>() % 4)
+            .collect::<Vec<_>>();
+        let ctxts = clears.iter().map(|c| cks.encrypt(*c)).collect::<Vec<_>>();
+
+        let ct_res = sks.sum_of_squares_parallelized(&ctxts).unwrap();
+        assert!(ct_res.block_carries_are_empty());
+
+        let dec: u64 = cks.decrypt(&ct_res);
+        assert_eq!(clears.iter().map(|c| c * c).sum::<u64>(), dec);
+    }
+}
+
+fn integer_scalar_div(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+    let cks = RadixClientKey::from((cks, NB_CTXT));
+
+    //RNG
+    let mut rng = rand::thread_rng();
+
+    // message_modulus^vec_length
+    let modulus = param.message_modulus.0.pow(NB_CTXT as u32) as u64;
+
+    for _ in 0..NB_TEST_SMALLER {
+        let clear = rng.gen::<u64>() % modulus;
+        // Also test divisors larger than the value, and than the modulus
+        let divisor = rng.gen_range(1..=2 * modulus);
+
+        let ctxt = cks.encrypt(clear);
+
+        let ct_res = sks.scalar_div_parallelized(&ctxt, divisor);
+        assert!(ct_res.block_carries_are_empty());
+
+        let dec: u64 = cks.decrypt(&ct_res);
+        assert_eq!(clear / divisor, dec);
+    }
+}
+
+fn integer_mean(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+    let cks = RadixClientKey::from((cks, NB_CTXT));
+
+    //RNG
+    let mut rng = rand::thread_rng();
+
+    // message_modulus^vec_length
+    let modulus = param.message_modulus.0.pow(NB_CTXT as u32) as u64;
+
+    let no_cts: [RadixCiphertextBig; 0] = [];
+    assert!(sks.mean_parallelized(&no_cts).is_none());
+
+    for num_terms in 1..=5 {
+        let clears = (0..num_terms)
+            .map(|_| rng.gen::<u64>() % modulus)
+            .collect::<Vec<_>>();
+        let ctxts = clears.iter().map(|c| cks.encrypt(*c)).collect::<Vec<_>>();
+
+        let ct_res = sks.mean_parallelized(&ctxts).unwrap();
+        assert_eq!(NB_CTXT, ct_res.blocks.len());
+        assert!(ct_res.block_carries_are_empty());
+
+        // The sum is computed without wrapping around, then truncated by the division
+        let dec: u64 = cks.decrypt(&ct_res);
+        assert_eq!(clears.iter().sum::<u64>() / num_terms, dec);
+    }
+}
+
 fn integer_default_mul(param: PBSParameters) {
     let (cks, sks) = KEY_CACHE.get_from_params(param);
     let cks = RadixClientKey::from((cks, NB_CTXT));
@@ -1970,3 +2883,196 @@ fn integer_default_scalar_sub(param: PBSParameters) {
         }
     }
 }
+
+fn integer_with_server_key_ops(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+    let cks = RadixClientKey::from((cks, NB_CTXT));
+
+    //RNG
+    let mut rng = rand::thread_rng();
+
+    // message_modulus^vec_length
+    let modulus = param.message_modulus.0.pow(NB_CTXT as u32) as u64;
+
+    for _ in 0..NB_TEST_SMALLER {
+        let clear_0 = rng.gen::<u64>() % modulus;
+        let clear_1 = rng.gen::<u64>() % modulus;
+
+        let ctxt_0 = sks.with_ciphertext(cks.encrypt(clear_0));
+        let ctxt_1 = sks.with_ciphertext(cks.encrypt(clear_1));
+
+        let ct_res = &ctxt_0 + &ctxt_1;
+        assert_eq!(
+            ct_res.ciphertext(),
+            &sks.add_parallelized(ctxt_0.ciphertext(), ctxt_1.ciphertext())
+        );
+        let dec: u64 = cks.decrypt(ct_res.ciphertext());
+        assert_eq!((clear_0 + clear_1) % modulus, dec);
+
+        let ct_res = &ctxt_0 - ctxt_1.ciphertext();
+        let dec: u64 = cks.decrypt(ct_res.ciphertext());
+        assert_eq!(clear_0.wrapping_sub(clear_1) % modulus, dec);
+
+        let ct_res = &ctxt_0 * &ctxt_1;
+        let dec: u64 = cks.decrypt(ct_res.ciphertext());
+        assert_eq!((clear_0 * clear_1) % modulus, dec);
+
+        let mut ct_res = ctxt_0.clone();
+        ct_res += &ctxt_1;
+        ct_res -= ctxt_0.ciphertext();
+        ct_res *= &ctxt_1;
+        assert!(ct_res.ciphertext().block_carries_are_empty());
+        let dec: u64 = cks.decrypt(&ct_res.into_inner());
+        assert_eq!((clear_1 * clear_1) % modulus, dec);
+    }
+}
+
+fn integer_argmax(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+    let cks = RadixClientKey::from((cks, NB_CTXT));
+
+    //RNG
+    let mut rng = rand::thread_rng();
+
+    // message_modulus^vec_length
+    let modulus = param.message_modulus.0.pow(NB_CTXT as u32) as u64;
+
+    let no_cts: [RadixCiphertextBig; 0] = [];
+    assert!(sks.argmax_parallelized(&no_cts).is_none());
+
+    for _ in 0..NB_TEST_SMALLER {
+        let num_cts = rng.gen_range(1..=5);
+        let mut clears = (0..num_cts)
+            .map(|_| rng.gen::<u64>() % modulus)
+            .collect::<Vec<_>>();
+        // Force a tie on the maximum
+        let max = *clears.iter().max().unwrap();
+        clears.push(max);
+
+        let cts = clears
+            .iter()
+            .map(|clear| cks.encrypt(*clear))
+            .collect::<Vec<_>>();
+
+        let ct_res = sks.argmax_parallelized(&cts).unwrap();
+        assert!(ct_res.block_carries_are_empty());
+
+        let expected = clears.iter().position(|clear| *clear == max).unwrap() as u64;
+        let dec: u64 = cks.decrypt(&ct_res);
+        assert_eq!(expected, dec);
+    }
+}
+
+fn integer_is_sorted(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+    let cks = RadixClientKey::from((cks, NB_CTXT));
+
+    //RNG
+    let mut rng = rand::thread_rng();
+
+    // message_modulus^vec_length
+    let modulus = param.message_modulus.0.pow(NB_CTXT as u32) as u64;
+
+    let no_cts: [RadixCiphertextBig; 0] = [];
+    assert!(sks.is_sorted_parallelized(&no_cts).is_none());
+
+    for _ in 0..NB_TEST_SMALLER {
+        let num_cts = rng.gen_range(1..=5);
+        let mut sorted = (0..num_cts)
+            .map(|_| rng.gen::<u64>() % modulus)
+            .collect::<Vec<_>>();
+        sorted.sort_unstable();
+
+        let mut reversed = sorted.clone();
+        reversed.reverse();
+
+        let mut swapped = sorted.clone();
+        let i = rng.gen_range(0..num_cts);
+        let j = rng.gen_range(0..num_cts);
+        swapped.swap(i, j);
+
+        for clears in [sorted, reversed, swapped] {
+            let cts = clears
+                .iter()
+                .map(|clear| cks.encrypt(*clear))
+                .collect::<Vec<_>>();
+
+            let ct_res = sks.is_sorted_parallelized(&cts).unwrap();
+            assert!(ct_res.block_carries_are_empty());
+
+            let expected = clears.windows(2).all(|pair| pair[0] <= pair[1]) as u64;
+            let dec: u64 = cks.decrypt(&ct_res);
+            assert_eq!(expected, dec);
+        }
+    }
+}
+
+fn integer_select(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+    let cks = RadixClientKey::from((cks, NB_CTXT));
+
+    //RNG
+    let mut rng = rand::thread_rng();
+
+    // message_modulus^vec_length
+    let modulus = param.message_modulus.0.pow(NB_CTXT as u32) as u64;
+
+    let no_options: [RadixCiphertextBig; 0] = [];
+    let ct_index = cks.encrypt(0u64);
+    assert!(sks.select_parallelized(&no_options, &ct_index).is_none());
+
+    let num_options = 6;
+    let clears = (0..num_options)
+        .map(|_| rng.gen::<u64>() % modulus)
+        .collect::<Vec<_>>();
+    let options = clears
+        .iter()
+        .map(|clear| cks.encrypt(*clear))
+        .collect::<Vec<_>>();
+
+    // Indices past the last option select nothing
+    for index in 0..num_options as u64 + 2 {
+        let ct_index = cks.encrypt(index);
+
+        let ct_res = sks.select_parallelized(&options, &ct_index).unwrap();
+        assert!(ct_res.block_carries_are_empty());
+
+        let expected = clears.get(index as usize).copied().unwrap_or(0);
+        let dec: u64 = cks.decrypt(&ct_res);
+        assert_eq!(expected, dec);
+    }
+}
+
+fn integer_clamp(param: PBSParameters) {
+    let (cks, sks) = KEY_CACHE.get_from_params(param);
+    let cks = RadixClientKey::from((cks, NB_CTXT));
+
+    //RNG
+    let mut rng = rand::thread_rng();
+
+    // message_modulus^vec_length
+    let modulus = param.message_modulus.0.pow(NB_CTXT as u32) as u64;
+
+    for _ in 0..NB_TEST_SMALLER {
+        let clear = rng.gen::<u64>() % modulus;
+        let clear_lo = rng.gen::<u64>() % modulus;
+        let clear_hi = rng.gen::<u64>() % modulus;
+
+        let ctxt = cks.encrypt(clear);
+        let ctxt_lo = cks.encrypt(clear_lo);
+        let ctxt_hi = cks.encrypt(clear_hi);
+
+        // Covers lo <= hi, lo > hi and the value below, inside or above the bounds
+        let ct_res = sks.clamp_parallelized(&ctxt, &ctxt_lo, &ctxt_hi);
+        assert!(ct_res.block_carries_are_empty());
+
+        let expected = clear.max(clear_lo).min(clear_hi);
+        let dec: u64 = cks.decrypt(&ct_res);
+        assert_eq!(expected, dec);
+
+        // Equal bounds
+        let ct_res = sks.clamp_parallelized(&ctxt, &ctxt_lo, &ctxt_lo);
+        let dec: u64 = cks.decrypt(&ct_res);
+        assert_eq!(clear_lo, dec);
+    }
+}